@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use unreal_doc::ast::unreal_cpp_header::fuzz_unreal_cpp_header;
+
+fuzz_target!(|data: &[u8]| {
+    fuzz_unreal_cpp_header(data);
+});
@@ -0,0 +1,9 @@
+#include "PickupExample.h"
+
+void APickupSpawner::SpawnPickup()
+{
+	//// [snippet: spawn_pickup]
+	ATestPickup* Pickup = GetWorld()->SpawnActor<ATestPickup>(PickupClass, GetActorTransform());
+	Pickup->Count = 3;
+	//// [/snippet]
+}
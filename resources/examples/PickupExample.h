@@ -0,0 +1,16 @@
+#pragma once
+
+#include "GameFramework/Actor.h"
+#include "PickupExample.generated.h"
+
+UCLASS()
+class APickupSpawner : public AActor
+{
+	GENERATED_BODY()
+
+public:
+	UPROPERTY(EditAnywhere)
+	TSubclassOf<ATestPickup> PickupClass;
+
+	void SpawnPickup();
+};
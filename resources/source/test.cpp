@@ -0,0 +1,11 @@
+#include "test.h"
+
+#include "Net/UnrealNetwork.h"
+
+void ATestPickup::GetLifetimeReplicatedProps(TArray<FLifetimeProperty>& OutLifetimeProps) const
+{
+	Super::GetLifetimeReplicatedProps(OutLifetimeProps);
+
+	DOREPLIFETIME_CONDITION_NOTIFY(ATestPickup, Count, COND_None, REPNOTIFY_Always);
+	DOREPLIFETIME_CONDITION(ATestPickup, Instigator, COND_OwnerOnly);
+}
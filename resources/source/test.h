@@ -22,12 +22,26 @@ enum class Something : uint8
 	B
 };
 
+/// Quality levels selectable in settings menu.
+UENUM(BlueprintType)
+enum class EQualityLevel : uint8
+{
+	Low = 0 UMETA(DisplayName = "Performance"),
+	High UMETA(DisplayName = "Best Looking"),
+	Max UMETA(Hidden)
+};
+
 /// Description of struct
 ///
 /// More information and examples.
 ///
 /// [`struct: Self::Foo`]()
 /// [`struct: Self::A`]()
+/// [`enum: Something::B`]()
+/// [`property: Foo::A`]()
+/// [`method: Foo::Foo`]()
+/// [`method: Foo::A`]()
+/// [`class: ALegacyPickup`]()
 USTRUCT(BlueprintType, Meta = (Foo = Bar))
 template <typename T>
 struct BAR Foo : public Bar
@@ -148,23 +162,261 @@ struct Who
 };
 
 /// Test blueprint library.
-UCLASS()
-class UTestLibrary : public UBlueprintFunctionLibrary
+UCLASS(Abstract)
+class TEST_API UTestLibrary final : public UBlueprintFunctionLibrary
 {
 	GENERATED_BODY()
 
 public:
 #pragma region
 	/// Test blueprint function.
+	///
+	/// @param InFirstDelay [s] Delay before first call.
 	UFUNCTION(BlueprintCallable, Category = "TestLibrary")
 	static void SetXX(
 		const UObject* WCO,
 		FTimerHandle& InOutHandle,
 		/// Function type signature support.
 		TFunction<void(int, float)>&& Callback,
+		/// [s] Time between calls.
 		float Internal,
 		bool InbLoop,
 		float InFirstDelay = 1.f,
 	);
+
+	/// Moves location by offset.
+	UFUNCTION(BlueprintCallable, Category = "TestLibrary")
+	static void AddOffset(UPARAM(ref) FVector& Location, FVector Offset, FVector& OutPrevious);
 #pragma endregion
-};
\ No newline at end of file
+};
+/// Container and smart pointer types.
+USTRUCT(BlueprintType)
+struct FTextureSet
+{
+	GENERATED_BODY()
+
+	/// Textures loaded on demand.
+	UPROPERTY(EditAnywhere, AssetRegistrySearchable, meta = (AssetBundles = "Client,Explore"))
+	TArray<TSoftObjectPtr<UTexture2D>> Textures;
+
+	/// Spawnable actor classes by name.
+	UPROPERTY(EditAnywhere)
+	TMap<FName, TSubclassOf<AActor>> Spawnables;
+
+	/// Enables custom streaming radius.
+	UPROPERTY(EditAnywhere)
+	bool bUseCustomRadius = false;
+
+	/// Radius of texture streaming.
+	UPROPERTY(EditAnywhere, meta = (EditCondition = "bUseCustomRadius", Units = "cm", ClampMin = 0, ClampMax = 1000, UIMin = 10, UIMax = 500))
+	float StreamingRadius = 100.0f;
+
+	/// Number of texture streaming requests.
+	UPROPERTY(BlueprintGetter = GetRequestCount, BlueprintSetter = SetRequestCount)
+	int32 RequestCount = 0;
+
+	/// Blueprint accessor of request count.
+	UFUNCTION(BlueprintGetter)
+	int32 GetRequestCount() const;
+
+	/// Blueprint mutator of request count.
+	///
+	/// @throws FTextureSet Never, only to check exception references.
+	UFUNCTION(BlueprintSetter)
+	void SetRequestCount(int32 InRequestCount);
+
+	// Legacy style comment.
+	// Documented only when informal comments are enabled.
+	void Refresh();
+
+	/// Moves textures out of temporary sets.
+	TArray<TSoftObjectPtr<UTexture2D>> TakeTextures() &&;
+
+	/// Copies textures into given array.
+	void GetTextures(TArray<TSoftObjectPtr<UTexture2D>>& OutTextures) const &;
+};
+
+/// Test cheats.
+UCLASS()
+class TEST_API UTestCheatManager : public UCheatManager
+{
+	GENERATED_BODY()
+
+public:
+	/// Gives items to local player.
+	///
+	/// @fails Does nothing when item name is unknown.
+	UFUNCTION(Exec)
+	void GiveItems(FName ItemName, int32 Count = 1);
+};
+
+/// Callback and container types with nested templates.
+struct FCallbacks
+{
+	/// Callback with multiple arguments.
+	TFunction<void(int32, float)> OnChanged;
+
+	/// Callback returning template type.
+	TFunction<TArray<int32>(int32)> Producer;
+
+	/// Const-callable unique function.
+	TUniqueFunction<void() const> Finalizer;
+
+	/// Array with inline allocator.
+	TArray<int32, TInlineAllocator<4>> Indices;
+
+	/// Fixed size array.
+	TStaticArray<float, 4> Weights;
+
+	/// Raw function pointer.
+	void (*Handler)(int32);
+
+	/// Member function pointer.
+	void (UObject::*Method)() = nullptr;
+
+	friend class FTextureSet;
+	friend bool operator==(const FCallbacks& Lhs, const FCallbacks& Rhs);
+
+	/// Callbacks are created empty.
+	FCallbacks() = default;
+
+	/// Callbacks are not copyable.
+	FCallbacks(const FCallbacks& Other) = delete;
+
+	/// Unbinds callbacks.
+	virtual ~FCallbacks() = default;
+
+	/// Callbacks seeded with initial value.
+	explicit FCallbacks(int32 Seed);
+
+	/// Tells if any callback is bound.
+	explicit operator bool() const;
+
+	/// Invokes change callback.
+	void operator()(int32 Value) const;
+
+	/// Compares bound handlers.
+	bool operator!=(const FCallbacks& Other) const;
+};
+
+/// Movement tuning with defaults set in constructor.
+///
+/// @renamed-from FMoveSettings in 1.1
+USTRUCT(BlueprintType)
+struct FMovementTuning
+{
+	GENERATED_BODY()
+
+	FMovementTuning()
+	{
+		//// [ignore]
+		MaxSpeed = 600.f;
+		this->Acceleration = 2048.f;
+		//// [/ignore]
+	}
+
+	/// Top speed.
+	UPROPERTY(EditAnywhere, Category = "Movement|Speed")
+	float MaxSpeed;
+
+	/// Acceleration rate.
+	UPROPERTY(EditAnywhere, Category = "movement | Speed|Rates")
+	float Acceleration;
+};
+
+/// Replicated pickup actor, available since Unreal Engine {{ENGINE_VERSION}}.
+///
+/// <alias>ALegacyPickup</alias>
+///
+/// @owner Gameplay Team
+///
+/// @experimental
+///
+/// @example PickupExample
+///
+/// @include shared/replication-warning.md
+///
+/// ```snippet
+/// spawn_pickup
+/// ```
+UCLASS()
+class TEST_API ATestPickup : public AActor
+{
+	GENERATED_BODY()
+
+public:
+	/// Number of items left.
+	UPROPERTY(ReplicatedUsing = OnRep_Count)
+	int32 Count = 1;
+
+	/// Player that picked this up.
+	UPROPERTY(Replicated)
+	APawn* Instigator = nullptr;
+
+	/// Reacts on replicated count change.
+	UFUNCTION()
+	void OnRep_Count();
+};
+
+/// Test widget with designer bindings.
+UCLASS(Abstract)
+class TEST_API UTestWidget : public UUserWidget
+{
+	GENERATED_BODY()
+
+protected:
+	/// Title text.
+	UPROPERTY(meta = (BindWidget))
+	UTextBlock* Title;
+
+	/// Optional close button.
+	UPROPERTY(meta = (BindWidgetOptional))
+	TObjectPtr<UButton> CloseButton;
+
+	/// Animation played on show.
+	UPROPERTY(Transient, meta = (BindWidgetAnim))
+	UWidgetAnimation* FadeIn;
+};
+
+/// Color packed into single integer.
+struct FPackedColor
+{
+	union
+	{
+		struct
+		{
+			/// Red channel.
+			uint8 R;
+
+			/// Green channel.
+			uint8 G;
+		};
+
+		/// All channels packed together.
+		uint32 Bits;
+	};
+
+	/// Alpha is stored separately.
+	uint8 A;
+};
+
+/// Thing player can interact with.
+UINTERFACE(MinimalAPI, Blueprintable)
+class UInteractable : public UInterface
+{
+	GENERATED_UINTERFACE_BODY()
+};
+
+class IInteractable
+{
+	GENERATED_IINTERFACE_BODY()
+
+public:
+	/// Called when player interacts.
+	UFUNCTION(BlueprintNativeEvent, BlueprintCallable)
+	void Interact(AActor* Instigator);
+	virtual void Interact_Implementation(AActor* Instigator);
+
+	/// Tells if thing can be used now.
+	virtual bool CanInteract() const = 0;
+};
@@ -1,5 +1,6 @@
-use crate::{config::Settings, document::*};
+use crate::{config::ParseSettings, document::*, plugins::ElementPlugins};
 use pest::{error::Error, iterators::Pair, Parser};
+use regex::Regex;
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -11,57 +12,108 @@ pub struct UnrealCppHeaderParser;
 pub fn parse_unreal_cpp_header(
     content: &str,
     document: &mut Document,
-    settings: &Settings,
+    settings: &ParseSettings,
+    plugins: &mut ElementPlugins,
     path: &Path
 ) -> Result<(), Error<Rule>> {
     let pair = UnrealCppHeaderParser::parse(Rule::file, content)?
         .next()
         .unwrap();
+    let structs_count = document.structs.len();
+    let classes_count = document.classes.len();
+    let functions_count = document.functions.len();
     if pair.as_rule() == Rule::file {
-        let filename = path.file_name().unwrap().to_str().unwrap();
-        parse_file(pair, document, settings, filename);
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        parse_file(pair, document, settings, plugins, &filename)?;
+    }
+    document.pair_interfaces();
+    let editor_path = path
+        .components()
+        .any(|component| component.as_os_str() == "Editor");
+    let api_macro = settings.api_macro_regex();
+    for item in document.structs[structs_count..]
+        .iter_mut()
+        .chain(document.classes[classes_count..].iter_mut())
+    {
+        item.module = api_module(item.api.as_deref(), &api_macro);
+        // members of exported types belong to their module, even without own export macros
+        for method in &mut item.methods {
+            method.module =
+                api_module(method.api.as_deref(), &api_macro).or_else(|| item.module.to_owned());
+        }
+        item.is_editor_only = editor_path
+            || item
+                .module
+                .as_ref()
+                .is_some_and(|module| module.to_ascii_uppercase().ends_with("EDITOR"));
+    }
+    for item in &mut document.functions[functions_count..] {
+        item.module = api_module(item.api.as_deref(), &api_macro);
     }
     Ok(())
 }
 
+/// Module name of the first of given export macros that matches API macro pattern, taken from
+/// its first capture group (or whole macro when pattern has no groups).
+fn api_module(api: Option<&str>, pattern: &Regex) -> Option<String> {
+    api?.split_whitespace().find_map(|token| {
+        let captures = pattern.captures(token)?;
+        let module = captures.get(1).or_else(|| captures.get(0))?;
+        Some(module.as_str().to_owned())
+    })
+}
+
+#[allow(clippy::result_large_err)]
 fn parse_unreal_cpp_element(
     content: &str,
     document: &mut Document,
-    settings: &Settings,
+    settings: &ParseSettings,
     filename: &str
-) -> Element {
-    let pair = UnrealCppHeaderParser::parse(Rule::element, content)
-        .unwrap_or_else(|error| {
-            panic!(
-                "Could not parse Unreal C++ element content!\nError:\n{}",
-                error
-            )
-        })
+) -> Result<Element, Error<Rule>> {
+    let pair = UnrealCppHeaderParser::parse(Rule::element, content)?
         .next()
         .unwrap();
     match pair.as_rule() {
-        Rule::element => parse_element(pair, Visibility::Public, settings, document, filename),
+        Rule::element => Ok(parse_element(
+            pair,
+            Visibility::Public,
+            settings,
+            document,
+            filename,
+        )),
         _ => unreachable!(),
     }
 }
 
-fn parse_file(pair: Pair<Rule>, document: &mut Document, settings: &Settings, filename: &str) {
+#[allow(clippy::result_large_err)]
+fn parse_file(
+    pair: Pair<Rule>,
+    document: &mut Document,
+    settings: &ParseSettings,
+    plugins: &mut ElementPlugins,
+    filename: &str,
+) -> Result<(), Error<Rule>> {
     for pair in pair.into_inner() {
         match pair.as_rule() {
-            Rule::proxy => parse_proxy(pair, settings, document, filename),
+            Rule::proxy => parse_proxy(pair, settings, document, filename)?,
+            Rule::namespace => parse_file(pair, document, settings, plugins, filename)?,
             Rule::snippet => parse_snippet(pair, document),
-            Rule::element => match parse_element(pair, Visibility::Public, settings, document, filename) {
-                Element::Enum(element) => {
-                    if element.can_export(settings) {
+            Rule::element => {
+                let line = pair.line_col().0;
+                let source = pair.as_str();
+                let element = parse_element(pair, Visibility::Public, settings, document, filename);
+                match apply_plugins(element, plugins, filename, line, source) {
+                    Element::Enum(element) => {
                         if document.enums.iter().any(|item| item.name == element.name) {
                             println!("Overwriting existing enum: {}", element.name);
                         }
                         document.enums.push(element)
                     }
-                }
-                Element::StructClass(element) => match element.mode {
-                    StructClassMode::Struct => {
-                        if element.can_export(settings) {
+                    Element::StructClass(element) => match element.mode {
+                        StructClassMode::Struct => {
                             if document
                                 .structs
                                 .iter()
@@ -71,9 +123,7 @@ fn parse_file(pair: Pair<Rule>, document: &mut Document, settings: &Settings, fi
                             }
                             document.structs.push(element)
                         }
-                    }
-                    StructClassMode::Class => {
-                        if element.can_export(settings) {
+                        StructClassMode::Class => {
                             if document
                                 .classes
                                 .iter()
@@ -83,10 +133,8 @@ fn parse_file(pair: Pair<Rule>, document: &mut Document, settings: &Settings, fi
                             }
                             document.classes.push(element)
                         }
-                    }
-                },
-                Element::Delegate(element) => {
-                    if element.can_export(settings) {
+                    },
+                    Element::Delegate(element) => {
                         if document
                             .delegates
                             .iter()
@@ -96,9 +144,7 @@ fn parse_file(pair: Pair<Rule>, document: &mut Document, settings: &Settings, fi
                         }
                         document.delegates.push(element);
                     }
-                },
-                Element::Function(element) => {
-                    if element.can_export(settings) {
+                    Element::Function(element) => {
                         if document
                             .functions
                             .iter()
@@ -108,15 +154,51 @@ fn parse_file(pair: Pair<Rule>, document: &mut Document, settings: &Settings, fi
                         }
                         document.functions.push(element)
                     }
-                },
-                _ => {}
-            },
+                    Element::Property(_) | Element::None => {}
+                }
+            }
             _ => {}
         }
     }
+    Ok(())
+}
+
+/// Passes top level symbol through extraction plugins, that can modify or drop it.
+fn apply_plugins(
+    element: Element,
+    plugins: &mut ElementPlugins,
+    filename: &str,
+    line: usize,
+    source: &str,
+) -> Element {
+    let element = match element {
+        Element::Enum(item) => plugins
+            .process("enum", item, filename, line, source)
+            .map(Element::Enum),
+        Element::StructClass(item) => {
+            let kind = item.mode.signature();
+            plugins
+                .process(&kind, item, filename, line, source)
+                .map(Element::StructClass)
+        }
+        Element::Function(item) => plugins
+            .process("function", item, filename, line, source)
+            .map(Element::Function),
+        Element::Delegate(item) => plugins
+            .process("delegate", item, filename, line, source)
+            .map(Element::Delegate),
+        element => Some(element),
+    };
+    element.unwrap_or(Element::None)
 }
 
-fn parse_proxy(pair: Pair<Rule>, settings: &Settings, document: &mut Document, filename: &str) {
+#[allow(clippy::result_large_err)]
+fn parse_proxy(
+    pair: Pair<Rule>,
+    settings: &ParseSettings,
+    document: &mut Document,
+    filename: &str,
+) -> Result<(), Error<Rule>> {
     let mut doc_comments = None;
     let mut tags = HashSet::new();
     let mut content = String::new();
@@ -132,7 +214,7 @@ fn parse_proxy(pair: Pair<Rule>, settings: &Settings, document: &mut Document, f
             _ => {}
         }
     }
-    match parse_unreal_cpp_element(&content, document, settings, filename) {
+    match parse_unreal_cpp_element(&content, document, settings, filename)? {
         Element::Function(mut item) => {
             if let Some(doc_comments) = doc_comments {
                 item.doc_comments = Some(doc_comments);
@@ -147,6 +229,7 @@ fn parse_proxy(pair: Pair<Rule>, settings: &Settings, document: &mut Document, f
         }
         _ => {}
     }
+    Ok(())
 }
 
 fn parse_snippet(pair: Pair<Rule>, document: &mut Document) {
@@ -176,7 +259,7 @@ fn parse_snippet_inner(pair: Pair<Rule>) -> String {
         .unwrap_or_default();
     pair.as_str()
         .lines()
-        .map(|line| line[level..].to_owned())
+        .map(|line| line.chars().skip(level).collect::<String>())
         .collect::<Vec<_>>()
         .join("\n")
 }
@@ -193,6 +276,77 @@ fn parse_doc_comments(pair: Pair<Rule>) -> String {
         .join("\n")
 }
 
+/// Collects ordinary `//` comment lines directly above the element, stopping at first line
+/// that is not such comment.
+fn parse_informal_comments(preceding: &str) -> Option<String> {
+    let mut lines = preceding.lines().rev();
+    // element might be preceded by indentation or other code in the same line.
+    if !preceding.ends_with('\n') && !lines.next().unwrap_or_default().trim().is_empty() {
+        return None;
+    }
+    let mut result = lines
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with("//") && !line.starts_with("///"))
+        .map(|line| line[2..].trim().to_owned())
+        .collect::<Vec<_>>();
+    if result.is_empty() {
+        return None;
+    }
+    result.reverse();
+    Some(result.join("\n"))
+}
+
+/// Source text of element declaration: everything up to opening brace of class, struct and enum
+/// bodies, and up to function body, so only declaration lines end up in excerpt.
+fn declaration_source<'a>(pair: &Pair<'a, Rule>) -> &'a str {
+    let content = pair.as_str();
+    let body = pair
+        .clone()
+        .into_inner()
+        .flat_map(|pair| pair.into_inner())
+        .find(|pair| {
+            matches!(
+                pair.as_rule(),
+                Rule::struct_class_body | Rule::enum_body | Rule::function_body
+            )
+        });
+    let Some(body) = body else {
+        return content;
+    };
+    let offset = body.as_span().start() - pair.as_span().start();
+    match content[..offset].rfind('{') {
+        Some(brace) if body.as_rule() == Rule::function_body => content[..brace].trim_end(),
+        Some(brace) => &content[..=brace],
+        None => content,
+    }
+}
+
+fn parse_source_excerpt(content: &str) -> String {
+    // first line starts right at the element, so only following lines carry indentation.
+    let mut lines = content.trim_end().lines();
+    let first = lines.next().unwrap_or_default();
+    let rest = lines.collect::<Vec<_>>();
+    // indentation is counted in characters, as it can contain non-ASCII whitespace
+    let level = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or_default();
+    std::iter::once(first)
+        .chain(rest.iter().map(|line| {
+            let indentation = line
+                .chars()
+                .take(level)
+                .take_while(|c| c.is_whitespace())
+                .map(char::len_utf8)
+                .sum::<usize>();
+            &line[indentation..]
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 enum Element {
     None,
     Enum(Enum),
@@ -205,12 +359,23 @@ enum Element {
 fn parse_element(
     pair: Pair<Rule>,
     visibility: Visibility,
-    settings: &Settings,
+    settings: &ParseSettings,
     document: &mut Document,
     filename: &str
 ) -> Element {
     let mut result = Element::None;
     let mut doc_comments = None;
+    let mut informal_docs = false;
+    let source = settings
+        .raw_header_view
+        .then(|| parse_source_excerpt(declaration_source(&pair)));
+    if settings.informal_comments
+        && pair.clone().into_inner().next().map(|pair| pair.as_rule())
+            != Some(Rule::doc_comment_lines)
+    {
+        doc_comments = parse_informal_comments(&pair.get_input()[..pair.as_span().start()]);
+        informal_docs = doc_comments.is_some();
+    }
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::doc_comment_lines => doc_comments = Some(parse_doc_comments(pair)),
@@ -282,6 +447,29 @@ fn parse_element(
             _ => {}
         }
     }
+    match &mut result {
+        Element::None => {}
+        Element::Enum(item) => {
+            item.source = source;
+            item.informal_docs = informal_docs;
+        }
+        Element::StructClass(item) => {
+            item.source = source;
+            item.informal_docs = informal_docs;
+        }
+        Element::Property(item) => {
+            item.source = source;
+            item.informal_docs = informal_docs;
+        }
+        Element::Function(item) => {
+            item.source = source;
+            item.informal_docs = informal_docs;
+        }
+        Element::Delegate(item) => {
+            item.source = source;
+            item.informal_docs = informal_docs;
+        }
+    }
     result
 }
 
@@ -337,7 +525,7 @@ fn parse_element_enum(pair: Pair<Rule>, doc_comments: &Option<String>, filename:
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::uenum => result.specifiers = Some(parse_specifiers(pair)),
-            Rule::enum_signature => result.name = parse_enum_signature(pair),
+            Rule::enum_signature => parse_enum_signature(pair, &mut result),
             Rule::enum_body => parse_enum_body(pair, &mut result),
             _ => {}
         }
@@ -345,13 +533,33 @@ fn parse_element_enum(pair: Pair<Rule>, doc_comments: &Option<String>, filename:
     result
 }
 
-fn parse_enum_signature(pair: Pair<Rule>) -> String {
-    parse_identifier(pair.into_inner().next().unwrap())
+fn parse_enum_signature(pair: Pair<Rule>, result: &mut Enum) {
+    result.unscoped = true;
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::scopedness => result.unscoped = false,
+            Rule::identifier => result.name = parse_identifier(pair),
+            Rule::value_type => result.underlying_type = Some(parse_value_type(pair)),
+            _ => {}
+        }
+    }
 }
 
 fn parse_enum_body(pair: Pair<Rule>, result: &mut Enum) {
     for pair in pair.into_inner() {
-        result.variants.push(parse_identifier(pair));
+        result.variants.push(parse_identifier(pair.clone()));
+        let mut name = None;
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                Rule::identifier => name = Some(parse_identifier(pair)),
+                Rule::umeta => {
+                    if let Some(name) = name.take() {
+                        result.variant_meta.insert(name, parse_specifiers(pair));
+                    }
+                }
+                _ => {}
+            }
+        }
     }
 }
 
@@ -359,7 +567,7 @@ fn parse_element_struct_class(
     pair: Pair<Rule>,
     doc_comments: &Option<String>,
     mode: StructClassMode,
-    settings: &Settings,
+    settings: &ParseSettings,
     document: &mut Document,
     filename: &str
 ) -> StructClass {
@@ -373,6 +581,10 @@ fn parse_element_struct_class(
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::ustruct | Rule::uclass => result.specifiers = Some(parse_specifiers(pair)),
+            Rule::uinterface => {
+                result.specifiers = Some(parse_specifiers(pair));
+                result.is_interface = true;
+            }
             Rule::struct_signature | Rule::class_signature => {
                 parse_struct_class_signature(pair, &mut result);
             }
@@ -389,6 +601,7 @@ fn parse_element_struct_class(
             _ => {}
         }
     }
+    result.pair_implementations();
     result
 }
 
@@ -396,9 +609,16 @@ fn parse_struct_class_signature(pair: Pair<Rule>, result: &mut StructClass) {
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::template_declaration => result.template = Some(parse_template_declaration(pair)),
-            Rule::api => result.api = Some(parse_identifier(pair)),
+            Rule::api => {
+                let token = parse_identifier(pair);
+                result.api = Some(match result.api.take() {
+                    Some(api) => format!("{} {}", api, token),
+                    None => token,
+                });
+            }
             Rule::identifier => result.name = parse_identifier(pair),
             Rule::inheritances => result.inherits = parse_inheritances(pair),
+            Rule::finalness => result.is_final = true,
             _ => {}
         }
     }
@@ -408,7 +628,7 @@ fn parse_struct_class_body(
     pair: Pair<Rule>,
     result: &mut StructClass,
     mut visibility: Visibility,
-    settings: &Settings,
+    settings: &ParseSettings,
     document: &mut Document,
     filename: &str
 ) {
@@ -424,20 +644,17 @@ fn parse_struct_class_body(
                     result.injects.insert(parse_identifier(pair));
                 }
             }
+            Rule::friend => result.friends.extend(parse_friend(pair)),
+            Rule::nested_aggregate => {
+                parse_nested_aggregate(pair, result, visibility, settings, document, filename)
+            }
             Rule::element => match parse_element(pair, visibility, settings, document, filename) {
-                Element::Property(element) => {
-                    if element.can_export(settings) {
-                        result.properties.push(element);
-                    }
-                }
+                Element::Property(element) => result.properties.push(element),
                 Element::Function(element) => {
-                    if element.can_export(settings) {
-                        if element.return_type == None {
-                            result.constructors.push(element);
-                        }
-                        else {
-                            result.methods.push(element);
-                        }
+                    if element.return_type.is_none() {
+                        result.constructors.push(element);
+                    } else {
+                        result.methods.push(element);
                     }
                 }
                 _ => {}
@@ -447,6 +664,58 @@ fn parse_struct_class_body(
     }
 }
 
+/// Members of unions and anonymous structs belong to the enclosing struct or class.
+fn parse_nested_aggregate(
+    pair: Pair<Rule>,
+    result: &mut StructClass,
+    visibility: Visibility,
+    settings: &ParseSettings,
+    document: &mut Document,
+    filename: &str,
+) {
+    let mut union = false;
+    let mut aggregate = StructClass::default();
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::unionness => union = true,
+            Rule::struct_class_body => parse_struct_class_body(
+                pair,
+                &mut aggregate,
+                visibility,
+                settings,
+                document,
+                filename,
+            ),
+            _ => {}
+        }
+    }
+    for mut property in aggregate.properties {
+        property.is_union_member |= union;
+        result.properties.push(property);
+    }
+    result.methods.extend(aggregate.methods);
+}
+
+fn parse_friend(pair: Pair<Rule>) -> Option<String> {
+    let pair = pair
+        .into_inner()
+        .find(|pair| pair.as_rule() != Rule::template_declaration)?;
+    match pair.as_rule() {
+        Rule::friend_class | Rule::friend_struct => pair
+            .into_inner()
+            .next()
+            .map(|pair| pair.as_str().trim().to_owned()),
+        Rule::friend_function => {
+            let mut function = Function::default();
+            if let Some(pair) = pair.into_inner().next() {
+                parse_function_signature(pair, &mut function);
+            }
+            Some(function.name)
+        }
+        _ => None,
+    }
+}
+
 fn parse_element_property(
     pair: Pair<Rule>,
     doc_comments: &Option<String>,
@@ -472,6 +741,7 @@ fn parse_property_signature(pair: Pair<Rule>, result: &mut Property) {
         match pair.as_rule() {
             Rule::value_type => result.value_type = parse_value_type(pair),
             Rule::identifier => result.name = parse_identifier(pair),
+            Rule::function_pointer => parse_function_pointer(pair, result),
             Rule::property_array => result.array = parse_property_array(pair),
             Rule::default_value => result.default_value = Some(parse_default_value(pair)),
             Rule::staticness => result.is_static = true,
@@ -480,6 +750,24 @@ fn parse_property_signature(pair: Pair<Rule>, result: &mut Property) {
     }
 }
 
+/// Function pointer properties keep their type without the name, e.g. `void (*)(int32)`.
+fn parse_function_pointer(pair: Pair<Rule>, result: &mut Property) {
+    let start = pair.as_span().start();
+    let content = pair.as_str().trim();
+    if let Some(pair) = pair
+        .into_inner()
+        .find(|pair| pair.as_rule() == Rule::identifier)
+    {
+        let span = pair.as_span();
+        result.name = parse_identifier(pair);
+        result.value_type = format!(
+            "{}{}",
+            &content[..(span.start() - start)],
+            &content[(span.end() - start)..]
+        );
+    }
+}
+
 fn parse_property_array(pair: Pair<Rule>) -> PropertyArray {
     if let Some(pair) = pair.into_inner().next() {
         PropertyArray::Sized(pair.as_str().trim().to_owned())
@@ -587,6 +875,19 @@ fn parse_element_function(
             Rule::function_signature | Rule::constructor_signature => {
                 parse_function_signature(pair, &mut result)
             }
+            Rule::conversion_signature => {
+                parse_function_signature(pair, &mut result);
+                if let Some(return_type) = &result.return_type {
+                    result.name = format!("operator {}", return_type);
+                }
+            }
+            Rule::destructor_signature => {
+                parse_function_signature(pair, &mut result);
+                result.name = format!("~{}", result.name);
+            }
+            Rule::defaulted => result.is_defaulted = true,
+            Rule::deleted => result.is_deleted = true,
+            Rule::pure_virtual | Rule::pure_virtual_macro => result.is_pure_virtual = true,
             Rule::function_body => parse_function_body(pair, document),
             _ => {}
         }
@@ -599,12 +900,17 @@ fn parse_function_signature(pair: Pair<Rule>, result: &mut Function) {
         match pair.as_rule() {
             Rule::template_declaration => result.template = Some(parse_template_declaration(pair)),
             Rule::virtualness => result.is_virtual = true,
-            Rule::value_type => result.return_type = Some(parse_value_type(pair)),
+            Rule::explicitness => result.is_explicit = true,
+            Rule::value_type | Rule::conversion_type => {
+                result.return_type = Some(parse_value_type(pair))
+            }
             Rule::operator | Rule::identifier => result.name = parse_identifier(pair),
             Rule::function_arguments => parse_function_arguments(pair, result),
             Rule::constness => result.is_const_this = true,
             Rule::overrideness => result.is_override = true,
+            Rule::ref_qualifier => result.ref_qualifier = Some(pair.as_str().to_owned()),
             Rule::staticness => result.is_static = true,
+            Rule::function_api => result.api = Some(pair.as_str().to_owned()),
             _ => {}
         }
     }
@@ -623,6 +929,7 @@ fn parse_function_argument(pair: Pair<Rule>) -> Argument {
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::doc_comment_lines => result.doc_comments = Some(parse_doc_comments(pair)),
+            Rule::uparam => result.specifiers = Some(parse_specifiers(pair)),
             Rule::value_type => result.value_type = parse_value_type(pair),
             Rule::identifier => result.name = Some(parse_identifier(pair)),
             Rule::default_value => result.default_value = Some(parse_default_value(pair)),
@@ -681,6 +988,310 @@ fn parse_identifier(pair: Pair<Rule>) -> String {
 fn test_parsing() {
     let content = crate::read_file("resources/source/test.h").unwrap();
     let mut document = Document::default();
-    parse_unreal_cpp_header(&content, &mut document, &Default::default(), Path::new("Test.h"))
+    parse_unreal_cpp_header(
+        &content,
+        &mut document,
+        &Default::default(),
+        &mut Default::default(),
+        Path::new("Test.h"),
+    )
         .unwrap_or_else(|error| panic!("Error parsing C++ header: {}", error));
+    document.remove_hidden(&Default::default());
+    let reason = |name: &str| {
+        document
+            .skipped
+            .iter()
+            .find(|item| item.name == name)
+            .map(|item| item.reason)
+    };
+    assert_eq!(reason("Who::SetWhat"), Some(SkipReason::Undocumented));
+    assert_eq!(
+        reason("Foo::A"),
+        Some(SkipReason::Visibility(Visibility::Private))
+    );
+
+    assert!(
+        !document
+            .classes
+            .iter()
+            .any(|item| item.name == "UInteractable")
+    );
+    let interface = document
+        .classes
+        .iter()
+        .find(|item| item.name == "IInteractable")
+        .unwrap();
+    assert!(interface.is_interface);
+    assert_eq!(
+        interface.doc_comments.as_deref(),
+        Some("Thing player can interact with.")
+    );
+    let methods = interface
+        .methods
+        .iter()
+        .map(|item| {
+            (
+                item.name.as_str(),
+                item.is_pure_virtual,
+                item.has_implementation,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        methods,
+        vec![("Interact", false, true), ("CanInteract", true, false)]
+    );
+}
+
+#[test]
+fn test_api_macros() {
+    let content = r#"
+/// Exported with custom macro.
+class MyGame_API DEPRECATED_TYPE UFoo final : public UObject
+{
+    GENERATED_BODY()
+
+public:
+    /// Method.
+    void Bar();
+};
+
+/// Free function.
+static GAME_API void Baz();
+"#;
+    let parse = |settings: &ParseSettings| {
+        let mut document = Document::default();
+        parse_unreal_cpp_header(
+            content,
+            &mut document,
+            settings,
+            &mut Default::default(),
+            Path::new("Test.h"),
+        )
+        .unwrap_or_else(|error| panic!("Error parsing C++ header: {}", error));
+        document
+    };
+
+    let document = parse(&Default::default());
+    let item = &document.classes[0];
+    assert_eq!(item.name, "UFoo");
+    assert!(item.is_final);
+    assert_eq!(item.api.as_deref(), Some("MyGame_API DEPRECATED_TYPE"));
+    assert_eq!(item.module.as_deref(), Some("MyGame"));
+    assert_eq!(item.methods[0].module.as_deref(), Some("MyGame"));
+    assert_eq!(document.functions[0].module.as_deref(), Some("GAME"));
+
+    let settings = ParseSettings {
+        api_macro: r"^(\w+)_TYPE$".to_owned(),
+        ..Default::default()
+    };
+    let document = parse(&settings);
+    assert_eq!(document.classes[0].module.as_deref(), Some("DEPRECATED"));
+    assert_eq!(document.functions[0].module, None);
+}
+
+#[test]
+fn test_plain_header() {
+    let content = r#"
+typedef TArray<int32> FIntList;
+
+namespace Utils { namespace Detail {
+/// Flags.
+enum EFlags
+{
+    None = 0,
+    First = 1 << 0, // comment
+    Both = (1 << 1) | First
+};
+} } // namespace Utils::Detail
+
+/// Helpers.
+struct FHelpers
+{
+    typedef int32 FIndex;
+
+    /// Formats value.
+    static FORCEINLINE FString Format(int32 Value)
+    {
+        // braces in comments, strings and characters: {
+        const TCHAR* Open = TEXT("{\"");
+        const TCHAR Close = '}';
+        int32 Big = 1'000'000;
+        if (Value > 0) { return FString::FromInt(Value); }
+        return FString();
+    }
+};
+
+/// Free function.
+inline float Saturate(float Value) { return FMath::Clamp(Value, 0.0f, 1.0f); }
+
+/// East const arguments.
+void Move(FVector const& Target, FVector* const Out);
+"#;
+    let mut document = Document::default();
+    parse_unreal_cpp_header(
+        content,
+        &mut document,
+        &Default::default(),
+        &mut Default::default(),
+        Path::new("Test.h"),
+    )
+    .unwrap_or_else(|error| panic!("Error parsing C++ header: {}", error));
+    let item = &document.enums[0];
+    assert!(item.unscoped);
+    assert_eq!(item.underlying_type, None);
+    assert_eq!(item.variant_names(), vec!["None", "First", "Both"]);
+    assert!(item.signature().starts_with("enum EFlags {"));
+    let item = &document.structs[0];
+    assert!(item.specifiers.is_none());
+    assert_eq!(item.methods[0].name, "Format");
+    assert!(item.methods[0].is_static);
+    assert_eq!(document.functions[0].name, "Saturate");
+    let arguments = &document.functions[1].arguments;
+    assert_eq!(arguments[0].value_type, "FVector const&");
+    assert_eq!(arguments[0].passing(), ArgumentPassing::ConstReference);
+    assert_eq!(arguments[0].direction(), ArgumentDirection::In);
+    assert_eq!(arguments[1].value_type, "FVector* const");
+    assert_eq!(arguments[1].name.as_deref(), Some("Out"));
+}
+
+/// Fuzzing entry point: parses arbitrary bytes as header content. It must never panic, invalid
+/// content should only end up with parsing error. Used by fuzz target in `fuzz` directory.
+pub fn fuzz_unreal_cpp_header(data: &[u8]) {
+    let content = String::from_utf8_lossy(data);
+    let settings = ParseSettings {
+        raw_header_view: true,
+        informal_comments: true,
+        ..Default::default()
+    };
+    let mut document = Document::default();
+    let _ = parse_unreal_cpp_header(
+        &content,
+        &mut document,
+        &settings,
+        &mut Default::default(),
+        Path::new("Fuzz.h"),
+    );
+}
+
+#[test]
+fn test_parsing_mutations() {
+    // xorshift generator, so mutations are the same on every run.
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = move |limit: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % limit.max(1) as u64) as usize
+    };
+    let content = crate::read_file("resources/source/test.h").unwrap();
+    let tokens = [
+        "///",
+        "////",
+        "//// [proxy: a]\n",
+        "//// [/proxy]\n",
+        "//// [snippet: a]\n",
+        "//// [/snippet]\n",
+        "UCLASS(",
+        "UINTERFACE(",
+        "USTRUCT()",
+        "UPROPERTY(",
+        "UFUNCTION(",
+        "(",
+        ")",
+        "{",
+        "}",
+        "<",
+        ">",
+        ";",
+        "\"",
+        "=",
+        "&&",
+        "\u{3000}",
+        "\n",
+        "\t",
+        "\u{FEFF}",
+    ];
+    fuzz_unreal_cpp_header(b"/// Doc.\n//// [proxy: a]\n//// Invalid(\n//// [/proxy]\n");
+    fuzz_unreal_cpp_header("//// [snippet: a]\n\u{3000}a\n  b\n//// [/snippet]\n".as_bytes());
+    for _ in 0..200 {
+        let mut data = content.as_bytes().to_vec();
+        for _ in 0..(random(4) + 1) {
+            let index = random(data.len());
+            match random(4) {
+                0 => data.truncate(index),
+                1 => {
+                    let end = (index + random(64)).min(data.len());
+                    data.drain(index..end);
+                }
+                2 => {
+                    let token = tokens[random(tokens.len())];
+                    data.splice(index..index, token.bytes());
+                }
+                _ => data.insert(index, random(256) as u8),
+            }
+        }
+        fuzz_unreal_cpp_header(&data);
+    }
+    for _ in 0..200 {
+        let data = (0..random(128))
+            .map(|_| tokens[random(tokens.len())])
+            .collect::<String>();
+        fuzz_unreal_cpp_header(data.as_bytes());
+    }
+}
+
+#[test]
+fn test_parse_source_excerpt() {
+    assert_eq!(
+        parse_source_excerpt("UPROPERTY()\n    float Speed;\n\n      int32 Count;\n"),
+        "UPROPERTY()\nfloat Speed;\n\n  int32 Count;"
+    );
+    // full-width and no-break spaces take more than one byte each
+    assert_eq!(
+        parse_source_excerpt("UFUNCTION()\n\u{3000}\u{a0}void Jump();\n\u{3000} void Land();"),
+        "UFUNCTION()\nvoid Jump();\nvoid Land();"
+    );
+}
+
+#[test]
+fn test_declaration_source() {
+    let content = r#"
+/// Foo class.
+UCLASS()
+class FOO_API UFoo : public UObject
+{
+    GENERATED_BODY()
+
+public:
+    /// Jumps.
+    UFUNCTION()
+    void Jump() const
+    {
+        Velocity.Z = 1.0f;
+    }
+};
+"#;
+    let settings = ParseSettings {
+        raw_header_view: true,
+        ..Default::default()
+    };
+    let mut document = Document::default();
+    parse_unreal_cpp_header(
+        content,
+        &mut document,
+        &settings,
+        &mut Default::default(),
+        Path::new("Foo.h"),
+    )
+    .unwrap();
+    let class = &document.classes[0];
+    assert_eq!(
+        class.source.as_deref(),
+        Some("/// Foo class.\nUCLASS()\nclass FOO_API UFoo : public UObject\n{")
+    );
+    assert_eq!(
+        class.methods[0].source.as_deref(),
+        Some("/// Jumps.\nUFUNCTION()\nvoid Jump() const")
+    );
 }
@@ -0,0 +1,651 @@
+use crate::{
+    backends::{
+        docusaurus::summary_entries,
+        markdown::resolve_relative,
+        mdbook::{bake_book_files, page_count, prune_stale_files},
+    },
+    config::{BackendConfluence, Config},
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::{Document, markdown_options},
+    ensure_dir,
+    hooks::run_hooks,
+    read_file,
+    report::BakeStats,
+    staging::Staging,
+};
+use fs_extra::{copy_items, dir::CopyOptions};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd, html::push_html};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fs::{OpenOptions, remove_file, write},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Name of manifest file with page tree, in output directory.
+const MANIFEST: &str = "manifest.json";
+
+/// Page entry of page tree manifest, listed in order pages have to be pushed in.
+#[derive(Debug, Serialize)]
+struct ManifestPage {
+    title: String,
+    /// Path of page content file relative to output directory.
+    file: String,
+    /// Title of parent page, none for top level pages.
+    parent: Option<String>,
+    /// Paths of images page shows as attachments, relative to output directory.
+    attachments: Vec<String>,
+}
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// into Confluence storage format (XHTML) pages in `pages` directory, along with `manifest.json`
+/// with page tree made of book index. Pages can then be pushed to Confluence space.
+pub fn bake_confluence(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let options = config
+        .backend_confluence
+        .as_ref()
+        .cloned()
+        .unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        output_dir,
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    let site_url = mdbook.site_url.as_deref().unwrap_or("/");
+    let (files, manifest) = confluence_files(&pages, &options.title_prefix, site_url);
+    let mut files = files;
+    files.insert(
+        MANIFEST.to_owned(),
+        serde_json::to_string_pretty(&json!({
+            "space": options.space,
+            "parent_id": options.parent_id,
+            "pages": manifest,
+        }))
+        .expect("Could not serialize Confluence manifest!"),
+    );
+    if diff_output {
+        preview_changes(&config.output_dir, &files, Some("pages"), "xhtml");
+        return Ok(stats);
+    }
+    let generated = files
+        .keys()
+        .cloned()
+        .chain(diagrams.values().flatten().map(|(_, path)| path.to_owned()))
+        .collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write Confluence file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = mdbook.assets.as_ref() {
+        let from = root.join(assets);
+        let to = output_dir.join("assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if options.push {
+        push_pages(&manifest, output_dir, &options)?;
+    }
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Page files in storage format mapped by their paths, along with page tree manifest. Pages go
+/// in book index order, with ones missing in book index put at top level after them.
+fn confluence_files(
+    pages: &BTreeMap<String, String>,
+    title_prefix: &str,
+    site_url: &str,
+) -> (BTreeMap<String, String>, Vec<ManifestPage>) {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    // stack of page ancestors, indexed by index level
+    let mut parents = Vec::<String>::new();
+    let mut tree = vec![];
+    for (level, label, page) in summary_entries(summary) {
+        parents.truncate(level);
+        tree.push((page.to_owned(), label, parents.last().cloned()));
+        parents.push(page);
+    }
+    let listed = tree
+        .iter()
+        .map(|(page, _, _)| page.to_owned())
+        .collect::<BTreeSet<_>>();
+    for page in pages
+        .keys()
+        .filter_map(|path| path.strip_prefix("src/")?.strip_suffix(".md"))
+        .filter(|page| *page != "SUMMARY" && !listed.contains(*page))
+    {
+        let label = page.rsplit('/').next().unwrap_or(page).to_owned();
+        tree.push((page.to_owned(), label, None));
+    }
+
+    // page titles have to be unique in whole space
+    let mut titles = HashMap::<String, String>::new();
+    let mut used = BTreeSet::new();
+    for (page, label, _) in &tree {
+        let mut title = format!("{}{}", title_prefix, label);
+        if !used.insert(title.to_owned()) {
+            title = format!("{} ({})", title, page);
+            used.insert(title.to_owned());
+        }
+        titles.entry(page.to_owned()).or_insert(title);
+    }
+
+    let mut files = BTreeMap::new();
+    let mut manifest = vec![];
+    let mut included = BTreeSet::new();
+    for (page, _, parent) in &tree {
+        let Some(content) = pages.get(&format!("src/{}.md", page)) else {
+            continue;
+        };
+        if !included.insert(page.to_owned()) {
+            continue;
+        }
+        let (body, attachments) = storage_content(content, page, site_url, &titles);
+        let file = format!("pages/{}.xhtml", page);
+        files.insert(file.to_owned(), body);
+        manifest.push(ManifestPage {
+            title: titles[page].to_owned(),
+            file,
+            parent: parent
+                .as_ref()
+                .and_then(|parent| titles.get(parent))
+                .cloned(),
+            attachments,
+        });
+    }
+    (files, manifest)
+}
+
+/// Renders Markdown page into Confluence storage format: code blocks become code macros, links
+/// to other pages become page links by their titles and images become attachments. Returns
+/// page content along with paths of its attachments.
+fn storage_content(
+    content: &str,
+    page: &str,
+    site_url: &str,
+    titles: &HashMap<String, String>,
+) -> (String, Vec<String>) {
+    let mut events = vec![];
+    let mut attachments = vec![];
+    let mut code = None::<(String, String)>;
+    let mut links = vec![];
+    let mut image = None::<String>;
+    for event in Parser::new_ext(content, markdown_options()) {
+        if let Some((language, text)) = code.as_mut() {
+            match event {
+                Event::Text(value) => text.push_str(&value),
+                Event::End(TagEnd::CodeBlock) => {
+                    let mut result = "<ac:structured-macro ac:name=\"code\">".to_owned();
+                    if !language.is_empty() {
+                        result.push_str(&format!(
+                            "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+                            escape_xml(language)
+                        ));
+                    }
+                    result.push_str(&format!(
+                        "<ac:plain-text-body>{}</ac:plain-text-body></ac:structured-macro>\n",
+                        cdata(text.trim_end_matches('\n'))
+                    ));
+                    events.push(Event::Html(result.into()));
+                    code = None;
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if let Some(alt) = image.as_mut() {
+            match event {
+                Event::End(TagEnd::Image) => {
+                    let alt = image.take().unwrap_or_default();
+                    if let Some(Event::Html(value)) = events.last_mut() {
+                        *value = value.replace("{{alt}}", &escape_xml(&alt)).into();
+                    }
+                }
+                Event::Text(value) | Event::Code(value) => alt.push_str(&value),
+                _ => {}
+            }
+            continue;
+        }
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split([' ', ',']).next().unwrap_or_default().to_owned()
+                    }
+                    CodeBlockKind::Indented => String::default(),
+                };
+                code = Some((language, String::default()));
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                match page_target(&dest_url, page, site_url).and_then(|page| titles.get(&page)) {
+                    Some(title) => {
+                        events.push(Event::Html(
+                            format!(
+                                "<ac:link><ri:page ri:content-title=\"{}\" /><ac:link-body>",
+                                escape_xml(title)
+                            )
+                            .into(),
+                        ));
+                        links.push(true);
+                    }
+                    None => {
+                        let url = match dest_url.strip_prefix(site_url) {
+                            Some(_) if !dest_url.contains("://") => String::default(),
+                            _ => dest_url.to_string(),
+                        };
+                        events.push(Event::Html(
+                            format!("<a href=\"{}\">", escape_xml(&url)).into(),
+                        ));
+                        links.push(false);
+                    }
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if links.pop().unwrap_or_default() {
+                    events.push(Event::Html("</ac:link-body></ac:link>".into()));
+                } else {
+                    events.push(Event::Html("</a>".into()));
+                }
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let markup = if dest_url.contains("://") {
+                    format!(
+                        "<ac:image ac:alt=\"{{{{alt}}}}\"><ri:url ri:value=\"{}\" /></ac:image>",
+                        escape_xml(&dest_url)
+                    )
+                } else {
+                    let path = match dest_url.strip_prefix(site_url) {
+                        Some(path) => path.to_owned(),
+                        None => resolve_relative(&dest_url, page),
+                    };
+                    let markup = format!(
+                        "<ac:image ac:alt=\"{{{{alt}}}}\"><ri:attachment ri:filename=\"{}\" /></ac:image>",
+                        escape_xml(attachment_name(&path))
+                    );
+                    if !attachments.contains(&path) {
+                        attachments.push(path);
+                    }
+                    markup
+                };
+                events.push(Event::Html(markup.into()));
+                image = Some(String::default());
+            }
+            Event::Html(value) => events.push(Event::Html(xhtml(&value).into())),
+            Event::InlineHtml(value) => events.push(Event::InlineHtml(xhtml(&value).into())),
+            Event::SoftBreak => events.push(Event::Text(" ".into())),
+            Event::HardBreak => events.push(Event::Html("<br />".into())),
+            Event::Rule => events.push(Event::Html("<hr />\n".into())),
+            event => events.push(event),
+        }
+    }
+    let mut result = String::default();
+    push_html(&mut result, events.into_iter());
+    (result, attachments)
+}
+
+fn attachment_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Page path (without extension) that given link of given page points at, if it links page.
+fn page_target(url: &str, page: &str, site_url: &str) -> Option<String> {
+    if url.contains("://") || url.starts_with('#') {
+        return None;
+    }
+    let path = url.split('#').next().unwrap_or(url);
+    let path = match path
+        .strip_prefix(site_url)
+        .or_else(|| path.strip_prefix('/'))
+    {
+        Some(path) => path.to_owned(),
+        None => resolve_relative(path, page),
+    };
+    path.strip_suffix(".md").map(|path| path.to_owned())
+}
+
+/// Makes raw HTML well-formed XHTML, which storage format requires: void elements get
+/// self-closed.
+fn xhtml(content: &str) -> String {
+    let re = Regex::new(r"<(br|hr|img|input|wbr)(\s[^<>]*?)?\s*/?>").unwrap();
+    re.replace_all(content, "<$1$2 />").into_owned()
+}
+
+fn cdata(content: &str) -> String {
+    format!("<![CDATA[{}]]>", content.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn escape_xml(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Creates or updates pages listed in manifest in Confluence space, in manifest order so parents
+/// exist before their children. Requests are sent with `curl` command, authenticated with
+/// `UNREAL_DOC_CONFLUENCE_USER` and `UNREAL_DOC_CONFLUENCE_TOKEN` environment variables passed
+/// in curl config file, so they never show up in command line of running process.
+fn push_pages(
+    manifest: &[ManifestPage],
+    output_dir: &Path,
+    options: &BackendConfluence,
+) -> Result<(), Box<dyn Error>> {
+    let base_url = options
+        .base_url
+        .as_deref()
+        .ok_or("Pushing to Confluence needs `backend_confluence.base_url`!")?
+        .trim_end_matches('/');
+    let space = options
+        .space
+        .as_deref()
+        .ok_or("Pushing to Confluence needs `backend_confluence.space`!")?;
+    let user = std::env::var("UNREAL_DOC_CONFLUENCE_USER").map_err(
+        |_| "Pushing to Confluence needs `UNREAL_DOC_CONFLUENCE_USER` environment variable!",
+    )?;
+    let token = std::env::var("UNREAL_DOC_CONFLUENCE_TOKEN").map_err(
+        |_| "Pushing to Confluence needs `UNREAL_DOC_CONFLUENCE_TOKEN` environment variable!",
+    )?;
+    let credentials = CurlCredentials::new(&user, &token)?;
+    let mut ids = HashMap::<&str, String>::new();
+    for page in manifest {
+        let path = output_dir.join(&page.file);
+        let body = read_file(&path)
+            .map_err(|error| format!("Could not read page file: {:?} ({})", path, error))?;
+        let parent_id = match &page.parent {
+            Some(parent) => ids.get(parent.as_str()).cloned(),
+            None => options.parent_id.to_owned(),
+        };
+        let mut content = json!({
+            "type": "page",
+            "title": page.title,
+            "space": {"key": space},
+            "body": {"storage": {"value": body, "representation": "storage"}},
+        });
+        if let Some(parent_id) = parent_id {
+            content["ancestors"] = json!([{"id": parent_id}]);
+        }
+        let existing = curl(
+            "GET",
+            &format!(
+                "{}/rest/api/content?spaceKey={}&title={}&expand=version",
+                base_url,
+                percent_encode(space),
+                percent_encode(&page.title)
+            ),
+            None,
+            credentials.path(),
+        )?;
+        let response = match existing["results"].get(0) {
+            Some(existing) => {
+                let id = existing["id"].as_str().unwrap_or_default();
+                let version = existing["version"]["number"].as_u64().unwrap_or_default();
+                content["id"] = json!(id);
+                content["version"] = json!({"number": version + 1});
+                curl(
+                    "PUT",
+                    &format!("{}/rest/api/content/{}", base_url, id),
+                    Some(&content),
+                    credentials.path(),
+                )?
+            }
+            None => curl(
+                "POST",
+                &format!("{}/rest/api/content", base_url),
+                Some(&content),
+                credentials.path(),
+            )?,
+        };
+        let Some(id) = response["id"].as_str() else {
+            return Err(format!("Could not push Confluence page: {}", page.title).into());
+        };
+        for attachment in &page.attachments {
+            let path = output_dir.join(attachment);
+            if !path.exists() {
+                println!(
+                    "Attachment of page {} does not exist: {:?}",
+                    page.title, path
+                );
+                continue;
+            }
+            // uploading with PUT creates attachment or updates existing one of the same name
+            let output = Command::new("curl")
+                .arg("--silent")
+                .arg("--show-error")
+                .arg("--fail")
+                .arg("--config")
+                .arg(credentials.path())
+                .arg("--request")
+                .arg("PUT")
+                .arg("--header")
+                .arg("X-Atlassian-Token: nocheck")
+                .arg("--form")
+                .arg(format!("file=@{}", path.to_string_lossy()))
+                .arg(format!(
+                    "{}/rest/api/content/{}/child/attachment",
+                    base_url, id
+                ))
+                .output()
+                .map_err(|error| format!("Could not run `curl` command: {}", error))?;
+            if !output.status.success() {
+                return Err(format!(
+                    "Could not upload attachment of page {}: {:?}",
+                    page.title, path
+                )
+                .into());
+            }
+        }
+        ids.insert(&page.title, id.to_owned());
+        println!("Pushed Confluence page: {}", page.title);
+    }
+    Ok(())
+}
+
+/// Sends request to Confluence REST API with `curl` command and returns its JSON response.
+/// Credentials are read from given curl config file.
+fn curl(
+    method: &str,
+    url: &str,
+    body: Option<&Value>,
+    credentials: &Path,
+) -> Result<Value, Box<dyn Error>> {
+    let mut command = Command::new("curl");
+    command
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--fail")
+        .arg("--config")
+        .arg(credentials)
+        .arg("--request")
+        .arg(method)
+        .arg("--header")
+        .arg("Accept: application/json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped());
+    if body.is_some() {
+        command
+            .arg("--header")
+            .arg("Content-Type: application/json")
+            .arg("--data-binary")
+            .arg("@-");
+    }
+    let mut child = command
+        .arg(url)
+        .spawn()
+        .map_err(|error| format!("Could not run `curl` command: {}", error))?;
+    if let (Some(body), Some(mut stdin)) = (body, child.stdin.take()) {
+        stdin
+            .write_all(body.to_string().as_bytes())
+            .map_err(|error| {
+                format!("Could not send request to Confluence: {} ({})", url, error)
+            })?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("Could not send request to Confluence: {} ({})", url, error))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Confluence request failed with {}: {} {}",
+            output.status, method, url
+        )
+        .into());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|error| {
+        format!(
+            "Could not parse Confluence response: {} {} ({})",
+            method, url, error
+        )
+        .into()
+    })
+}
+
+/// Temporary curl config file with user credentials, readable by current user only and removed
+/// once dropped.
+struct CurlCredentials(PathBuf);
+
+impl CurlCredentials {
+    fn new(user: &str, token: &str) -> Result<Self, Box<dyn Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "unreal-doc-confluence-{}.curlrc",
+            std::process::id()
+        ));
+        // created anew, so leftover file of the same name can not keep other permissions
+        let _ = remove_file(&path);
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let escape = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+        options
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "user = \"{}:{}\"", escape(user), escape(token)))
+            .map_err(|error| format!("Could not write curl config: {:?} ({})", path, error))?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for CurlCredentials {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.0);
+    }
+}
+
+fn percent_encode(content: &str) -> String {
+    content
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[test]
+fn test_confluence_files() {
+    let pages = BTreeMap::from([
+        (
+            "src/SUMMARY.md".to_owned(),
+            "# Index\n\n- [Hello](hello.md)\n- [Reference](reference.md)\n  \
+            - [UFoo](reference/classes/UFoo.md)\n"
+                .to_owned(),
+        ),
+        (
+            "src/hello.md".to_owned(),
+            "# Hello\n\nSee [`UFoo`](/reference/classes/UFoo.md#methods) and <br>.\n\n\
+            ```cpp\nif (a < b) {}\n```\n"
+                .to_owned(),
+        ),
+        ("src/reference.md".to_owned(), "# Reference\n".to_owned()),
+        (
+            "src/reference/classes/UFoo.md".to_owned(),
+            "# UFoo\n\n![Diagram of UFoo](../../assets/diagrams/UFoo.svg)\n".to_owned(),
+        ),
+    ]);
+    let (files, manifest) = confluence_files(&pages, "API: ", "/");
+    let hello = &files["pages/hello.xhtml"];
+    assert!(hello.contains(
+        "<ac:link><ri:page ri:content-title=\"API: UFoo\" /><ac:link-body><code>UFoo</code>"
+    ));
+    assert!(hello.contains(" and <br />."));
+    assert!(hello.contains(
+        "<ac:parameter ac:name=\"language\">cpp</ac:parameter>\
+        <ac:plain-text-body><![CDATA[if (a < b) {}]]></ac:plain-text-body>"
+    ));
+    assert_eq!(
+        manifest
+            .iter()
+            .map(|page| (page.title.as_str(), page.parent.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("API: Hello", None),
+            ("API: Reference", None),
+            ("API: UFoo", Some("API: Reference")),
+        ]
+    );
+    assert_eq!(manifest[2].attachments, vec!["assets/diagrams/UFoo.svg"]);
+    assert!(files["pages/reference/classes/UFoo.xhtml"].contains(
+        "<ac:image ac:alt=\"Diagram of UFoo\"><ri:attachment ri:filename=\"UFoo.svg\" /></ac:image>"
+    ));
+}
+
+#[test]
+fn test_curl_credentials() {
+    let credentials = CurlCredentials::new("user@example.com", "to\\ke\"n").unwrap();
+    let path = credentials.path().to_owned();
+    assert_eq!(
+        read_file(&path).unwrap(),
+        "user = \"user@example.com:to\\\\ke\\\"n\"\n"
+    );
+    drop(credentials);
+    assert!(!path.exists());
+}
@@ -0,0 +1,359 @@
+use crate::{
+    backends::mdbook::{bake_book_files, page_count, prune_stale_files},
+    config::Config,
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::Document,
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+    staging::Staging,
+};
+use fs_extra::{copy_items, dir::CopyOptions};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::{Value, json};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fs::write,
+    path::Path,
+};
+
+/// HTML elements that can not have children, so MDX needs them self-closed.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "input", "wbr"];
+
+/// HTML comment, possibly spanning multiple lines, or inline code span that comments are not
+/// looked for in.
+static HTML_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)(`[^`\n]*`)|<!--.*?-->").unwrap());
+
+/// Autolink, e.g. `<https://example.com>`.
+static AUTOLINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"<(https?://[^<>\s]+)>").unwrap());
+
+/// Opening, closing or self-closing HTML tag at start of text.
+static HTML_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^</?([a-z][a-z0-9-]*)(\s+[^<>]*?)?\s*/?>"#).unwrap());
+
+/// Destination of Markdown link.
+static LINK_URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\]\(([^()\s]+)\)").unwrap());
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// into Docusaurus v3 docs: MDX pages with front matter in `docs` directory and `sidebars.js`
+/// with book index.
+pub fn bake_docusaurus(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let docusaurus = config
+        .backend_docusaurus
+        .as_ref()
+        .cloned()
+        .unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        &output_dir.join("docs"),
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    let site_url = mdbook.site_url.as_deref().unwrap_or("/");
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let entries = summary_entries(summary);
+    let labels = entries
+        .iter()
+        .map(|(_, label, page)| (page.as_str(), label.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    let mut files = BTreeMap::new();
+    for (path, content) in &pages {
+        let Some(page) = path
+            .strip_prefix("src/")
+            .and_then(|path| path.strip_suffix(".md"))
+        else {
+            files.insert(path.to_owned(), content.to_owned());
+            continue;
+        };
+        if page == "SUMMARY" {
+            continue;
+        }
+        let label = labels
+            .get(page)
+            .copied()
+            .unwrap_or_else(|| page.rsplit('/').next().unwrap_or(page));
+        let content = format!(
+            "---\nsidebar_label: {}\n---\n\n{}",
+            json!(label),
+            mdx_content(content, page, site_url)
+        );
+        files.insert(format!("docs/{}.mdx", page), content);
+    }
+    files.insert(
+        "sidebars.js".to_owned(),
+        sidebars(&entries, &docusaurus.sidebar),
+    );
+    if diff_output {
+        preview_changes(&config.output_dir, &files, Some("docs"), "mdx");
+        return Ok(stats);
+    }
+    let generated = files
+        .keys()
+        .cloned()
+        .chain(
+            diagrams
+                .values()
+                .flatten()
+                .map(|(_, path)| format!("docs/{}", path)),
+        )
+        .collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write Docusaurus file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = mdbook.assets.as_ref() {
+        let from = root.join(assets);
+        let to = output_dir.join("docs/assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Entries of book index as nesting level, label and page path without extension.
+pub fn summary_entries(summary: &str) -> Vec<(usize, String, String)> {
+    let re = Regex::new(r"^(\s*)- \[(.*)\]\((.*)\.md\)\s*$").unwrap();
+    summary
+        .lines()
+        .filter_map(|line| {
+            let captures = re.captures(line)?;
+            Some((
+                captures[1].len() / 2,
+                captures[2].to_owned(),
+                captures[3].trim_start_matches('/').to_owned(),
+            ))
+        })
+        .collect()
+}
+
+/// Source of `sidebars.js` with single sidebar made of book index, where entries with nested
+/// entries become categories linked to their pages.
+fn sidebars(entries: &[(usize, String, String)], name: &str) -> String {
+    fn items(entries: &[(usize, String, String)], index: &mut usize, level: usize) -> Vec<Value> {
+        let mut result = vec![];
+        while let Some((entry_level, label, page)) = entries.get(*index) {
+            if *entry_level < level {
+                break;
+            }
+            *index += 1;
+            let children = items(entries, index, entry_level + 1);
+            if children.is_empty() {
+                result.push(json!({"type": "doc", "id": page, "label": label}));
+            } else {
+                result.push(json!({
+                    "type": "category",
+                    "label": label,
+                    "link": {"type": "doc", "id": page},
+                    "items": children,
+                }));
+            }
+        }
+        result
+    }
+
+    let mut index = 0;
+    let sidebar = json!({ name: items(entries, &mut index, 0) });
+    format!(
+        "// Generated by unreal-doc, do not edit.\n\
+        /** @type {{import('@docusaurus/plugin-content-docs').SidebarsConfig}} */\n\
+        const sidebars = {};\n\nexport default sidebars;\n",
+        serde_json::to_string_pretty(&sidebar).expect("Could not serialize Docusaurus sidebars!")
+    )
+}
+
+/// Makes Markdown page valid MDX: outside of code, braces and `<` not starting HTML tags get
+/// escaped, HTML comments are removed, void elements get self-closed and `class` attributes
+/// become `className`. Links to pages become file links relative to given page, so Docusaurus
+/// resolves them to routes of these pages.
+fn mdx_content(content: &str, page: &str, site_url: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut text = String::default();
+    let mut fence = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            result.push_str(line);
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            result.push_str(&mdx_lines(&text, page, site_url));
+            text.clear();
+            result.push_str(line);
+            continue;
+        }
+        text.push_str(line);
+    }
+    result.push_str(&mdx_lines(&text, page, site_url));
+    result
+}
+
+/// Makes lines between code blocks valid MDX, once HTML comments, that may span multiple lines,
+/// are removed.
+fn mdx_lines(text: &str, page: &str, site_url: &str) -> String {
+    let text = HTML_COMMENT.replace_all(text, "$1");
+    let mut result = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        // odd parts are inline code spans
+        for (index, part) in line.split('`').enumerate() {
+            if index > 0 {
+                result.push('`');
+            }
+            if index % 2 == 1 {
+                result.push_str(part);
+            } else {
+                result.push_str(&mdx_text(part, page, site_url));
+            }
+        }
+    }
+    result
+}
+
+fn mdx_text(text: &str, page: &str, site_url: &str) -> String {
+    let text = AUTOLINK.replace_all(text, "[$1]($1)");
+    let text = LINK_URL.replace_all(&text, |captures: &Captures| {
+        format!("]({})", mdx_link(&captures[1], page, site_url))
+    });
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text.as_ref();
+    while let Some(position) = rest.find(['<', '{', '}']) {
+        result.push_str(&rest[..position]);
+        rest = &rest[position..];
+        if let Some(captures) = HTML_TAG.captures(rest) {
+            let mut element = captures[0].replace(" class=", " className=");
+            if VOID_ELEMENTS.contains(&&captures[1]) && !element.ends_with("/>") {
+                element = format!("{} />", element.trim_end_matches('>').trim_end());
+            }
+            result.push_str(&element);
+            rest = &rest[captures[0].len()..];
+        } else {
+            match rest.as_bytes()[0] {
+                b'<' => result.push_str("&lt;"),
+                b'{' => result.push_str("\\{"),
+                _ => result.push_str("\\}"),
+            }
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Link to other page, made relative to given page and pointing at its MDX file. Links that
+/// are relative already stay relative to given page.
+fn mdx_link(url: &str, page: &str, site_url: &str) -> String {
+    if url.contains("://") {
+        return url.to_owned();
+    }
+    let (path, section) = match url.split_once('#') {
+        Some((path, section)) => (path, Some(section)),
+        None => (url, None),
+    };
+    let Some(path) = path.strip_suffix(".md") else {
+        return url.to_owned();
+    };
+    let mut result = match path
+        .strip_prefix(site_url)
+        .or_else(|| path.strip_prefix('/'))
+    {
+        Some(path) => {
+            let depth = page.matches('/').count();
+            let mut result = if depth == 0 {
+                "./".to_owned()
+            } else {
+                "../".repeat(depth)
+            };
+            result.push_str(path);
+            result
+        }
+        None => path.to_owned(),
+    };
+    result.push_str(".mdx");
+    if let Some(section) = section {
+        result.push('#');
+        result.push_str(section);
+    }
+    result
+}
+
+#[test]
+fn test_mdx_content() {
+    let content = "# **`TArray<int>`** {x}\n\
+        <!-- hidden -->Uses TMap<A, B> and <br> in <div class=\"a\">[`Foo`](/reference/Foo.md#bar)</div>\n\
+        ```cpp\n{ a < b }\n```\n";
+    assert_eq!(
+        mdx_content(content, "reference/classes/UFoo", "/"),
+        "# **`TArray<int>`** \\{x\\}\n\
+        Uses TMap&lt;A, B> and <br /> in <div className=\"a\">[`Foo`](../../reference/Foo.mdx#bar)</div>\n\
+        ```cpp\n{ a < b }\n```\n"
+    );
+
+    let content = "Before <!-- hidden\nacross lines -->after `<!-- kept -->`.\n\
+        See [Bar](../enums/EBar.md) and [Baz](UBaz.md#top).\n";
+    assert_eq!(
+        mdx_content(content, "reference/classes/UFoo", "/"),
+        "Before after `<!-- kept -->`.\n\
+        See [Bar](../enums/EBar.mdx) and [Baz](UBaz.mdx#top).\n"
+    );
+}
+
+#[test]
+fn test_sidebars() {
+    let entries = summary_entries(
+        "# Index\n\n- [Hello](hello.md)\n\n- [C++ API Reference](reference.md)\n  \
+        - [Enums](reference/enums.md)\n    - [EFoo](reference/enums/EFoo.md)\n",
+    );
+    assert_eq!(entries.len(), 4);
+    let content = sidebars(&entries, "docs");
+    assert!(content.contains(r#""id": "reference/enums/EFoo""#));
+    assert!(content.contains(r#""type": "category""#));
+    assert!(content.ends_with("export default sidebars;\n"));
+}
@@ -0,0 +1,525 @@
+use crate::{
+    backends::mdbook::prune_stale_files,
+    config::{BriefMode, Config},
+    diff::preview_changes,
+    document::*,
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+    staging::Staging,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    error::Error,
+    fs::write,
+    path::Path,
+};
+
+/// Doxygen version whose XML output format is produced.
+const DOXYGEN_VERSION: &str = "1.9.8";
+
+const XML_HEADER: &str = "<?xml version='1.0' encoding='UTF-8' standalone='no'?>\n";
+
+/// Bakes document into Doxygen XML output (`index.xml` and one XML file per compound), so tools
+/// made for Doxygen (like Breathe for Sphinx) can use it.
+pub fn bake_doxygen(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let files = doxygen_files(document);
+    // every compound is a page, index is not
+    let stats = BakeStats {
+        pages: files.len() - 1,
+        ..Default::default()
+    };
+    if diff_output {
+        preview_changes(&config.output_dir, &files, None, "xml");
+        return Ok(stats);
+    }
+    let staging = Staging::begin(&config.output_dir, true)?;
+    let generated = files.keys().cloned().collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = staging.path().join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write Doxygen XML file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(staging.path(), &generated)?;
+    run_hooks(&config.hooks.post_bake, config, root, staging.path())?;
+    staging.commit()?;
+    Ok(stats)
+}
+
+/// Doxygen XML files mapped by their names: compound per struct and class, compound per header
+/// file with its enums, functions and delegates, and index of all of them.
+fn doxygen_files(document: &Document) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    let mut index = format!(
+        "{}<doxygenindex version=\"{}\" xml:lang=\"en-US\">\n",
+        XML_HEADER, DOXYGEN_VERSION
+    );
+    let mut inner_classes = BTreeMap::<&str, Vec<(String, &str)>>::new();
+    for item in document.structs.iter().chain(document.classes.iter()) {
+        let kind = item.mode.signature();
+        let id = format!("{}{}", kind, escape_id(&item.name));
+        let mut ids = HashSet::new();
+        let mut sections = BTreeMap::<String, String>::new();
+        index.push_str(&format!(
+            "  <compound refid=\"{}\" kind=\"{}\"><name>{}</name>\n",
+            id,
+            kind,
+            escape_xml(&item.name)
+        ));
+        let file = item.header_path.as_deref().unwrap_or(&item.filename);
+        for property in &item.properties {
+            let member_id = member_id(&id, &property.name, &mut ids);
+            index.push_str(&index_member(&member_id, "variable", &property.name));
+            let section = section_kind(property.visibility, property.is_static, "attrib");
+            sections
+                .entry(section)
+                .or_default()
+                .push_str(&property_member(item, property, &member_id, file));
+        }
+        for function in item.constructors.iter().chain(item.methods.iter()) {
+            let member_id = member_id(&id, &function.name, &mut ids);
+            index.push_str(&index_member(&member_id, "function", &function.name));
+            let section = section_kind(function.visibility, function.is_static, "func");
+            sections
+                .entry(section)
+                .or_default()
+                .push_str(&function_member(
+                    Some(&item.name),
+                    function,
+                    &member_id,
+                    file,
+                ));
+        }
+        index.push_str("  </compound>\n");
+
+        let mut content = compound_header(&id, &kind, &item.name);
+        for (_, parent) in &item.inherits {
+            content.push_str(&format!(
+                "    <basecompoundref prot=\"public\" virt=\"non-virtual\">{}</basecompoundref>\n",
+                escape_xml(parent)
+            ));
+        }
+        for (kind, members) in sections {
+            content.push_str(&format!(
+                "    <sectiondef kind=\"{}\">\n{}    </sectiondef>\n",
+                kind, members
+            ));
+        }
+        content.push_str(&descriptions(&item.doc_comments, "    "));
+        content.push_str(&format!(
+            "    <location file=\"{}\" line=\"{}\"/>\n",
+            escape_xml(file),
+            item.fileline
+        ));
+        content.push_str(COMPOUND_FOOTER);
+        inner_classes
+            .entry(file)
+            .or_default()
+            .push((id.to_owned(), &item.name));
+        result.insert(format!("{}.xml", id), content);
+    }
+
+    let mut files = BTreeMap::<&str, BTreeMap<&str, String>>::new();
+    let mut file_members = BTreeMap::<&str, Vec<(String, &str, &str)>>::new();
+    let mut ids = HashSet::new();
+    for item in &document.enums {
+        let file = item.header_path.as_deref().unwrap_or(&item.filename);
+        let file_id = escape_id(file);
+        let member_id = member_id(&file_id, &item.name, &mut ids);
+        let mut member = format!(
+            "      <memberdef kind=\"enum\" id=\"{}\" prot=\"public\" static=\"no\">\n\
+            \x20       <type></type>\n        <name>{}</name>\n",
+            member_id,
+            escape_xml(&item.name)
+        );
+        for variant in item.variant_names() {
+            member.push_str(&format!(
+                "        <enumvalue id=\"{}_1{}\" prot=\"public\">\n\
+                \x20         <name>{}</name>\n\
+                \x20         <briefdescription>\n          </briefdescription>\n\
+                \x20         <detaileddescription>\n          </detaileddescription>\n\
+                \x20       </enumvalue>\n",
+                member_id,
+                escape_id(variant),
+                escape_xml(variant)
+            ));
+        }
+        member.push_str(&descriptions(&item.doc_comments, "        "));
+        member.push_str(&location(file, item.fileline));
+        member.push_str("      </memberdef>\n");
+        files
+            .entry(file)
+            .or_default()
+            .entry("enum")
+            .or_default()
+            .push_str(&member);
+        file_members
+            .entry(file)
+            .or_default()
+            .push((member_id, "enum", &item.name));
+    }
+    for item in &document.functions {
+        let file = item.header_path.as_deref().unwrap_or(&item.filename);
+        let member_id = member_id(&escape_id(file), &item.name, &mut ids);
+        files
+            .entry(file)
+            .or_default()
+            .entry("func")
+            .or_default()
+            .push_str(&function_member(None, item, &member_id, file));
+        file_members
+            .entry(file)
+            .or_default()
+            .push((member_id, "function", &item.name));
+    }
+    for item in &document.delegates {
+        let file = item.header_path.as_deref().unwrap_or(&item.filename);
+        let member_id = member_id(&escape_id(file), &item.name, &mut ids);
+        let return_type = item.return_type.as_deref().unwrap_or("void");
+        let mut member = format!(
+            "      <memberdef kind=\"typedef\" id=\"{}\" prot=\"public\" static=\"no\">\n\
+            \x20       <type>{}</type>\n\
+            \x20       <definition>delegate {} {}</definition>\n\
+            \x20       <argsstring>{}</argsstring>\n\
+            \x20       <name>{}</name>\n",
+            member_id,
+            escape_xml(return_type),
+            escape_xml(return_type),
+            escape_xml(&item.name),
+            escape_xml(&arguments_string(&item.arguments)),
+            escape_xml(&item.name)
+        );
+        member.push_str(&parameters(&item.arguments));
+        member.push_str(&descriptions(&item.doc_comments, "        "));
+        member.push_str(&location(file, item.fileline));
+        member.push_str("      </memberdef>\n");
+        files
+            .entry(file)
+            .or_default()
+            .entry("typedef")
+            .or_default()
+            .push_str(&member);
+        file_members
+            .entry(file)
+            .or_default()
+            .push((member_id, "typedef", &item.name));
+    }
+    for file in inner_classes.keys() {
+        files.entry(file).or_default();
+    }
+
+    for (file, sections) in files {
+        let id = escape_id(file);
+        index.push_str(&format!(
+            "  <compound refid=\"{}\" kind=\"file\"><name>{}</name>\n",
+            id,
+            escape_xml(file)
+        ));
+        for (member_id, kind, name) in file_members.get(file).into_iter().flatten() {
+            index.push_str(&index_member(member_id, kind, name));
+        }
+        index.push_str("  </compound>\n");
+        let mut content = compound_header(&id, "file", file);
+        for (class_id, name) in inner_classes.get(file).into_iter().flatten() {
+            content.push_str(&format!(
+                "    <innerclass refid=\"{}\" prot=\"public\">{}</innerclass>\n",
+                class_id,
+                escape_xml(name)
+            ));
+        }
+        for (kind, members) in sections {
+            content.push_str(&format!(
+                "    <sectiondef kind=\"{}\">\n{}    </sectiondef>\n",
+                kind, members
+            ));
+        }
+        content.push_str(&descriptions(&None, "    "));
+        content.push_str(&format!("    <location file=\"{}\"/>\n", escape_xml(file)));
+        content.push_str(COMPOUND_FOOTER);
+        result.insert(format!("{}.xml", id), content);
+    }
+    index.push_str("</doxygenindex>\n");
+    result.insert("index.xml".to_owned(), index);
+    result
+}
+
+const COMPOUND_FOOTER: &str = "  </compounddef>\n</doxygen>\n";
+
+fn compound_header(id: &str, kind: &str, name: &str) -> String {
+    format!(
+        "{}<doxygen version=\"{}\" xml:lang=\"en-US\">\n\
+        \x20 <compounddef id=\"{}\" kind=\"{}\" language=\"C++\" prot=\"public\">\n\
+        \x20   <compoundname>{}</compoundname>\n",
+        XML_HEADER,
+        DOXYGEN_VERSION,
+        id,
+        kind,
+        escape_xml(name)
+    )
+}
+
+fn index_member(id: &str, kind: &str, name: &str) -> String {
+    format!(
+        "    <member refid=\"{}\" kind=\"{}\"><name>{}</name></member>\n",
+        id,
+        kind,
+        escape_xml(name)
+    )
+}
+
+/// Doxygen section kind of member, e.g. `public-func` or `protected-static-attrib`.
+fn section_kind(visibility: Visibility, is_static: bool, kind: &str) -> String {
+    if is_static {
+        format!("{}-static-{}", visibility.signature(), kind)
+    } else {
+        format!("{}-{}", visibility.signature(), kind)
+    }
+}
+
+fn property_member(owner: &StructClass, property: &Property, id: &str, file: &str) -> String {
+    let mut result = format!(
+        "      <memberdef kind=\"variable\" id=\"{}\" prot=\"{}\" static=\"{}\" mutable=\"no\">\n\
+        \x20       <type>{}</type>\n\
+        \x20       <definition>{} {}::{}</definition>\n\
+        \x20       <argsstring></argsstring>\n\
+        \x20       <name>{}</name>\n\
+        \x20       <qualifiedname>{}::{}</qualifiedname>\n",
+        id,
+        property.visibility.signature(),
+        yes_no(property.is_static),
+        escape_xml(&property.value_type),
+        escape_xml(&property.value_type),
+        escape_xml(&owner.name),
+        escape_xml(&property.name),
+        escape_xml(&property.name),
+        escape_xml(&owner.name),
+        escape_xml(&property.name)
+    );
+    if let Some(value) = property
+        .default_value
+        .as_ref()
+        .or(property.constructor_default.as_ref())
+    {
+        result.push_str(&format!(
+            "        <initializer>= {}</initializer>\n",
+            escape_xml(value)
+        ));
+    }
+    result.push_str(&descriptions(&property.doc_comments, "        "));
+    result.push_str(&format!(
+        "        <location file=\"{}\"/>\n      </memberdef>\n",
+        escape_xml(file)
+    ));
+    result
+}
+
+fn function_member(owner: Option<&str>, function: &Function, id: &str, file: &str) -> String {
+    let return_type = function.return_type.as_deref().unwrap_or_default();
+    let qualified_name = match owner {
+        Some(owner) => format!("{}::{}", owner, function.name),
+        None => function.name.to_owned(),
+    };
+    let mut arguments = arguments_string(&function.arguments);
+    if function.is_const_this {
+        arguments.push_str(" const");
+    }
+    if function.is_override {
+        arguments.push_str(" override");
+    }
+    let virt = if function.is_pure_virtual {
+        "pure-virtual"
+    } else if function.is_virtual || function.is_override {
+        "virtual"
+    } else {
+        "non-virtual"
+    };
+    let mut result = format!(
+        "      <memberdef kind=\"function\" id=\"{}\" prot=\"{}\" static=\"{}\" const=\"{}\" \
+        explicit=\"{}\" inline=\"no\" virt=\"{}\">\n\
+        \x20       <type>{}</type>\n\
+        \x20       <definition>{}</definition>\n\
+        \x20       <argsstring>{}</argsstring>\n\
+        \x20       <name>{}</name>\n\
+        \x20       <qualifiedname>{}</qualifiedname>\n",
+        id,
+        function.visibility.signature(),
+        yes_no(function.is_static),
+        yes_no(function.is_const_this),
+        yes_no(function.is_explicit),
+        virt,
+        escape_xml(return_type),
+        escape_xml(format!("{} {}", return_type, qualified_name).trim()),
+        escape_xml(&arguments),
+        escape_xml(&function.name),
+        escape_xml(&qualified_name)
+    );
+    result.push_str(&parameters(&function.arguments));
+    result.push_str(&descriptions(&function.doc_comments, "        "));
+    result.push_str(&location(file, function.fileline));
+    result.push_str("      </memberdef>\n");
+    result
+}
+
+fn arguments_string(arguments: &[Argument]) -> String {
+    let arguments = arguments
+        .iter()
+        .map(|argument| {
+            let mut result = argument.value_type.to_owned();
+            if let Some(name) = &argument.name {
+                result.push(' ');
+                result.push_str(name);
+            }
+            if let Some(value) = &argument.default_value {
+                result.push_str(" = ");
+                result.push_str(value);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+    format!("({})", arguments.join(", "))
+}
+
+fn parameters(arguments: &[Argument]) -> String {
+    let mut result = String::default();
+    for argument in arguments {
+        result.push_str(&format!(
+            "        <param>\n          <type>{}</type>\n",
+            escape_xml(&argument.value_type)
+        ));
+        if let Some(name) = &argument.name {
+            result.push_str(&format!(
+                "          <declname>{}</declname>\n",
+                escape_xml(name)
+            ));
+        }
+        if let Some(value) = &argument.default_value {
+            result.push_str(&format!(
+                "          <defval>{}</defval>\n",
+                escape_xml(value)
+            ));
+        }
+        if let Some(docs) = &argument.doc_comments {
+            result.push_str(&format!(
+                "          <briefdescription><para>{}</para></briefdescription>\n",
+                escape_xml(docs.trim())
+            ));
+        }
+        result.push_str("        </param>\n");
+    }
+    result
+}
+
+/// Brief (first sentence) and detailed (all paragraphs) descriptions made of doc comments.
+fn descriptions(doc_comments: &Option<String>, indent: &str) -> String {
+    let brief = doc_brief(doc_comments, BriefMode::FirstSentence)
+        .map(|brief| format!("<para>{}</para>", escape_xml(&brief)))
+        .unwrap_or_default();
+    let detailed = doc_comments
+        .as_deref()
+        .unwrap_or_default()
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim())
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| format!("<para>{}</para>", escape_xml(paragraph)))
+        .collect::<String>();
+    format!(
+        "{}<briefdescription>{}</briefdescription>\n{}<detaileddescription>{}</detaileddescription>\n",
+        indent, brief, indent, detailed
+    )
+}
+
+fn location(file: &str, line: usize) -> String {
+    format!(
+        "        <location file=\"{}\" line=\"{}\"/>\n",
+        escape_xml(file),
+        line
+    )
+}
+
+/// Unique id of member of given compound, with overloads numbered.
+fn member_id(compound: &str, name: &str, ids: &mut HashSet<String>) -> String {
+    let base = format!("{}_1{}", compound, escape_id(name));
+    let mut result = base.to_owned();
+    let mut index = 1;
+    while !ids.insert(result.to_owned()) {
+        result = format!("{}_{}", base, index);
+        index += 1;
+    }
+    result
+}
+
+/// Id the way Doxygen escapes names for ids and file names, e.g. `UFoo.h` gives `_u_foo_8h`.
+fn escape_id(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() * 2);
+    for c in name.chars() {
+        match c {
+            'A'..='Z' => {
+                result.push('_');
+                result.push(c.to_ascii_lowercase());
+            }
+            '_' => result.push_str("__"),
+            '.' => result.push_str("_8"),
+            ':' => result.push_str("_1"),
+            '/' | '\\' => result.push_str("_2"),
+            '<' => result.push_str("_3"),
+            '>' => result.push_str("_4"),
+            ' ' => result.push_str("_01"),
+            c if c.is_ascii_alphanumeric() => result.push(c),
+            c => result.push_str(&format!("_{:x}", c as u32)),
+        }
+    }
+    result
+}
+
+fn escape_xml(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+#[test]
+fn test_doxygen_files() {
+    let document = Document {
+        classes: vec![StructClass {
+            mode: StructClassMode::Class,
+            name: "UFoo".to_owned(),
+            filename: "Foo.h".to_owned(),
+            methods: vec![
+                Function {
+                    name: "Get".to_owned(),
+                    return_type: Some("TArray<int32>".to_owned()),
+                    ..Default::default()
+                },
+                Function {
+                    name: "Get".to_owned(),
+                    is_const_this: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let files = doxygen_files(&document);
+    assert_eq!(
+        files.keys().collect::<Vec<_>>(),
+        vec!["_foo_8h.xml", "class_u_foo.xml", "index.xml"]
+    );
+    let class = &files["class_u_foo.xml"];
+    assert!(class.contains(r#"<memberdef kind="function" id="class_u_foo_1_get" "#));
+    assert!(class.contains(r#"id="class_u_foo_1_get_1""#));
+    assert!(class.contains("<type>TArray&lt;int32&gt;</type>"));
+    assert!(files["_foo_8h.xml"].contains(r#"<innerclass refid="class_u_foo""#));
+}
@@ -0,0 +1,129 @@
+use crate::{document::*, ensure_dir};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+/// Graphviz DOT source of inheritance diagram around given struct or class: its documented
+/// parents chain and direct children. Returns `None` if there are no relations to show.
+pub fn inheritance_graph(document: &Document, item: &StructClass) -> Option<String> {
+    let parents = document.parent_chain(item);
+    let children = document.children(&item.name);
+    if parents.is_empty() && children.is_empty() {
+        return None;
+    }
+    let mut result = graph_header(&item.name, "arrowhead=empty");
+    result.push_str(&format!(
+        "    \"{}\" [style=filled, fillcolor=lightgrey];\n",
+        item.name
+    ));
+    let mut child = item.name.as_str();
+    for parent in &parents {
+        result.push_str(&format!("    \"{}\" -> \"{}\";\n", child, parent));
+        child = parent;
+    }
+    for child in children {
+        result.push_str(&format!("    \"{}\" -> \"{}\";\n", child.name, item.name));
+    }
+    result.push_str("}\n");
+    Some(result)
+}
+
+/// Graphviz DOT source of diagram of all inheritance relations between structs and classes, along
+/// with undocumented types they derive from. Returns `None` if no type derives from another.
+pub fn hierarchy_graph(document: &Document) -> Option<String> {
+    let edges = document.inheritance_edges();
+    if edges.is_empty() {
+        return None;
+    }
+    let mut result = graph_header("hierarchy", "arrowhead=empty");
+    for (child, parent) in edges {
+        result.push_str(&format!("    \"{}\" -> \"{}\";\n", child, parent));
+    }
+    result.push_str("}\n");
+    Some(result)
+}
+
+/// Graphviz DOT source of composition diagram of given struct or class: documented structs and
+/// classes its properties hold, with edge style telling how they are held. Returns `None` if
+/// it holds no documented types.
+pub fn composition_graph(document: &Document, item: &StructClass) -> Option<String> {
+    let mut held = BTreeMap::<(&str, Holding), Vec<&str>>::new();
+    for property in &item.properties {
+        let type_ref = TypeRef::parse(&property.value_type);
+        for (name, holding) in type_ref.held_types() {
+            if let Some(target) = document.find_struct_class(name) {
+                held.entry((target.name.as_str(), holding))
+                    .or_default()
+                    .push(property.name.as_str());
+            }
+        }
+    }
+    if held.is_empty() {
+        return None;
+    }
+    let mut result = graph_header(&item.name, "dir=back, arrowtail=diamond");
+    result.push_str(&format!(
+        "    \"{}\" [style=filled, fillcolor=lightgrey];\n",
+        item.name
+    ));
+    for ((name, holding), properties) in held {
+        let style = match holding {
+            Holding::Value => "solid",
+            Holding::Pointer => "dashed",
+            Holding::Weak | Holding::Soft => "dotted",
+        };
+        let arrowtail = match holding {
+            Holding::Value => "diamond",
+            _ => "odiamond",
+        };
+        result.push_str(&format!(
+            "    \"{}\" -> \"{}\" [style={}, arrowtail={}, label=\"{}\\n({})\"];\n",
+            item.name,
+            name,
+            style,
+            arrowtail,
+            properties.join("\\n"),
+            holding.description()
+        ));
+    }
+    result.push_str("}\n");
+    Some(result)
+}
+
+/// Renders DOT source into SVG file using `dot` binary. Returns `false` if rendering failed.
+pub fn render_svg(source: &str, path: &Path) -> bool {
+    ensure_dir(path);
+    let child = Command::new("dot")
+        .arg("-Tsvg")
+        .arg("-o")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(error) => {
+            println!("Could not run graphviz `dot` command: {}", error);
+            return false;
+        }
+    };
+    if let Some(stdin) = child.stdin.as_mut() {
+        let _ = stdin.write_all(source.as_bytes());
+    }
+    match child.wait() {
+        Ok(status) if status.success() => true,
+        _ => {
+            println!("Could not render graphviz diagram: {:?}", path);
+            false
+        }
+    }
+}
+
+fn graph_header(name: &str, edge: &str) -> String {
+    format!(
+        "digraph \"{}\" {{\n    rankdir=BT;\n    node [shape=box, fontname=\"monospace\"];\n    edge [{}];\n",
+        name, edge
+    )
+}
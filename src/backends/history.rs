@@ -0,0 +1,212 @@
+use crate::{document::*, ensure_dir, read_file};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Directory next to config file that API history gets recorded in, out of published output.
+const HISTORY_DIR: &str = ".unreal-doc-cache";
+
+/// Name of file in output directory that API history was recorded in formerly, still read when
+/// there is no history recorded in `HISTORY_DIR` yet.
+const FORMER_HISTORY: &str = "api_history.json";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Dates (as `YYYY-MM-DD`) at which documented symbols first appeared, mapped by their code
+/// references, e.g. `class: UFoo` or `method: UFoo::Bar`. Symbols that were already documented
+/// when history started have no date.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiHistory {
+    #[serde(default)]
+    pub symbols: BTreeMap<String, Option<String>>,
+}
+
+impl ApiHistory {
+    /// Path of history recorded for given output directory, in cache directory next to config
+    /// file (in given root directory), so it never gets published along with output.
+    pub fn path(root: &Path, output_dir: &Path) -> PathBuf {
+        let relative = output_dir.strip_prefix(root).unwrap_or(output_dir);
+        let name = relative
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        root.join(HISTORY_DIR)
+            .join(format!("api_history.{}.json", name.trim_matches('_')))
+    }
+
+    /// Reads history recorded by previous baking into given output directory, if there is any.
+    pub fn load(root: &Path, output_dir: &Path) -> Option<Self> {
+        let path = Self::path(root, output_dir);
+        let (path, content) = match read_file(&path) {
+            Ok(content) => (path, content),
+            Err(_) => {
+                let path = output_dir.join(FORMER_HISTORY);
+                let content = read_file(&path).ok()?;
+                (path, content)
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(history) => Some(history),
+            Err(error) => {
+                println!(
+                    "Could not parse API history {:?}, starting new one: {}",
+                    path, error
+                );
+                None
+            }
+        }
+    }
+
+    /// Records history for next bakings into given output directory.
+    pub fn store(&self, root: &Path, output_dir: &Path) -> Result<(), Box<dyn Error>> {
+        let path = Self::path(root, output_dir);
+        let content = serde_json::to_string_pretty(self).expect("Could not serialize API history!");
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write API history: {:?} ({})", path, error))?;
+        Ok(())
+    }
+
+    /// History of document symbols: symbols missing in previous history are dated with given
+    /// date and ones not documented anymore are forgotten. Without previous history, all symbols
+    /// start new one undated.
+    pub fn update(previous: Option<&Self>, document: &Document, date: &str) -> Self {
+        let symbols = symbol_references(document)
+            .into_iter()
+            .map(|reference| {
+                let date = match previous {
+                    Some(previous) => previous
+                        .symbols
+                        .get(&reference)
+                        .cloned()
+                        .unwrap_or_else(|| Some(date.to_owned())),
+                    None => None,
+                };
+                (reference, date)
+            })
+            .collect();
+        Self { symbols }
+    }
+
+    /// Symbols that first appeared at given date or later, as pairs of date and code reference,
+    /// newest first.
+    pub fn added_since(&self, date: &str) -> Vec<(String, String)> {
+        let mut result = self
+            .symbols
+            .iter()
+            .filter_map(|(reference, added)| {
+                let added = added.as_deref()?;
+                (added >= date).then(|| (added.to_owned(), reference.to_owned()))
+            })
+            .collect::<Vec<_>>();
+        result.sort_by(|(a_date, a), (b_date, b)| b_date.cmp(a_date).then(a.cmp(b)));
+        result
+    }
+}
+
+/// Code references of all documented symbols and members.
+fn symbol_references(document: &Document) -> Vec<String> {
+    let mut result = vec![];
+    for item in &document.enums {
+        result.push(format!("enum: {}", item.name));
+    }
+    for item in document.structs.iter().chain(document.classes.iter()) {
+        result.push(format!("{}: {}", item.mode.signature(), item.name));
+        for property in &item.properties {
+            result.push(format!("property: {}::{}", item.name, property.name));
+        }
+        for method in &item.methods {
+            result.push(format!("method: {}::{}", item.name, method.name));
+        }
+    }
+    for item in &document.functions {
+        result.push(format!("function: {}", item.name));
+    }
+    for item in &document.delegates {
+        result.push(format!("delegate: {}", item.name));
+    }
+    result
+}
+
+/// Current date, or date of `SOURCE_DATE_EPOCH` timestamp if set, for reproducible builds,
+/// moved given number of days back.
+pub fn date_days_ago(days: usize) -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or_default()
+        });
+    civil_date(seconds.div_euclid(SECONDS_PER_DAY) - days as i64)
+}
+
+/// Gregorian calendar date of given day counted from Unix epoch.
+fn civil_date(days: i64) -> String {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[test]
+fn test_civil_date() {
+    assert_eq!(civil_date(0), "1970-01-01");
+    assert_eq!(civil_date(11_016), "2000-02-29");
+    assert_eq!(civil_date(20_742), "2026-10-16");
+    assert_eq!(civil_date(-1), "1969-12-31");
+}
+
+#[test]
+fn test_api_history() {
+    let mut document = Document {
+        functions: vec![Function {
+            name: "Main".to_owned(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let first = ApiHistory::update(None, &document, "2026-01-01");
+    assert_eq!(first.symbols["function: Main"], None);
+    assert!(first.added_since("2000-01-01").is_empty());
+
+    document.functions.push(Function {
+        name: "Other".to_owned(),
+        ..Default::default()
+    });
+    let second = ApiHistory::update(Some(&first), &document, "2026-02-01");
+    let third = ApiHistory::update(Some(&second), &document, "2026-03-01");
+    assert_eq!(
+        third.added_since("2026-01-15"),
+        vec![("2026-02-01".to_owned(), "function: Other".to_owned())]
+    );
+    assert!(third.added_since("2026-02-02").is_empty());
+}
+
+#[test]
+fn test_api_history_path() {
+    let root = Path::new("project");
+    assert_eq!(
+        ApiHistory::path(root, &root.join("./docs/mdbook")),
+        root.join(".unreal-doc-cache/api_history.docs_mdbook.json")
+    );
+}
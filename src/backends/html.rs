@@ -0,0 +1,458 @@
+use crate::{
+    backends::mdbook::{bake_book_files, page_count, prune_stale_files},
+    config::{BackendHtml, Config},
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::{Document, markdown_options},
+    ensure_dir,
+    hooks::run_hooks,
+    read_file,
+    report::BakeStats,
+    staging::Staging,
+};
+use fs_extra::{copy_items, dir::CopyOptions};
+use pulldown_cmark::{CowStr, Event, Parser, Tag, TagEnd, html::push_html};
+use regex::Regex;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fs::write,
+    path::Path,
+};
+
+/// Template of every site page, with `{{name}}` placeholders.
+const PAGE_TEMPLATE: &str = include_str!("page.html");
+
+/// Page entry of client-side search index.
+#[derive(Serialize)]
+struct SearchEntry {
+    title: String,
+    path: String,
+    text: String,
+    /// Tags from front matter of book pages.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// straight into self-contained static HTML site with navigation and client-side search, so
+/// `mdbook` command is not needed to publish documentation.
+pub fn bake_html(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let options = config.backend_html.as_ref().cloned().unwrap_or_default();
+    // marketplace packages have the whole site in `docs` directory
+    let site_dir = if options.marketplace { "docs/" } else { "" };
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (mut pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        &output_dir.join(site_dir),
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let mut stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    if mdbook.permalinks {
+        pages.insert(
+            "permalinks.js".to_owned(),
+            include_str!("permalinks.js").to_owned(),
+        );
+        pages.insert(
+            "permalinks.css".to_owned(),
+            include_str!("permalinks.css").to_owned(),
+        );
+    }
+    let site_url = mdbook.site_url.as_deref().unwrap_or("/");
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+
+    let mut files = BTreeMap::new();
+    let mut search_index = vec![];
+    for (path, content) in &pages {
+        let Some(page) = path
+            .strip_prefix("src/")
+            .and_then(|path| path.strip_suffix(".md"))
+        else {
+            files.insert(path.to_owned(), content.to_owned());
+            continue;
+        };
+        if page == "SUMMARY" {
+            continue;
+        }
+        // marketplace packages can only link relatively, so they can be opened from any place
+        let page_root = if options.marketplace {
+            "../".repeat(page.matches('/').count())
+        } else {
+            site_url.to_owned()
+        };
+        let (title, text, body) = render_markdown(content, site_url, &page_root);
+        let title = title.unwrap_or_else(|| page.rsplit('/').next().unwrap_or(page).to_owned());
+        let styles = pages
+            .keys()
+            .filter(|path| path.ends_with(".css"))
+            .map(|path| {
+                format!(
+                    "    <link rel=\"stylesheet\" href=\"{}{}\">\n",
+                    page_root, path
+                )
+            })
+            .collect::<String>();
+        let scripts = pages
+            .keys()
+            .filter(|path| path.ends_with(".js"))
+            .map(|path| format!("    <script src=\"{}{}\"></script>\n", page_root, path))
+            .collect::<String>();
+        let html = PAGE_TEMPLATE
+            .replace("{{language}}", &escape_html(&mdbook.language))
+            .replace("{{page_title}}", &escape_html(&title))
+            .replace("{{title}}", &escape_html(&mdbook.title))
+            .replace("{{styles}}", &styles)
+            .replace("{{scripts}}", &scripts)
+            .replace(
+                "{{navigation}}",
+                &render_navigation(summary, site_url, &page_root),
+            )
+            .replace("{{root}}", &page_root)
+            .replace("{{content}}", &body);
+        let tags = document
+            .front_matter
+            .get(&format!("{}.md", page))
+            .map(|front_matter| front_matter.tags.to_owned())
+            .unwrap_or_default();
+        let path = format!("{}.html", page);
+        search_index.push(SearchEntry {
+            title,
+            path: path.to_owned(),
+            text,
+            tags,
+        });
+        files.insert(path, html);
+    }
+    // like in MD Book, first chapter is also the landing page
+    if !files.contains_key("index.html")
+        && let Some(first) = first_chapter(summary).and_then(|path| files.get(&path))
+    {
+        files.insert("index.html".to_owned(), first.to_owned());
+    }
+    files.insert(
+        "search_index.js".to_owned(),
+        format!(
+            "var searchIndex = {};\n",
+            serde_json::to_string(&search_index).expect("Could not serialize search index!")
+        ),
+    );
+    files.insert("site.css".to_owned(), include_str!("site.css").to_owned());
+    files.insert("search.js".to_owned(), include_str!("search.js").to_owned());
+    let files = files
+        .into_iter()
+        .map(|(path, content)| (format!("{}{}", site_dir, path), content))
+        .collect::<BTreeMap<_, _>>();
+    if diff_output {
+        preview_changes(
+            &config.output_dir,
+            &files,
+            Some(&format!("{}reference", site_dir)),
+            "html",
+        );
+        return Ok(stats);
+    }
+    let generated = files
+        .keys()
+        .cloned()
+        .chain(
+            diagrams
+                .values()
+                .flatten()
+                .map(|(_, path)| format!("{}{}", site_dir, path)),
+        )
+        .collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write site file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = mdbook.assets.as_ref() {
+        let from = root.join(assets);
+        let to = output_dir.join(site_dir).join("assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    if options.marketplace {
+        let mut diagnostics = Diagnostics::default();
+        check_marketplace_package(&output_dir.join(site_dir), &options, &mut diagnostics);
+        stats.diagnostics.extend(diagnostics.items.iter().cloned());
+        diagnostics.report()?;
+    }
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Renders Markdown page into HTML, returning its title (first heading), plain text for search
+/// and HTML content. Headings get anchors like in MD Book, so section links keep working. Links
+/// starting with site URL get it replaced with given page root.
+fn render_markdown(content: &str, site_url: &str, root: &str) -> (Option<String>, String, String) {
+    let mut events = Parser::new_ext(content, markdown_options())
+        .map(|event| html_link_event(event, site_url, root))
+        .collect::<Vec<_>>();
+    let mut title = None;
+    let mut text = vec![];
+    let mut used_ids = HashMap::<String, usize>::new();
+    let mut heading_start = None;
+    let mut heading_text = String::default();
+    for index in 0..events.len() {
+        match &events[index] {
+            Event::Start(Tag::Heading { .. }) => {
+                heading_start = Some(index);
+                heading_text.clear();
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some(start) = heading_start.take() else {
+                    continue;
+                };
+                if title.is_none() {
+                    title = Some(heading_text.trim().to_owned());
+                }
+                let id = normalize_id(&heading_text);
+                let count = used_ids.entry(id.to_owned()).or_default();
+                let unique_id = if *count == 0 {
+                    id
+                } else {
+                    format!("{}-{}", id, count)
+                };
+                *count += 1;
+                if let Event::Start(Tag::Heading { id, .. }) = &mut events[start]
+                    && id.is_none()
+                {
+                    *id = Some(CowStr::from(unique_id));
+                }
+            }
+            Event::Text(value) | Event::Code(value) => {
+                if heading_start.is_some() {
+                    heading_text.push_str(value);
+                }
+                text.push(value.to_string());
+            }
+            _ => {}
+        }
+    }
+    let mut html = String::default();
+    push_html(&mut html, events.into_iter());
+    let text = text
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (title, text, html)
+}
+
+/// Renders book index into navigation list shown on every page, with links starting at given
+/// page root.
+fn render_navigation(summary: &str, site_url: &str, root: &str) -> String {
+    let summary = summary.strip_prefix("# Index").unwrap_or(summary);
+    let events = Parser::new_ext(summary, markdown_options()).map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !dest_url.starts_with('/') && !dest_url.contains("://") => html_link_event(
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url: format!("{}{}", site_url, dest_url).into(),
+                title,
+                id,
+            }),
+            site_url,
+            root,
+        ),
+        event => html_link_event(event, site_url, root),
+    });
+    let mut result = String::default();
+    push_html(&mut result, events);
+    result
+}
+
+/// Path of site page of first chapter in book index.
+fn first_chapter(summary: &str) -> Option<String> {
+    Parser::new(summary).find_map(|event| match event {
+        Event::Start(Tag::Link { dest_url, .. }) => dest_url
+            .strip_suffix(".md")
+            .map(|path| format!("{}.html", path.trim_start_matches('/'))),
+        _ => None,
+    })
+}
+
+/// Points links to Markdown pages at their HTML pages, with site URL replaced by given root.
+fn html_link_event<'a>(event: Event<'a>, site_url: &str, root: &str) -> Event<'a> {
+    let Event::Start(Tag::Link {
+        link_type,
+        dest_url,
+        title,
+        id,
+    }) = event
+    else {
+        return event;
+    };
+    let dest_url = match dest_url.strip_prefix(site_url) {
+        Some(path) => format!("{}{}", root, path).into(),
+        None => dest_url,
+    };
+    let dest_url = if dest_url.contains("://") {
+        dest_url
+    } else {
+        let (path, section) = match dest_url.split_once('#') {
+            Some((path, section)) => (path, Some(section)),
+            None => (dest_url.as_ref(), None),
+        };
+        match (path.strip_suffix(".md"), section) {
+            (Some(path), Some(section)) => format!("{}.html#{}", path, section).into(),
+            (Some(path), None) => format!("{}.html", path).into(),
+            _ => dest_url,
+        }
+    };
+    Event::Start(Tag::Link {
+        link_type,
+        dest_url,
+        title,
+        id,
+    })
+}
+
+/// Heading anchor the same way MD Book makes them, e.g. `Max Speed` gives `max-speed`.
+fn normalize_id(content: &str) -> String {
+    content
+        .trim()
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                Some(c.to_ascii_lowercase())
+            } else if c.is_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Reports files of marketplace package that are too big, links that are not relative and
+/// resources loaded from outside of package.
+fn check_marketplace_package(
+    site_dir: &Path,
+    options: &BackendHtml,
+    diagnostics: &mut Diagnostics,
+) {
+    let attribute = Regex::new(r#"<(\w+)\b[^>]*?\s(href|src)\s*=\s*"([^"]*)""#).unwrap();
+    let mut pending = vec![site_dir.to_owned()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = dir.read_dir() else {
+            continue;
+        };
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let page = path
+                .strip_prefix(site_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let size = path.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            if let Some(max_file_size) = options.max_file_size
+                && size > max_file_size
+            {
+                diagnostics.error(
+                    "marketplace",
+                    &page,
+                    format!(
+                        "File has {} bytes, more than allowed {} bytes",
+                        size, max_file_size
+                    ),
+                );
+            }
+            if path.extension().is_none_or(|extension| extension != "html") {
+                continue;
+            }
+            let Ok(content) = read_file(&path) else {
+                continue;
+            };
+            for captures in attribute.captures_iter(&content) {
+                let url = &captures[3];
+                if url.starts_with("//") || url.contains("://") {
+                    // hyperlinks to web pages are fine, loading resources from there is not
+                    if &captures[1] != "a" {
+                        diagnostics.error(
+                            "marketplace",
+                            &page,
+                            format!("External resource: {}", url),
+                        );
+                    }
+                } else if url.starts_with('/') {
+                    diagnostics.error(
+                        "marketplace",
+                        &page,
+                        format!("Link is not relative: {}", url),
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub fn escape_html(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_render_markdown() {
+    let (title, text, html) = render_markdown(
+        "# Foo Bar\n\nSee [`UFoo`](/reference/classes/UFoo.md#speed).\n\n## Speed\n\n## Speed\n",
+        "/",
+        "../",
+    );
+    assert_eq!(title.as_deref(), Some("Foo Bar"));
+    assert_eq!(text, "Foo Bar See UFoo . Speed Speed");
+    assert!(html.contains(r#"<h1 id="foo-bar">"#));
+    assert!(html.contains(r#"href="../reference/classes/UFoo.html#speed""#));
+    assert!(html.contains(r#"<h2 id="speed">"#));
+    assert!(html.contains(r#"<h2 id="speed-1">"#));
+}
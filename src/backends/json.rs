@@ -1,11 +1,117 @@
-use crate::{config::Config, document::Document, ensure_dir};
-use std::fs::write;
+use crate::{
+    config::{BackendJson, Config},
+    diff::preview_changes,
+    document::Document,
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::{rename, write},
+    path::Path,
+    process,
+};
 
-pub fn bake_json(document: &Document, config: &Config) {
-    let content =
-        serde_json::to_string_pretty(&document).expect("Could not serialize document into JSON!");
+/// Version of versioned JSON output schema, bumped whenever document items change in a way that
+/// breaks tools reading them.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Bakes document into `documentation.json` file of output directory. Errors of writing it are
+/// returned.
+pub fn bake_json(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let content = document_json(
+        document,
+        &config.backend_json.as_ref().cloned().unwrap_or_default(),
+    );
+    let stats = BakeStats {
+        pages: 1,
+        ..Default::default()
+    };
+    if diff_output {
+        let files = BTreeMap::from([("documentation.json".to_owned(), content)]);
+        preview_changes(&config.output_dir, &files, None, "json");
+        return Ok(stats);
+    }
     let path = config.output_dir.join("documentation.json");
     ensure_dir(&path);
-    write(&path, content)
-        .unwrap_or_else(|_| panic!("Could not write document into JSON file: {:?}", path));
+    // written next to target first, so it never gets read half-written
+    let staged = path.with_extension(format!("json.{}.tmp", process::id()));
+    write(&staged, content).map_err(|error| {
+        format!(
+            "Could not write document into JSON file: {:?} ({})",
+            staged, error
+        )
+    })?;
+    rename(&staged, &path).map_err(|error| {
+        format!(
+            "Could not write document into JSON file: {:?} ({})",
+            path, error
+        )
+    })?;
+    run_hooks(&config.hooks.post_bake, config, root, &config.output_dir)?;
+    Ok(stats)
+}
+
+fn document_json(document: &Document, options: &BackendJson) -> String {
+    // going through JSON value sorts maps by keys, so output is stable between runs
+    let mut value =
+        serde_json::to_value(document).expect("Could not serialize document into JSON!");
+    if options.versioned {
+        value = json!({
+            "schema_version": SCHEMA_VERSION,
+            "generator": format!("unreal-doc {}", env!("CARGO_PKG_VERSION")),
+            "document": value,
+        });
+    }
+    if options.pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    }
+    .expect("Could not serialize document into JSON!")
+}
+
+#[test]
+fn test_document_json() {
+    let mut document = Document::default();
+    for name in ["b", "a", "c"] {
+        document
+            .snippets
+            .insert(name.to_owned(), name.to_uppercase());
+    }
+    let options = BackendJson {
+        versioned: true,
+        pretty: false,
+    };
+    let content = document_json(&document, &options);
+    assert!(content.contains(r#""schema_version":1"#));
+    assert!(content.contains(r#""snippets":{"a":"A","b":"B","c":"C"}"#));
+    let value = serde_json::from_str::<serde_json::Value>(&content).unwrap();
+    serde_json::from_value::<Document>(value["document"].clone()).unwrap();
+}
+
+#[test]
+fn test_bake_json_error() {
+    // output directory taken by file can not be written into
+    let output_dir = std::env::temp_dir().join(format!("unreal-doc-json-{}", process::id()));
+    write(&output_dir, "").unwrap();
+    let config = Config {
+        output_dir: output_dir.to_owned(),
+        ..Default::default()
+    };
+    let error = bake_json(&Document::default(), &config, Path::new("."), false).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Could not write document into JSON file")
+    );
+    std::fs::remove_file(&output_dir).unwrap();
 }
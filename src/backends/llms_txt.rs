@@ -0,0 +1,365 @@
+use crate::{
+    backends::{
+        docusaurus::summary_entries,
+        markdown::book_order,
+        mdbook::{bake_book_files, page_count, page_stem, prune_stale_files},
+    },
+    config::{BackendLlmsTxt, Config},
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::{Document, StructClassMode},
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+    staging::Staging,
+};
+use regex::{Captures, Regex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs::write,
+    path::Path,
+};
+
+/// Name of index file in output directory, following `llms.txt` convention.
+const INDEX_FILE: &str = "llms.txt";
+
+/// Name of file with all pages in output directory, split into chunks.
+const FULL_FILE: &str = "llms-full.txt";
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// into plain text files meant to be fed to language models and retrieval pipelines: `llms.txt`
+/// index linking published pages and `llms-full.txt` with all pages in book index order, split
+/// into chunks with stable delimiters and metadata headers.
+pub fn bake_llms_txt(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let options = config
+        .backend_llms_txt
+        .as_ref()
+        .cloned()
+        .unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (pages, _) = bake_book_files(
+        document,
+        config,
+        root,
+        output_dir,
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    let symbols = symbol_pages(document);
+    let files = BTreeMap::from([
+        (
+            INDEX_FILE.to_owned(),
+            index_file(&pages, &mdbook.title, &options),
+        ),
+        (
+            FULL_FILE.to_owned(),
+            full_file(&pages, &symbols, &mdbook.title, &options),
+        ),
+    ]);
+    if diff_output {
+        preview_changes(&config.output_dir, &files, None, "txt");
+        return Ok(stats);
+    }
+    // diagrams are left out, so they get pruned along with other stale files
+    let generated = files.keys().cloned().collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write llms.txt file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Kind and header file name of symbols, mapped by paths of their pages (without extension).
+fn symbol_pages(document: &Document) -> BTreeMap<String, (&'static str, &str)> {
+    let mut result = BTreeMap::new();
+    let mut insert = |kind: &'static str, directory: &str, name: &str, filename| {
+        result.insert(
+            format!(
+                "reference/{}/{}",
+                directory,
+                page_stem(document, directory, name)
+            ),
+            (kind, filename),
+        );
+    };
+    for item in &document.enums {
+        insert("enum", "enums", &item.name, item.filename.as_str());
+    }
+    for item in document.structs.iter().chain(document.classes.iter()) {
+        match item.mode {
+            StructClassMode::Struct => insert("struct", "structs", &item.name, &item.filename),
+            StructClassMode::Class => insert("class", "classes", &item.name, &item.filename),
+        }
+    }
+    for item in &document.functions {
+        insert("function", "functions", &item.name, &item.filename);
+    }
+    for item in &document.delegates {
+        insert("delegate", "delegates", &item.name, &item.filename);
+    }
+    result
+}
+
+/// URL of published page, relative to place of `llms.txt` file when there is no base URL.
+fn page_url(page: &str, options: &BackendLlmsTxt) -> String {
+    format!(
+        "{}{}.html",
+        options.base_url.as_deref().unwrap_or_default(),
+        page
+    )
+}
+
+/// Index following `llms.txt` convention: title, description and book index linking published
+/// pages.
+fn index_file(pages: &BTreeMap<String, String>, title: &str, options: &BackendLlmsTxt) -> String {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let mut result = format!("# {}\n\n", title);
+    if let Some(description) = options.description.as_ref() {
+        result.push_str(&format!("> {}\n\n", description));
+    }
+    result.push_str(&format!(
+        "Whole documentation as plain text: [{}]({}{})\n\n## Contents\n\n",
+        FULL_FILE,
+        options.base_url.as_deref().unwrap_or_default(),
+        FULL_FILE
+    ));
+    for (level, label, page) in summary_entries(summary) {
+        result.push_str(&format!(
+            "{}- [{}]({})\n",
+            "  ".repeat(level),
+            label,
+            page_url(&page, options)
+        ));
+    }
+    result
+}
+
+/// All pages in book index order, each split into chunks of at most `chunk_size` characters
+/// (unless single section of page is longer) with metadata header on top of every chunk.
+fn full_file(
+    pages: &BTreeMap<String, String>,
+    symbols: &BTreeMap<String, (&'static str, &str)>,
+    title: &str,
+    options: &BackendLlmsTxt,
+) -> String {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let labels = summary_entries(summary)
+        .into_iter()
+        .map(|(_, label, page)| (page, label))
+        .collect::<BTreeMap<_, _>>();
+    let mut result = format!("# {}\n\n", title);
+    if let Some(description) = options.description.as_ref() {
+        result.push_str(&format!("> {}\n\n", description));
+    }
+    result.push_str(&format!(
+        "generator: unreal-doc {}\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    for page in book_order(pages) {
+        let content = plain_content(&pages[&format!("src/{}.md", page)]);
+        let chunks = page_chunks(&content, options.chunk_size);
+        let (kind, header) = match symbols.get(&page) {
+            Some((kind, header)) => (*kind, Some(*header)),
+            None if page.starts_with("reference") => ("reference", None),
+            None => ("page", None),
+        };
+        let title = labels
+            .get(&page)
+            .map(|label| label.as_str())
+            .unwrap_or_else(|| page.rsplit('/').next().unwrap_or_default());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let id = format!("{}#{}", page, index + 1);
+            result.push_str(&format!(
+                "\n<<< chunk: {} >>>\ntitle: {}\nkind: {}\n",
+                id, title, kind
+            ));
+            if let Some(header) = header {
+                result.push_str(&format!("header: {}\n", header));
+            }
+            result.push_str(&format!(
+                "url: {}\npart: {}/{}\n\n{}\n<<< end: {} >>>\n",
+                page_url(&page, options),
+                index + 1,
+                chunks.len(),
+                chunk,
+                id
+            ));
+        }
+    }
+    result
+}
+
+/// Page content with markup that carries no meaning in plain text removed: images, layout HTML
+/// tags and links to other pages (their labels are kept). Code blocks are left untouched.
+fn plain_content(content: &str) -> String {
+    let image = Regex::new(r"!\[[^\]]*\]\([^()\s]*\)").unwrap();
+    let tag =
+        Regex::new(r"</?(?:div|span|details|summary|a|br|img|p|sup|sub|kbd)\b[^<>]*>").unwrap();
+    let link = Regex::new(r"\[([^\]]*)\]\(([^()\s]*)\)").unwrap();
+    let mut result = String::with_capacity(content.len());
+    let mut fence = None;
+    let mut blank = true;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            result.push_str(line);
+            result.push('\n');
+            blank = false;
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            result.push_str(line);
+            result.push('\n');
+            blank = false;
+            continue;
+        }
+        let line = image.replace_all(line, "");
+        let line = tag.replace_all(&line, "");
+        let line = link.replace_all(&line, |captures: &Captures| {
+            let url = &captures[2];
+            if url.contains("://") || url.starts_with("mailto:") {
+                captures[0].to_owned()
+            } else {
+                captures[1].to_owned()
+            }
+        });
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            // layout tags leave runs of empty lines behind
+            if !blank {
+                result.push('\n');
+            }
+            blank = true;
+        } else {
+            result.push_str(line);
+            result.push('\n');
+            blank = false;
+        }
+    }
+    result.trim().to_owned()
+}
+
+/// Splits content into chunks at headings (outside of code blocks), putting as many following
+/// sections in single chunk as fit in given size.
+fn page_chunks(content: &str, chunk_size: usize) -> Vec<String> {
+    let heading = Regex::new(r"^\s*(?:\* )?#+ ").unwrap();
+    let mut sections = vec![String::new()];
+    let mut fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = !fence;
+        }
+        if !fence && heading.is_match(line) && !sections.last().unwrap().trim().is_empty() {
+            sections.push(String::new());
+        }
+        let section = sections.last_mut().unwrap();
+        section.push_str(line);
+        section.push('\n');
+    }
+    let mut result = Vec::<String>::new();
+    for section in sections {
+        match result.last_mut() {
+            Some(chunk) if chunk.chars().count() + section.chars().count() <= chunk_size => {
+                chunk.push_str(&section);
+            }
+            _ => result.push(section),
+        }
+    }
+    result
+        .into_iter()
+        .map(|chunk| chunk.trim().to_owned())
+        .filter(|chunk| !chunk.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_full_file() {
+    let pages = BTreeMap::from([
+        (
+            "src/SUMMARY.md".to_owned(),
+            "# Index\n\n- [Hello](hello.md)\n- [Reference](reference.md)\n  \
+            - [UFoo](reference/classes/UFoo.md)\n"
+                .to_owned(),
+        ),
+        (
+            "src/hello.md".to_owned(),
+            "# Hello\n\nSee [`UFoo`](/reference/classes/UFoo.md#methods) and \
+            [site](https://example.com).\n"
+                .to_owned(),
+        ),
+        ("src/reference.md".to_owned(), "# Reference\n".to_owned()),
+        (
+            "src/reference/classes/UFoo.md".to_owned(),
+            "# UFoo\n\n![Diagram](../../assets/diagrams/UFoo.svg)\n\n<div class=\"ue-signature\">\n\n\
+            ```cpp\nclass UFoo;\nTArray<int32> <div>\n```\n\n</div>\n\n## Methods\n\n\
+            * ### __`Bar`__\n\n    Does bar.\n"
+                .to_owned(),
+        ),
+    ]);
+    let symbols = BTreeMap::from([("reference/classes/UFoo".to_owned(), ("class", "Foo.h"))]);
+    let options = BackendLlmsTxt {
+        base_url: Some("https://docs.example.com/".to_owned()),
+        chunk_size: 40,
+        ..Default::default()
+    };
+    let content = full_file(&pages, &symbols, "Docs", &options);
+    assert!(content.contains(
+        "<<< chunk: hello#1 >>>\ntitle: Hello\nkind: page\n\
+        url: https://docs.example.com/hello.html\npart: 1/1\n\n# Hello\n\n\
+        See `UFoo` and [site](https://example.com).\n<<< end: hello#1 >>>\n"
+    ));
+    assert!(content.contains(
+        "<<< chunk: reference/classes/UFoo#1 >>>\ntitle: UFoo\nkind: class\nheader: Foo.h\n"
+    ));
+    assert!(
+        content
+            .contains("part: 1/3\n\n# UFoo\n\n```cpp\nclass UFoo;\nTArray<int32> <div>\n```\n<<<")
+    );
+    assert!(content.contains("part: 3/3\n\n* ### __`Bar`__\n\n    Does bar.\n<<<"));
+    assert!(content.find("hello#1").unwrap() < content.find("reference#1").unwrap());
+
+    let index = index_file(&pages, "Docs", &options);
+    assert!(index.contains("  - [UFoo](https://docs.example.com/reference/classes/UFoo.html)\n"));
+}
@@ -0,0 +1,258 @@
+use crate::{
+    backends::{
+        docusaurus::summary_entries,
+        mdbook::{bake_book_files, page_count, prune_stale_files},
+    },
+    config::Config,
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::Document,
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+    staging::Staging,
+};
+use fs_extra::{copy_items, dir::CopyOptions};
+use regex::{Captures, Regex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs::write,
+    path::Path,
+};
+
+/// Name of baked Markdown file in output directory.
+const MARKDOWN_FILE: &str = "documentation.md";
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// into single Markdown file, with pages put one after another in book index order, table of
+/// contents on top and links between pages pointing at anchors of these pages.
+pub fn bake_markdown(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        output_dir,
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    let site_url = mdbook.site_url.as_deref().unwrap_or("/");
+    let files = BTreeMap::from([(
+        MARKDOWN_FILE.to_owned(),
+        single_file(&pages, &mdbook.title, site_url),
+    )]);
+    if diff_output {
+        preview_changes(&config.output_dir, &files, None, "md");
+        return Ok(stats);
+    }
+    let generated = files
+        .keys()
+        .cloned()
+        .chain(diagrams.values().flatten().map(|(_, path)| path.to_owned()))
+        .collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write Markdown file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = mdbook.assets.as_ref() {
+        let from = root.join(assets);
+        let to = output_dir.join("assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Content of single Markdown file made of book pages: title, table of contents and pages in
+/// book index order, each one preceded by its anchor. Pages missing in book index go last.
+fn single_file(pages: &BTreeMap<String, String>, title: &str, site_url: &str) -> String {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let mut result = format!("# {}\n\n## Contents\n\n", title);
+    for (level, label, page) in summary_entries(summary) {
+        result.push_str(&format!(
+            "{}- [{}](#{})\n",
+            "  ".repeat(level),
+            label,
+            page_anchor(&page)
+        ));
+    }
+    for page in book_order(pages) {
+        result.push_str(&format!(
+            "\n---\n\n<a id=\"{}\"></a>\n\n{}\n",
+            page_anchor(&page),
+            relink_content(pages[&format!("src/{}.md", page)].trim(), &page, site_url)
+        ));
+    }
+    result
+}
+
+/// Pages (paths without extension) in book index order, each listed once, followed by pages
+/// missing in book index.
+pub fn book_order(pages: &BTreeMap<String, String>) -> Vec<String> {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let listed = summary_entries(summary)
+        .into_iter()
+        .map(|(_, _, page)| page)
+        .chain(
+            pages
+                .keys()
+                .filter_map(|path| path.strip_prefix("src/")?.strip_suffix(".md"))
+                .filter(|page| *page != "SUMMARY")
+                .map(|page| page.to_owned()),
+        );
+    let mut included = BTreeSet::new();
+    listed
+        .filter(|page| pages.contains_key(&format!("src/{}.md", page)))
+        .filter(|page| included.insert(page.to_owned()))
+        .collect()
+}
+
+/// Anchor of page in single file, e.g. `reference-classes-ufoo` for `reference/classes/UFoo`.
+pub fn page_anchor(page: &str) -> String {
+    page.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Makes links of page work from single file: links to pages point at their anchors and links
+/// to other files become relative to output directory. Code blocks are left untouched.
+pub fn relink_content(content: &str, page: &str, site_url: &str) -> String {
+    let link = Regex::new(r"\]\(([^()\s]+)\)").unwrap();
+    let mut result = String::with_capacity(content.len());
+    let mut fence = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            result.push_str(line);
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            result.push_str(line);
+            continue;
+        }
+        result.push_str(&link.replace_all(line, |captures: &Captures| {
+            format!("]({})", relink(&captures[1], page, site_url))
+        }));
+    }
+    result
+}
+
+fn relink(url: &str, page: &str, site_url: &str) -> String {
+    if url.contains("://") || url.starts_with('#') || url.starts_with("mailto:") {
+        return url.to_owned();
+    }
+    let path = url.split('#').next().unwrap_or(url);
+    let path = match path
+        .strip_prefix(site_url)
+        .or_else(|| path.strip_prefix('/'))
+    {
+        Some(path) => path.to_owned(),
+        None => resolve_relative(path, page),
+    };
+    match path.strip_suffix(".md") {
+        Some(target) => format!("#{}", page_anchor(target)),
+        None => path,
+    }
+}
+
+/// Path relative to book root of given path relative to directory of given page.
+pub fn resolve_relative(path: &str, page: &str) -> String {
+    let mut parts = page.split('/').collect::<Vec<_>>();
+    parts.pop();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+#[test]
+fn test_single_file() {
+    let pages = BTreeMap::from([
+        (
+            "src/SUMMARY.md".to_owned(),
+            "# Index\n\n- [Hello](hello.md)\n- [Reference](reference.md)\n  \
+            - [UFoo](reference/classes/UFoo.md)\n"
+                .to_owned(),
+        ),
+        (
+            "src/hello.md".to_owned(),
+            "# Hello\n\nSee [`UFoo`](/reference/classes/UFoo.md#methods).\n\n\
+            ```md\n[raw](/hello.md)\n```\n"
+                .to_owned(),
+        ),
+        ("src/reference.md".to_owned(), "# Reference\n".to_owned()),
+        (
+            "src/reference/classes/UFoo.md".to_owned(),
+            "# UFoo\n\n![Diagram](../../assets/diagrams/UFoo.svg)\n[Back](../../hello.md)\n"
+                .to_owned(),
+        ),
+    ]);
+    let content = single_file(&pages, "Docs", "/");
+    assert!(content.starts_with("# Docs\n\n## Contents\n\n- [Hello](#hello)\n"));
+    assert!(content.contains("  - [UFoo](#reference-classes-ufoo)\n"));
+    assert!(content.contains("See [`UFoo`](#reference-classes-ufoo)."));
+    assert!(content.contains("[raw](/hello.md)"));
+    assert!(content.contains("![Diagram](assets/diagrams/UFoo.svg)\n[Back](#hello)"));
+    assert!(
+        content.find("<a id=\"hello\">").unwrap()
+            < content.find("<a id=\"reference-classes-ufoo\">").unwrap()
+    );
+}
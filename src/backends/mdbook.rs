@@ -1,10 +1,33 @@
-use crate::{config::*, document::*, ensure_dir, read_file};
+use crate::{
+    backends::{
+        graphviz::{composition_graph, hierarchy_graph, inheritance_graph, render_svg},
+        history::{ApiHistory, date_days_ago},
+        mermaid::{hierarchy_chart, inheritance_chart},
+        templates::Templates,
+    },
+    config::*,
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::*,
+    ensure_dir,
+    hooks::{run_book_hooks, run_hooks},
+    lint::prose,
+    read_file,
+    report::BakeStats,
+    staging::Staging,
+};
 use fs_extra::{copy_items, dir::CopyOptions};
+use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use serde::Serialize;
 use std::{
-    collections::HashMap,  fs::{remove_dir_all, write}, path::Path, process::Command
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fs::{remove_dir, remove_file, write},
+    path::{Component, Path},
+    process::Command,
 };
+use tera::Context;
 
 #[derive(Serialize)]
 struct Book {
@@ -35,6 +58,10 @@ pub struct BookHtml {
     no_section_label: bool,
     site_url: String,
     fold: BookFold,
+    additional_js: Vec<String>,
+    additional_css: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    redirect: BTreeMap<String, String>,
 }
 
 #[derive(Serialize)]
@@ -43,18 +70,255 @@ pub struct BookFold {
     level: usize,
 }
 
-pub fn bake_mdbook(document: &Document, config: &Config, root: &Path) {
-    let cleanup = config
+/// Bakes book into output directory and builds it with `mdbook` when configured. Errors of
+/// writing book files, copying assets, running hooks and building book are returned, as well as
+/// diagnostics errors found in pages.
+pub fn bake_mdbook(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (mut pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        &output_dir.join("src"),
+        diff_output,
+        &mut diagnostics,
+    )?;
+    // pages got reported already, so problems of manifest get reported on their own
+    let mut manifest_diagnostics = Diagnostics::default();
+    pages.insert(
+        "book.toml".to_owned(),
+        manifest(&mdbook, document, &mut manifest_diagnostics),
+    );
+    manifest_diagnostics.report()?;
+    diagnostics.items.extend(manifest_diagnostics.items);
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    if mdbook.permalinks {
+        pages.insert(
+            "permalinks.js".to_owned(),
+            include_str!("permalinks.js").to_owned(),
+        );
+        pages.insert(
+            "permalinks.css".to_owned(),
+            include_str!("permalinks.css").to_owned(),
+        );
+    }
+    if diff_output {
+        preview_changes(&config.output_dir, &pages, Some("src"), "md");
+        return Ok(stats);
+    }
+    let generated = pages
+        .keys()
+        .cloned()
+        .chain(
+            diagrams
+                .values()
+                .flatten()
+                .map(|(_, path)| format!("src/{}", path)),
+        )
+        .collect::<BTreeSet<_>>();
+    for (path, content) in pages {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write mdbook file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = config
+        .backend_mdbook
+        .as_ref()
+        .and_then(|mdbook| mdbook.assets.as_ref())
+    {
+        let from = root.join(assets);
+        let to = output_dir.join("src/assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+
+    let build = config
+        .backend_mdbook
+        .as_ref()
+        .map(|mdbook| mdbook.build)
+        .unwrap_or_default();
+    if build {
+        let status = Command::new("mdbook")
+            .arg("build")
+            .arg(output_dir)
+            .status()
+            .map_err(|error| format!("Could not build documentation with mdbook: {}", error))?;
+        if !status.success() {
+            return Err(format!("Could not build documentation with mdbook: {}", status).into());
+        }
+        run_book_hooks(&config.hooks.post_build, config, root, output_dir)?;
+    }
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Book files baked from document, mapped by their paths relative to output directory (with
+/// Markdown pages in `src` directory), along with diagrams rendered into given book sources
+/// directory. Problems found in pages get collected into given diagnostics and reported, and
+/// baking fails when any of them is an error.
+pub fn bake_book_files(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    source_dir: &Path,
+    diff_output: bool,
+    diagnostics: &mut Diagnostics,
+) -> Result<(BTreeMap<String, String>, Diagrams), Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let diagrams = if mdbook.graphviz {
+        bake_diagrams(document, &mdbook, source_dir, diff_output)
+    } else {
+        Default::default()
+    };
+
+    let header = config
+        .backend_mdbook
+        .as_ref()
+        .and_then(|mdbook| mdbook.header.as_ref())
+        .map(|path| {
+            read_file(root.join(path))
+                .map(|header| header + "\n")
+                .map_err(|error| format!("Could not read header file: {:?} ({})", path, error))
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let footer = config
         .backend_mdbook
         .as_ref()
-        .map(|mdbook| mdbook.cleanup)
+        .and_then(|mdbook| mdbook.footer.as_ref())
+        .map(|path| {
+            read_file(root.join(path))
+                .map(|footer| "\n".to_owned() + &footer)
+                .map_err(|error| format!("Could not read footer file: {:?} ({})", path, error))
+        })
+        .transpose()?
         .unwrap_or_default();
-    if cleanup {
-        let _ = remove_dir_all(&config.output_dir);
+    // symbols missing in history of previous baking are the ones added since then
+    let history = ApiHistory::update(
+        ApiHistory::load(root, &config.output_dir).as_ref(),
+        document,
+        &date_days_ago(0),
+    );
+    if !diff_output {
+        history.store(root, &config.output_dir)?;
+    }
+    let recent = history.added_since(&date_days_ago(mdbook.recent_days));
+    let templates = Templates::load(&mdbook.templates, &mdbook.variables, root);
+    let mut pages = render_pages(
+        document,
+        &mdbook,
+        &templates,
+        &diagrams,
+        &recent,
+        &header,
+        &footer,
+        diagnostics,
+    );
+    diagnostics.report()?;
+    if has_stability(document) {
+        pages.insert(
+            "stability.css".to_owned(),
+            include_str!("stability.css").to_owned(),
+        );
     }
+    Ok((pages, diagrams))
+}
+
+/// Number of documentation pages among book files baked by `bake_book_files`.
+pub fn page_count(pages: &BTreeMap<String, String>) -> usize {
+    pages
+        .keys()
+        .filter(|path| path.ends_with(".md") && *path != "src/SUMMARY.md")
+        .count()
+}
 
-    write_manifest(config);
+/// Name of file in output directory that lists all files generated by last baking.
+const GENERATED_MANIFEST: &str = ".unreal-doc-generated";
 
+/// Removes files generated by previous baking that are not generated anymore (for example pages
+/// of renamed or deleted symbols), along with directories left empty, and records currently
+/// generated files for the next baking. Files not generated by this tool are left intact.
+pub fn prune_stale_files(
+    output_dir: &Path,
+    generated: &BTreeSet<String>,
+) -> Result<(), Box<dyn Error>> {
+    let manifest_path = output_dir.join(GENERATED_MANIFEST);
+    if let Ok(previous) = read_file(&manifest_path) {
+        for relative in previous.lines() {
+            if relative.is_empty() || generated.contains(relative) {
+                continue;
+            }
+            let relative = Path::new(relative);
+            if !relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                continue;
+            }
+            let path = output_dir.join(relative);
+            if remove_file(&path).is_ok() {
+                for dir in path.ancestors().skip(1) {
+                    if dir == output_dir || remove_dir(dir).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    let content = generated
+        .iter()
+        .map(|path| format!("{}\n", path))
+        .collect::<String>();
+    write(&manifest_path, content).map_err(|error| {
+        format!(
+            "Could not write generated files manifest: {:?} ({})",
+            manifest_path, error
+        )
+    })?;
+    Ok(())
+}
+
+/// Renders all book source files, mapped by their paths relative to output directory.
+#[allow(clippy::too_many_arguments)]
+fn render_pages(
+    document: &Document,
+    mdbook: &BackendMdBook,
+    templates: &Templates,
+    diagrams: &Diagrams,
+    recent: &[(String, String)],
+    header: &str,
+    footer: &str,
+    diagnostics: &mut Diagnostics,
+) -> BTreeMap<String, String> {
     let mut files = HashMap::new();
     let mut index = "# Index\n\n".to_owned();
 
@@ -62,6 +326,35 @@ pub fn bake_mdbook(document: &Document, config: &Config, root: &Path) {
         include_book_index(None, &document.book, &mut files, &mut index, 0);
     }
 
+    if !document.examples.is_empty() {
+        index.push_str("\n- [Examples](examples.md)\n");
+        let mut listing = "# Examples\n\n".to_owned();
+        for example in document.examples.values() {
+            let index_path = format!("examples/{}.md", example.name);
+            index.push_str(&format!("  - [{}]({})\n", example.name, index_path));
+            listing.push_str(&format!("- [`{}`]({})\n", example.name, index_path));
+            files.insert(
+                format!("src/{}", index_path),
+                bake_example(example, document),
+            );
+        }
+        files.insert("src/examples.md".to_owned(), listing);
+    }
+
+    if !document.glossary.is_empty() {
+        index.push_str("\n- [Glossary](glossary.md)\n");
+        files.insert(
+            "src/glossary.md".to_owned(),
+            bake_glossary(&document.glossary),
+        );
+    }
+
+    if let Some(listing) = bake_faq(document) {
+        index.push_str("\n- [FAQ](faq.md)\n");
+        files.insert("src/faq.md".to_owned(), listing);
+    }
+
+    let reference_start = index.len();
     index.push_str("\n- [C++ API Reference](reference.md)\n");
     let mut reference_listing = "# C++ API Reference\n".to_owned();
 
@@ -70,73 +363,192 @@ pub fn bake_mdbook(document: &Document, config: &Config, root: &Path) {
         reference_listing.push_str("\n## Enums\n");
         let mut listing = "# Enums\n\n".to_owned();
         for item in &document.enums {
-            let index_path = format!("reference/enums/{}.md", item.name);
-            let file_path = format!("src/reference/enums/{}.md", item.name);
+            let index_path = format!(
+                "reference/enums/{}.md",
+                page_stem(document, "enums", &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
             let mut content = String::default();
-            bake_enum(item, &mut content);
+            bake_enum(item, mdbook, templates, mdbook.heading_level, &mut content);
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
             files.insert(file_path, content);
             let entry = format!("    - [{}]({})\n", item.name, index_path);
             index.push_str(&entry);
             let entry = format!("- [`{}`]({})\n", item.name, index_path);
-            listing.push_str(&entry);
             reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
         }
         files.insert("src/reference/enums.md".to_owned(), listing);
     }
 
-    if !document.structs.is_empty() {
+    let in_editor_tools = |item: &StructClass| mdbook.editor_section && item.is_editor_only;
+    let structs = document
+        .structs
+        .iter()
+        .filter(|item| !in_editor_tools(item))
+        .collect::<Vec<_>>();
+    if !structs.is_empty() {
         index.push_str("  - [Structs](reference/structs.md)\n");
         reference_listing.push_str("\n## Structs\n");
         let mut listing = "# Structs\n\n".to_owned();
-        for item in &document.structs {
-            let index_path = format!("reference/structs/{}.md", item.name);
-            let file_path = format!("src/reference/structs/{}.md", item.name);
+        for item in structs {
+            let index_path = format!(
+                "reference/structs/{}.md",
+                page_stem(document, "structs", &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
             let mut content = String::default();
-            bake_struct_class(item, &mut content);
+            bake_struct_class(item, document, mdbook, templates, diagrams, &mut content);
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
             files.insert(file_path, content);
             let entry = format!("    - [{}]({})\n", item.name, index_path);
             index.push_str(&entry);
             let entry = format!("- [`{}`]({})\n", item.name, index_path);
-            listing.push_str(&entry);
             reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
         }
         files.insert("src/reference/structs.md".to_owned(), listing);
     }
 
-    if !document.classes.is_empty() {
+    let classes = document
+        .classes
+        .iter()
+        .filter(|item| !in_editor_tools(item))
+        .collect::<Vec<_>>();
+    if !classes.is_empty() {
         index.push_str("  - [Classes](reference/classes.md)\n");
         reference_listing.push_str("\n## Classes\n");
         let mut listing = "# Classes\n\n".to_owned();
-        for item in &document.classes {
-            let index_path = format!("reference/classes/{}.md", item.name);
-            let file_path = format!("src/reference/classes/{}.md", item.name);
+        for item in classes {
+            let index_path = format!(
+                "reference/classes/{}.md",
+                page_stem(document, "classes", &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
             let mut content = String::default();
-            bake_struct_class(item, &mut content);
+            bake_struct_class(item, document, mdbook, templates, diagrams, &mut content);
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
             files.insert(file_path, content);
             let entry = format!("    - [{}]({})\n", item.name, index_path);
             index.push_str(&entry);
             let entry = format!("- [`{}`]({})\n", item.name, index_path);
-            listing.push_str(&entry);
             reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
         }
         files.insert("src/reference/classes.md".to_owned(), listing);
     }
 
+    let editor_tools = document
+        .structs
+        .iter()
+        .chain(document.classes.iter())
+        .filter(|item| in_editor_tools(item))
+        .collect::<Vec<_>>();
+    if !editor_tools.is_empty() {
+        index.push_str("  - [Editor Tools](reference/editor_tools.md)\n");
+        reference_listing.push_str("\n## Editor Tools\n");
+        let mut listing = "# Editor Tools\n\n".to_owned();
+        for item in editor_tools {
+            let directory = match item.mode {
+                StructClassMode::Struct => "structs",
+                StructClassMode::Class => "classes",
+            };
+            let index_path = format!(
+                "reference/{}/{}.md",
+                directory,
+                page_stem(document, directory, &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
+            let mut content = String::default();
+            bake_struct_class(item, document, mdbook, templates, diagrams, &mut content);
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
+            files.insert(file_path, content);
+            let entry = format!("    - [{}]({})\n", item.name, index_path);
+            index.push_str(&entry);
+            let entry = format!("- [`{}`]({})\n", item.name, index_path);
+            reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
+        }
+        files.insert("src/reference/editor_tools.md".to_owned(), listing);
+    }
+
     if !document.functions.is_empty() {
         index.push_str("  - [Functions](reference/functions.md)\n");
         reference_listing.push_str("\n## Functions\n");
         let mut listing = "# Functions\n\n".to_owned();
         for item in &document.functions {
-            let index_path = format!("reference/functions/{}.md", item.name);
-            let file_path = format!("src/reference/functions/{}.md", item.name);
+            let index_path = format!(
+                "reference/functions/{}.md",
+                page_stem(document, "functions", &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
             let mut content = String::default();
-            bake_function(item, &mut content, false);
+            bake_function(
+                item,
+                document,
+                mdbook,
+                templates,
+                mdbook.heading_level,
+                &mut content,
+                None,
+            );
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
             files.insert(file_path, content);
             let entry = format!("    - [{}]({})\n", item.name, index_path);
             index.push_str(&entry);
             let entry = format!("- [`{}`]({})\n", item.name, index_path);
-            listing.push_str(&entry);
             reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
         }
         files.insert("src/reference/functions.md".to_owned(), listing);
     }
@@ -146,43 +558,117 @@ pub fn bake_mdbook(document: &Document, config: &Config, root: &Path) {
         reference_listing.push_str("\n## Delegates\n");
         let mut listing = "# Delegates\n\n".to_owned();
         for item in &document.delegates {
-            let index_path = format!("reference/delegates/{}.md", item.name);
-            let file_path = format!("src/reference/delegates/{}.md", item.name);
+            let index_path = format!(
+                "reference/delegates/{}.md",
+                page_stem(document, "delegates", &item.name)
+            );
+            let file_path = format!("src/{}", index_path);
             let mut content = String::default();
-            bake_delegate(item, &mut content); // write out delegate content
+            bake_delegate(
+                item,
+                document,
+                templates,
+                mdbook.heading_level,
+                &mut content,
+            ); // write out delegate content
+            bake_edit_link(
+                item.header_path.as_deref(),
+                item.fileline,
+                mdbook,
+                &mut content,
+            );
             files.insert(file_path, content);
             let entry = format!("    - [{}]({})\n", item.name, index_path);
             index.push_str(&entry);
             let entry = format!("- [`{}`]({})\n", item.name, index_path);
-            listing.push_str(&entry);
             reference_listing.push_str(&entry);
+            listing.push_str(&listing_entry(
+                &item.name,
+                &index_path,
+                &item.doc_comments,
+                mdbook,
+            ));
         }
         files.insert("src/reference/delegates.md".to_owned(), listing);
     }
 
+    if mdbook.class_hierarchy
+        && let Some(listing) = bake_class_hierarchy(document, mdbook, diagrams)
+    {
+        index.push_str("  - [Class Hierarchy](reference/hierarchy.md)\n");
+        reference_listing
+            .push_str("\n## Class Hierarchy\n- [Class Hierarchy](reference/hierarchy.md)\n");
+        files.insert("src/reference/hierarchy.md".to_owned(), listing);
+    }
+
+    for rule in &mdbook.groups {
+        if let Some(listing) = bake_group(rule, document, mdbook) {
+            let index_path = format!("reference/groups/{}.md", group_slug(&rule.title));
+            index.push_str(&format!("  - [{}]({})\n", rule.title, index_path));
+            reference_listing.push_str(&format!(
+                "\n## {}\n- [{}]({})\n",
+                rule.title, rule.title, index_path
+            ));
+            files.insert(format!("src/{}", index_path), listing);
+        }
+    }
+
+    if let Some(listing) = bake_console_commands(document, mdbook) {
+        index.push_str("  - [Console Commands (Exec)](reference/console_commands.md)\n");
+        reference_listing.push_str(
+            "\n## Console Commands\n- [Console Commands (Exec)](reference/console_commands.md)\n",
+        );
+        files.insert("src/reference/console_commands.md".to_owned(), listing);
+    }
+
+    if let Some(listing) = bake_experimental_index(document, mdbook) {
+        index.push_str("  - [Experimental APIs](reference/experimental.md)\n");
+        reference_listing
+            .push_str("\n## Experimental APIs\n- [Experimental APIs](reference/experimental.md)\n");
+        files.insert("src/reference/experimental.md".to_owned(), listing);
+    }
+
+    if let Some(listing) = bake_recent_index(recent, mdbook) {
+        index.push_str("  - [Recently Added APIs](reference/recent.md)\n");
+        reference_listing
+            .push_str("\n## Recently Added APIs\n- [Recently Added APIs](reference/recent.md)\n");
+        files.insert("src/reference/recent.md".to_owned(), listing);
+    }
+
+    if let Some(listing) = bake_owners_index(document) {
+        index.push_str("  - [API Owners](reference/owners.md)\n");
+        reference_listing.push_str("\n## API Owners\n- [API Owners](reference/owners.md)\n");
+        files.insert("src/reference/owners.md".to_owned(), listing);
+    }
+
+    if let Some(listing) = bake_function_libraries(document, mdbook) {
+        index.push_str("  - [Function Libraries](reference/function_libraries.md)\n");
+        reference_listing.push_str(
+            "\n## Function Libraries\n- [Function Libraries](reference/function_libraries.md)\n",
+        );
+        files.insert("src/reference/function_libraries.md".to_owned(), listing);
+    }
+
+    let categories = category_tree(document, mdbook);
+    if !categories.children.is_empty() {
+        index.push_str("  - [Categories](reference/categories.md)\n");
+        reference_listing.push_str("\n## Categories\n- [Categories](reference/categories.md)\n");
+        bake_category_pages(
+            &categories,
+            &categories.title,
+            "reference/categories",
+            1,
+            &mut index,
+            &mut files,
+        );
+    }
+
     files.insert("src/reference.md".to_owned(), reference_listing);
 
-    let header = config
-        .backend_mdbook
-        .as_ref()
-        .and_then(|mdbook| mdbook.header.as_ref())
-        .map(|path| {
-            read_file(root.join(path))
-                .unwrap_or_else(|_| panic!("Could not read header file: {:?}", path))
-                + "\n"
-        })
-        .unwrap_or_default();
-    let footer = config
-        .backend_mdbook
-        .as_ref()
-        .and_then(|mdbook| mdbook.footer.as_ref())
-        .map(|path| {
-            "\n".to_owned()
-                + &read_file(root.join(path))
-                    .unwrap_or_else(|_| panic!("Could not read footer file: {:?}", path))
-        })
-        .unwrap_or_default();
-    for (path, content) in files {
+    let mut result = BTreeMap::new();
+    let mut metrics = BTreeMap::new();
+    let glossary_terms = glossary_patterns(&document.glossary);
+    for (path, content) in files.into_iter().collect::<BTreeMap<_, _>>() {
         let relative_path = if path.starts_with("src/") {
             path.rfind('/')
                 .map(|index| &path[4..(index + 1)])
@@ -190,161 +676,1814 @@ pub fn bake_mdbook(document: &Document, config: &Config, root: &Path) {
         } else {
             ""
         };
-        let content = preprocess_content(
+        let mut content = preprocess_content(
             &content,
             document,
-            config.backend_mdbook.as_ref(),
+            mdbook,
+            &path,
             relative_path,
+            diagnostics,
         );
-        let path = config.output_dir.join(path);
-        ensure_dir(&path);
-        let content = format!(
-            "{}{}{}\n",
+        if mdbook.glossary_terms != GlossaryTerms::Plain
+            && path.ends_with(".md")
+            && path != "src/glossary.md"
+            && !content.contains("<!-- no-glossary -->")
+        {
+            content = mark_glossary_terms(
+                &content,
+                &glossary_terms,
+                mdbook.glossary_terms,
+                relative_path,
+            );
+        }
+        if mdbook.page_metrics && path.ends_with(".md") {
+            metrics.insert(path.to_owned(), PageMetrics::new(&content));
+        }
+        // handbook pages get their edit links after site references in them got fixed
+        if let Some(source) = path
+            .strip_prefix("src/")
+            .filter(|source| document.book.contains_key(*source))
+        {
+            bake_page_edit_link(source, mdbook, &mut content);
+        }
+        let front_matter = path
+            .strip_prefix("src/")
+            .and_then(|source| document.front_matter.get(source));
+        let content = inject_header_footer(
+            &content,
             header,
-            content,
             footer,
+            mdbook.header_position,
+            front_matter,
         );
-        write(&path, content)
-            .unwrap_or_else(|_| panic!("Could not write mdbook page file: {:?}", path));
+        result.insert(path, content);
+    }
+    if mdbook.print_structure {
+        // reference kinds become chapters and symbols their sections, like in printed manuals
+        let reference = index.split_off(reference_start);
+        index.push_str("\n# C++ API Reference\n\n- [Overview](reference.md)\n");
+        for line in reference.lines().skip(2) {
+            index.push_str(line.strip_prefix("  ").unwrap_or(line));
+            index.push('\n');
+        }
     }
+    if mdbook.page_metrics {
+        index.push_str("\n- [Page Statistics](statistics.md)\n");
+        let content = bake_page_statistics(&metrics);
+        result.insert(
+            "src/statistics.md".to_owned(),
+            inject_header_footer(&content, header, footer, mdbook.header_position, None),
+        );
+        result.insert(
+            "page_metrics.json".to_owned(),
+            serde_json::to_string_pretty(&metrics).expect("Could not serialize page metrics!"),
+        );
+    }
+    result.insert("src/SUMMARY.md".to_owned(), index);
+    result
+}
 
-    let path = config.output_dir.join("src/SUMMARY.md");
-    ensure_dir(&path);
-    write(&path, index)
-        .unwrap_or_else(|_| panic!("Could not write mdbook summary file: {:?}", path));
+/// Glossary page with terms in alphabetical order, each one with anchor to link it with.
+fn bake_glossary(glossary: &BTreeMap<String, String>) -> String {
+    let mut terms = glossary.iter().collect::<Vec<_>>();
+    terms.sort_by_key(|(term, _)| term.to_lowercase());
+    let mut content = "# Glossary\n".to_owned();
+    for (term, definition) in terms {
+        content.push_str(&format!(
+            "\n<a id=\"{}\"></a>**{}**\n\n{}\n",
+            group_slug(term),
+            term,
+            definition.trim()
+        ));
+    }
+    content
+}
 
-    if let Some(assets) = config
-        .backend_mdbook
-        .as_ref()
-        .and_then(|mdbook| mdbook.assets.as_ref())
-    {
-        let from = root.join(assets);
-        let to = config.output_dir.join("src/assets");
-        ensure_dir(&to);
-        let mut options = CopyOptions::new();
-        options.overwrite = true;
-        options.copy_inside = true;
-        copy_items(&[from], &to, &options)
-            .unwrap_or_else(|_| panic!("Could not copy assets: {:?}", assets));
+/// Page content with shared header and footer injected into it, unless its front matter opts
+/// out of them. Headings of header put below page title get nested under it, so they do not
+/// end up as siblings of page title in table of contents.
+fn inject_header_footer(
+    content: &str,
+    header: &str,
+    footer: &str,
+    position: HeaderPosition,
+    front_matter: Option<&FrontMatter>,
+) -> String {
+    let header = match front_matter {
+        Some(front_matter) if !front_matter.header => "",
+        _ => header,
+    };
+    let footer = match front_matter {
+        Some(front_matter) if !front_matter.footer => "",
+        _ => footer,
+    };
+    let title = markdown_headings(content).next();
+    match (position, title) {
+        (HeaderPosition::AfterTitle, Some((start, level))) if !header.is_empty() => {
+            let end = content[start..]
+                .find('\n')
+                .map(|index| start + index + 1)
+                .unwrap_or(content.len());
+            format!(
+                "{}\n{}\n{}{}\n",
+                &content[..end],
+                nest_headings(header.trim_end(), level + 1),
+                &content[end..],
+                footer
+            )
+        }
+        (HeaderPosition::BeforeFooter, title) if !header.is_empty() => {
+            let level = title.map(|(_, level)| level + 1).unwrap_or(1);
+            format!(
+                "{}\n{}\n{}\n",
+                content,
+                nest_headings(header.trim_end(), level),
+                footer
+            )
+        }
+        _ => format!("{}{}{}\n", header, content, footer),
     }
+}
 
-    let build = config
-        .backend_mdbook
-        .as_ref()
-        .map(|mdbook| mdbook.build)
-        .unwrap_or_default();
-    if build {
-        Command::new("mdbook")
-            .arg("build")
-            .arg(&config.output_dir)
-            .status()
-            .expect("Could not build documentation with mdbook!");
+/// Starts and levels of ATX headings in Markdown content, skipping fenced code blocks.
+fn markdown_headings(content: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut fenced = false;
+    content
+        .split_inclusive('\n')
+        .scan(0, |start, line| {
+            let result = (*start, line);
+            *start += line.len();
+            Some(result)
+        })
+        .filter_map(move |(start, line)| {
+            let line = line.trim_end();
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                fenced = !fenced;
+                return None;
+            }
+            let level = line.len() - line.trim_start_matches('#').len();
+            let heading = !fenced
+                && (1..=6).contains(&level)
+                && (line.len() == level || line[level..].starts_with(' '));
+            heading.then_some((start, level))
+        })
+}
+
+/// Content with headings pushed down, so the highest one gets given level.
+fn nest_headings(content: &str, level: usize) -> String {
+    let Some(highest) = markdown_headings(content).map(|(_, level)| level).min() else {
+        return content.to_owned();
+    };
+    if highest >= level {
+        return content.to_owned();
+    }
+    let depth = level - highest;
+    let mut result = content.to_owned();
+    // inserted from the end, so starts of preceding headings stay valid
+    for (start, level) in markdown_headings(content)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+    {
+        let added = depth.min(6 - level);
+        result.insert_str(start, &"#".repeat(added));
     }
+    result
 }
 
-fn preprocess_content(
+/// Code, headings, links and HTML tags of page content, where glossary terms are not marked.
+static GLOSSARY_PROTECTED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ms)^[ \t]*(?:```|~~~).*?^[ \t]*(?:```|~~~)[^\n]*$|^#[^\n]*$|`[^`\n]*`|!?\[[^\]]*\]\([^)]*\)|<a\b[^>]*>.*?</a>|<abbr\b[^>]*>.*?</abbr>|<[^>]*>",
+    )
+    .unwrap()
+});
+
+/// Glossary terms with their definitions and patterns they are found with, longer terms first,
+/// compiled once for all pages.
+fn glossary_patterns(glossary: &BTreeMap<String, String>) -> Vec<(&str, &str, Regex)> {
+    let mut result = glossary
+        .iter()
+        .map(|(term, definition)| {
+            let pattern = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(term))).unwrap();
+            (term.as_str(), definition.as_str(), pattern)
+        })
+        .collect::<Vec<_>>();
+    result.sort_by_key(|(term, _, _)| std::cmp::Reverse(term.chars().count()));
+    result
+}
+
+/// Marks first occurrence of every glossary term in page content (found in given directory
+/// relative to book sources) as link to its glossary entry or as tooltip with its definition.
+/// Terms are not looked for in code, headings, links nor HTML tags, and longer terms are marked
+/// first, so shorter terms they contain do not break them apart.
+fn mark_glossary_terms(
     content: &str,
-    document: &Document,
-    config: Option<&BackendMdBook>,
+    terms: &[(&str, &str, Regex)],
+    mode: GlossaryTerms,
     relative_path: &str,
 ) -> String {
-    let content = replace_code_references(content, document);
-    let content = replace_snippets(&content, document);
-    fix_site_references(
+    let mut result = content.to_owned();
+    for (term, definition, pattern) in terms {
+        let spans = GLOSSARY_PROTECTED
+            .find_iter(&result)
+            .map(|found| found.range())
+            .collect::<Vec<_>>();
+        let Some(found) = pattern.find_iter(&result).find(|found| {
+            !spans
+                .iter()
+                .any(|span| span.start < found.end() && found.start() < span.end)
+        }) else {
+            continue;
+        };
+        let marked = match mode {
+            GlossaryTerms::Plain => continue,
+            GlossaryTerms::Link => format!(
+                "[{}]({}glossary.md#{})",
+                found.as_str(),
+                "../".repeat(relative_path.matches('/').count()),
+                group_slug(term)
+            ),
+            GlossaryTerms::Tooltip => format!(
+                "<abbr title=\"{}\">{}</abbr>",
+                definition
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .replace('&', "&amp;")
+                    .replace('"', "&quot;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;"),
+                found.as_str()
+            ),
+        };
+        result.replace_range(found.range(), &marked);
+    }
+    result
+}
+
+/// Average reading speed used to estimate reading time of pages.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Size of page content, not counting shared header and footer, nor code.
+/// Link targets and HTML tags of page content, not counted as words.
+static PAGE_MARKUP: Lazy<Regex> = Lazy::new(|| Regex::new(r"\]\([^)]*\)|<[^<>]*>").unwrap());
+
+#[derive(Debug, Serialize)]
+struct PageMetrics {
+    words: usize,
+    reading_minutes: usize,
+}
+
+impl PageMetrics {
+    fn new(content: &str) -> Self {
+        let words = PAGE_MARKUP
+            .replace_all(&prose(content), " ")
+            .split_whitespace()
+            .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+            .count();
+        Self {
+            words,
+            reading_minutes: words.div_ceil(WORDS_PER_MINUTE).max(1),
+        }
+    }
+}
+
+/// Listing of all pages from the biggest one, to find those that should be split.
+fn bake_page_statistics(metrics: &BTreeMap<String, PageMetrics>) -> String {
+    let mut pages = metrics.iter().collect::<Vec<_>>();
+    pages.sort_by(|(a, a_metrics), (b, b_metrics)| {
+        b_metrics.words.cmp(&a_metrics.words).then(a.cmp(b))
+    });
+    let total = pages
+        .iter()
+        .map(|(_, metrics)| metrics.words)
+        .sum::<usize>();
+    let mut content = format!(
+        "# Page Statistics\n\n{} pages, {} words in total.\n\n\
+        | Page | Words | Reading time |\n| --- | ---: | ---: |\n",
+        pages.len(),
+        total
+    );
+    for (path, metrics) in pages {
+        let path = path.strip_prefix("src/").unwrap_or(path);
+        content.push_str(&format!(
+            "| [{}]({}) | {} | {} min |\n",
+            path, path, metrics.words, metrics.reading_minutes
+        ));
+    }
+    content
+}
+
+/// Renders page of single documented enum, struct, class, function or delegate into Markdown, the
+/// same way it gets baked into book, so other tools can show documentation of one symbol without
+/// baking whole book. Links in it point at other book pages. Nothing is rendered if there is no
+/// symbol of given name.
+pub fn render_symbol(document: &Document, name: &str, mdbook: &BackendMdBook) -> Option<String> {
+    let templates = Templates::load(&mdbook.templates, &mdbook.variables, Path::new(""));
+    let mut content = String::default();
+    let (directory, header_path, fileline) =
+        if let Some(item) = document.enums.iter().find(|item| item.name == name) {
+            bake_enum(item, mdbook, &templates, mdbook.heading_level, &mut content);
+            ("enums", &item.header_path, item.fileline)
+        } else if let Some(item) = document
+            .structs
+            .iter()
+            .chain(document.classes.iter())
+            .find(|item| item.name == name)
+        {
+            bake_struct_class(
+                item,
+                document,
+                mdbook,
+                &templates,
+                &Diagrams::default(),
+                &mut content,
+            );
+            let directory = match item.mode {
+                StructClassMode::Struct => "structs",
+                StructClassMode::Class => "classes",
+            };
+            (directory, &item.header_path, item.fileline)
+        } else if let Some(item) = document.functions.iter().find(|item| item.name == name) {
+            bake_function(
+                item,
+                document,
+                mdbook,
+                &templates,
+                mdbook.heading_level,
+                &mut content,
+                None,
+            );
+            ("functions", &item.header_path, item.fileline)
+        } else if let Some(item) = document.delegates.iter().find(|item| item.name == name) {
+            bake_delegate(
+                item,
+                document,
+                &templates,
+                mdbook.heading_level,
+                &mut content,
+            );
+            ("delegates", &item.header_path, item.fileline)
+        } else {
+            return None;
+        };
+    bake_edit_link(header_path.as_deref(), fileline, mdbook, &mut content);
+    let path = format!(
+        "src/reference/{}/{}.md",
+        directory,
+        page_stem(document, directory, name)
+    );
+    let relative_path = format!("reference/{}/", directory);
+    // problems in single symbol are reported when whole book gets baked
+    let mut diagnostics = Diagnostics::default();
+    Some(preprocess_content(
         &content,
-        config
-            .and_then(|config| config.site_url.as_deref())
-            .unwrap_or("/"),
-        relative_path,
+        document,
+        mdbook,
+        &path,
+        &relative_path,
+        &mut diagnostics,
+    ))
+}
+
+/// Renders all book source files into single string, ordered by their paths, with each file
+/// preceded by `<!-- path -->` line. Output is deterministic and nothing gets written to disk,
+/// so it suits comparing baking results against golden files, e.g. in tests of grammar fixes.
+/// Diagrams, user templates, header and footer are left out.
+pub fn render_to_string(document: &Document, mdbook: &BackendMdBook) -> String {
+    let mut diagnostics = Diagnostics::default();
+    render_pages(
+        document,
+        mdbook,
+        &Templates::default(),
+        &Diagrams::default(),
+        &[],
+        "",
+        "",
+        &mut diagnostics,
     )
+    .into_iter()
+    .map(|(path, content)| format!("<!-- {} -->\n{}", path, content))
+    .collect::<Vec<_>>()
+    .join("\n")
 }
 
-fn replace_code_references(content: &str, document: &Document) -> String {
-    // TODO: put that regex in lazy static to not perform costly compilation on each call.
-    let re = Regex::new(r"\[`\s*(\w+)\s*:\s*(\w+)\s*(::\s*(\w+))?`\]s*\(\s*\)").unwrap();
-    re.replace_all(content, |captures: &Captures| {
-        let element = captures.get(1).unwrap().as_str().trim();
-        let name = captures.get(2).unwrap().as_str().trim();
-        let section = captures.get(4).map(|m| m.as_str().trim());
-        let path = match element {
-            "enum" => document
-                .enums
-                .iter()
-                .find(|item| item.name == name)
-                .map(|_| format!("/reference/enums/{}.md", name)),
-            "struct" => document
+fn listing_entry(
+    name: &str,
+    index_path: &str,
+    doc_comments: &Option<String>,
+    mdbook: &BackendMdBook,
+) -> String {
+    match doc_brief(doc_comments, mdbook.brief) {
+        Some(brief) => format!("- [`{}`]({}) — {}\n", name, index_path, brief),
+        None => format!("- [`{}`]({})\n", name, index_path),
+    }
+}
+
+/// Page of example showing all its files, along with symbols that link to it.
+fn bake_example(example: &Example, document: &Document) -> String {
+    let mut content = format!("# Example: `{}`\n\n", example.name);
+    let references = document.example_references(&example.name);
+    if !references.is_empty() {
+        content.push_str("Referenced by:\n\n");
+        for reference in references {
+            content.push_str(&format!("- {}\n", reference));
+        }
+        content.push('\n');
+    }
+    // headers go first, as they introduce what sources implement
+    let mut files = example.files.iter().collect::<Vec<_>>();
+    files.sort_by_key(|(path, _)| (!path.ends_with(".h"), path.to_owned()));
+    for (path, source) in files {
+        content.push_str(&format!("## `{}`\n\n```cpp\n{}\n```\n\n", path, source));
+    }
+    content
+}
+
+/// Overview of all documented structs and classes deriving from base class of group rule.
+fn bake_group(rule: &GroupRule, document: &Document, mdbook: &BackendMdBook) -> Option<String> {
+    let mut items = document
+        .structs
+        .iter()
+        .chain(document.classes.iter())
+        .filter(|item| document.derives_from(item, &rule.base))
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return None;
+    }
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut result = format!("# {}\n\n", rule.title);
+    for item in items {
+        let reference = type_reference(&item.name, document);
+        match doc_brief(&item.doc_comments, mdbook.brief) {
+            Some(brief) => result.push_str(&format!("- {} — {}\n", reference, brief)),
+            None => result.push_str(&format!("- {}\n", reference)),
+        }
+    }
+    Some(result)
+}
+
+/// Redirects from pages that symbols had under their former names to their current pages, keyed
+/// by absolute path of former page, as expected by MD Book. Aliases that would replace existing
+/// pages are reported instead.
+fn alias_redirects(document: &Document, diagnostics: &mut Diagnostics) -> BTreeMap<String, String> {
+    let items = document
+        .enums
+        .iter()
+        .map(|item| ("enums", &item.name, &item.aliases))
+        .chain(
+            document
                 .structs
                 .iter()
-                .find(|item| item.name == name)
-                .map(|_| format!("/reference/structs/{}.md", name)),
-            "class" => document
+                .map(|item| ("structs", &item.name, &item.aliases)),
+        )
+        .chain(
+            document
                 .classes
                 .iter()
-                .find(|item| item.name == name)
-                .map(|_| format!("/reference/classes/{}.md", name)),
-            "function" => document
+                .map(|item| ("classes", &item.name, &item.aliases)),
+        )
+        .chain(
+            document
                 .functions
                 .iter()
-                .find(|item| item.name == name)
-                .map(|_| format!("/reference/functions/{}.md", name)),
-            "delegate" => document
+                .map(|item| ("functions", &item.name, &item.aliases)),
+        )
+        .chain(
+            document
                 .delegates
                 .iter()
-                .find(|item| item.name == name)
-                .map(|_| format!("/reference/delegates/{}.md", name)),
-            _ => None,
-        };
-        if let Some(path) = path {
-            if let Some(section) = section {
-                format!(
-                    "[**`{}::{}`**]({}#{})",
-                    name,
-                    section,
-                    path,
-                    section.to_lowercase()
-                )
-            } else {
-                format!("[**`{}`**]({})", name, path)
+                .map(|item| ("delegates", &item.name, &item.aliases)),
+        );
+    let mut result = BTreeMap::new();
+    for (directory, name, aliases) in items {
+        let stem = page_stem(document, directory, name);
+        for alias in aliases {
+            let alias_stem = page_stem(document, directory, alias);
+            if directory_names(document, directory)
+                .into_iter()
+                .any(|other| page_stem(document, directory, other) == alias_stem)
+            {
+                diagnostics.warning(
+                    "aliases",
+                    "book.toml",
+                    format!(
+                        "Alias `{}` of `{}` is not redirected, as it would replace existing page",
+                        alias, name
+                    ),
+                );
+                continue;
             }
-        } else if let Some(section) = section {
-            format!("**`{}::{}`**", name, section)
-        } else {
-            format!("**`{}`**", name)
+            result.insert(
+                format!("/reference/{}/{}.html", directory, alias_stem),
+                format!("{}.html", stem),
+            );
+        }
+    }
+    result
+}
+
+/// Longest file name stem of symbol page, longer ones get shortened to stay within path length
+/// limits of some platforms.
+const MAX_PAGE_STEM: usize = 64;
+
+/// File name stem of symbol page in given reference directory. Symbols with names differing only
+/// in case would overwrite each other pages on case-insensitive file systems, so all but the
+/// first of them (in byte order) get hash of their name appended. Names too long get shortened
+/// and hash appended as well.
+pub fn page_stem(document: &Document, directory: &str, name: &str) -> String {
+    let slug = symbol_slug(name);
+    let collides = document
+        .first_page_slugs
+        .get_or_init(|| first_page_slugs(document))
+        .get(&(directory.to_owned(), slug.to_lowercase()))
+        .is_some_and(|first| *first != slug);
+    if !collides && slug.chars().count() <= MAX_PAGE_STEM {
+        return slug;
+    }
+    let shortened = slug.chars().take(MAX_PAGE_STEM - 9).collect::<String>();
+    format!("{}_{:08x}", shortened, name_hash(name))
+}
+
+/// First slugs (in byte order) of symbols of every reference directory, mapped by directory and
+/// lowercase slug.
+fn first_page_slugs(document: &Document) -> HashMap<(String, String), String> {
+    let mut result = HashMap::<_, String>::new();
+    for directory in ["enums", "structs", "classes", "functions", "delegates"] {
+        for name in directory_names(document, directory) {
+            let slug = symbol_slug(name);
+            result
+                .entry((directory.to_owned(), slug.to_lowercase()))
+                .and_modify(|first| {
+                    if slug < *first {
+                        *first = slug.to_owned();
+                    }
+                })
+                .or_insert(slug);
         }
+    }
+    result
+}
+
+/// Names of symbols that have their pages in given reference directory.
+fn directory_names<'a>(document: &'a Document, directory: &str) -> Vec<&'a String> {
+    match directory {
+        "enums" => document.enums.iter().map(|item| &item.name).collect(),
+        "structs" => document.structs.iter().map(|item| &item.name).collect(),
+        "classes" => document.classes.iter().map(|item| &item.name).collect(),
+        "functions" => document.functions.iter().map(|item| &item.name).collect(),
+        "delegates" => document.delegates.iter().map(|item| &item.name).collect(),
+        _ => vec![],
+    }
+}
+
+/// FNV-1a hash of name, stable between runs and platforms, so page paths stay the same.
+fn name_hash(name: &str) -> u32 {
+    name.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
     })
-    .into()
-}
-
-fn replace_snippets(content: &str, document: &Document) -> String {
-    // TODO: put that regex in lazy static to not perform costly compilation on each call.
-    let re = Regex::new(r"```\s*snippet[\n\r]+([\s/]*)(\w+)[\r\n]+\s*```").unwrap();
-    re.replace_all(content, |captures: &Captures| {
-        let prefix = captures.get(1).unwrap().as_str();
-        let name = captures.get(2).unwrap().as_str().trim();
-        if let Some(content) = document.snippets.get(name) {
-            let content = content
-                .lines()
-                .map(|line| format!("{}{}", prefix, line))
+}
+
+/// File name of group page, e.g. `Anim Notifies` becomes `anim_notifies`.
+fn group_slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Table of all `Exec` functions of documented classes, usable as console commands.
+fn bake_console_commands(document: &Document, mdbook: &BackendMdBook) -> Option<String> {
+    let mut rows = vec![];
+    for owner in document.classes.iter().chain(document.structs.iter()) {
+        for function in owner.methods.iter().filter(|function| function.is_exec()) {
+            let arguments = function
+                .arguments
+                .iter()
+                .map(|argument| format!("`{}`", argument.signature()))
                 .collect::<Vec<_>>()
-                .join("\n");
-            format!("```cpp\n{}\n{}```", content, prefix)
-        } else {
-            println!("Trying to inject non-existing snippet: {}", name);
-            format!("```\n{}Missing snippet: {}\n{}```", prefix, name, prefix)
+                .join(", ");
+            rows.push(format!(
+                "| `{}` | {} | {} | {} |\n",
+                function.name,
+                arguments,
+                member_reference(owner, &function.name),
+                doc_brief(&function.doc_comments, mdbook.brief).unwrap_or_default()
+            ));
         }
-    })
-    .into()
+    }
+    if rows.is_empty() {
+        return None;
+    }
+    rows.sort();
+    let mut result = "# Console Commands (Exec)\n\n".to_owned();
+    result.push_str("| Command | Parameters | Owner | Description |\n");
+    result.push_str("| --- | --- | --- | --- |\n");
+    for row in rows {
+        result.push_str(&row);
+    }
+    Some(result)
 }
 
-fn fix_site_references(content: &str, site_url: &str, relative_path: &str) -> String {
-    // TODO: put that regex in lazy static to not perform costly compilation on each call.
-    let re = Regex::new(r"\]\s*\((\s*/)?(.*\.md(\s*#.*)?)\)").unwrap();
-    re.replace_all(content, |captures: &Captures| {
-        let relative_path = captures.get(1).map(|_| "").unwrap_or_else(|| relative_path);
-        let reference = captures.get(2).unwrap().as_str().trim();
-        format!("]({}{}{})", site_url, relative_path, reference)
-    })
-    .into()
+/// Tells if any symbol or member declares its stability, so stability badges need styling.
+fn has_stability(document: &Document) -> bool {
+    document.enums.iter().any(|item| item.stability.is_some())
+        || document
+            .structs
+            .iter()
+            .chain(document.classes.iter())
+            .any(|item| {
+                item.stability.is_some()
+                    || item.properties.iter().any(|item| item.stability.is_some())
+                    || item
+                        .methods
+                        .iter()
+                        .chain(item.constructors.iter())
+                        .any(|item| item.stability.is_some())
+            })
+        || document
+            .functions
+            .iter()
+            .any(|item| item.stability.is_some())
+        || document
+            .delegates
+            .iter()
+            .any(|item| item.stability.is_some())
+}
+
+/// Symbols that appeared in recent bakings, grouped by dates they appeared at, newest first.
+fn bake_recent_index(recent: &[(String, String)], mdbook: &BackendMdBook) -> Option<String> {
+    if recent.is_empty() {
+        return None;
+    }
+    let mut result = "# Recently Added APIs\n".to_owned();
+    result.push_str(&format!(
+        "\nAPIs added in the last {} days.\n",
+        mdbook.recent_days
+    ));
+    let mut last_date = None;
+    for (date, reference) in recent {
+        if last_date != Some(date) {
+            result.push_str(&format!("\n## {}\n\n", date));
+            last_date = Some(date);
+        }
+        result.push_str(&format!("- [`{}`]()\n", reference));
+    }
+    Some(result)
+}
+
+/// Symbols marked as experimental, which may change or go away in future versions.
+fn bake_experimental_index(document: &Document, mdbook: &BackendMdBook) -> Option<String> {
+    let experimental = Some(Stability::Experimental);
+    let mut items = document
+        .enums
+        .iter()
+        .filter(|item| item.stability == experimental)
+        .map(|item| (type_reference(&item.name, document), &item.doc_comments))
+        .chain(
+            document
+                .structs
+                .iter()
+                .chain(document.classes.iter())
+                .filter(|item| item.stability == experimental)
+                .map(|item| (type_reference(&item.name, document), &item.doc_comments)),
+        )
+        .chain(
+            document
+                .functions
+                .iter()
+                .filter(|item| item.stability == experimental)
+                .map(|item| (format!("[`function: {}`]()", item.name), &item.doc_comments)),
+        )
+        .chain(
+            document
+                .delegates
+                .iter()
+                .filter(|item| item.stability == experimental)
+                .map(|item| (type_reference(&item.name, document), &item.doc_comments)),
+        )
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return None;
+    }
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut result = "# Experimental APIs\n\n".to_owned();
+    result.push_str("These APIs may change or go away in future versions.\n\n");
+    for (reference, doc_comments) in items {
+        match doc_brief(doc_comments, mdbook.brief) {
+            Some(brief) => result.push_str(&format!("- {} — {}\n", reference, brief)),
+            None => result.push_str(&format!("- {}\n", reference)),
+        }
+    }
+    Some(result)
+}
+
+/// Symbols with their own pages grouped by their owners, so readers know whom to ask about
+/// an API.
+fn bake_owners_index(document: &Document) -> Option<String> {
+    let mut owners = BTreeMap::<&str, Vec<String>>::new();
+    let items = document
+        .enums
+        .iter()
+        .map(|item| (&item.owners, type_reference(&item.name, document)))
+        .chain(
+            document
+                .structs
+                .iter()
+                .chain(document.classes.iter())
+                .map(|item| (&item.owners, type_reference(&item.name, document))),
+        )
+        .chain(
+            document
+                .functions
+                .iter()
+                .map(|item| (&item.owners, format!("[`function: {}`]()", item.name))),
+        )
+        .chain(
+            document
+                .delegates
+                .iter()
+                .map(|item| (&item.owners, type_reference(&item.name, document))),
+        );
+    for (item_owners, reference) in items {
+        for owner in item_owners {
+            owners.entry(owner).or_default().push(reference.to_owned());
+        }
+    }
+    if owners.is_empty() {
+        return None;
+    }
+    let mut result = "# API Owners\n".to_owned();
+    for (owner, mut references) in owners {
+        references.sort();
+        result.push_str(&format!("\n## {}\n\n", owner));
+        for reference in references {
+            result.push_str(&format!("- {}\n", reference));
+        }
+    }
+    Some(result)
+}
+
+/// Questions and answers of `@faq` doc tags, grouped by struct or class they concern (or by
+/// enum, function or delegate itself), each one linking back to its symbol.
+fn bake_faq(document: &Document) -> Option<String> {
+    let mut groups = BTreeMap::<&str, Vec<(&Faq, String)>>::new();
+    for item in &document.enums {
+        for faq in &item.faqs {
+            let reference = type_reference(&item.name, document);
+            groups.entry(&item.name).or_default().push((faq, reference));
+        }
+    }
+    for item in document.structs.iter().chain(document.classes.iter()) {
+        for faq in &item.faqs {
+            let reference = type_reference(&item.name, document);
+            groups.entry(&item.name).or_default().push((faq, reference));
+        }
+        for property in &item.properties {
+            for faq in &property.faqs {
+                let reference = member_reference(item, &property.name);
+                groups.entry(&item.name).or_default().push((faq, reference));
+            }
+        }
+        for function in item.constructors.iter().chain(item.methods.iter()) {
+            for faq in &function.faqs {
+                let reference = member_reference(item, &function.name);
+                groups.entry(&item.name).or_default().push((faq, reference));
+            }
+        }
+    }
+    for item in &document.functions {
+        for faq in &item.faqs {
+            let reference = format!("[`function: {}`]()", item.name);
+            groups.entry(&item.name).or_default().push((faq, reference));
+        }
+    }
+    for item in &document.delegates {
+        for faq in &item.faqs {
+            let reference = type_reference(&item.name, document);
+            groups.entry(&item.name).or_default().push((faq, reference));
+        }
+    }
+    if groups.is_empty() {
+        return None;
+    }
+    let mut result = "# FAQ\n".to_owned();
+    for (group, faqs) in groups {
+        result.push_str(&format!("\n## {}\n", group));
+        for (faq, reference) in faqs {
+            result.push_str(&format!(
+                "\n### {}\n\n{}\n\n_See {}_\n",
+                faq.question, faq.answer, reference
+            ));
+        }
+    }
+    Some(result)
+}
+
+/// Static functions of all Blueprint Function Libraries, grouped by their Blueprint node
+/// category.
+fn bake_function_libraries(document: &Document, mdbook: &BackendMdBook) -> Option<String> {
+    let mut groups = BTreeMap::<String, (String, Vec<String>)>::new();
+    for owner in &document.classes {
+        if !document.is_blueprint_function_library(owner) {
+            continue;
+        }
+        for function in owner.methods.iter().filter(|function| function.is_static) {
+            let category = function
+                .category()
+                .map(category_path)
+                .filter(|category| !category.is_empty())
+                .unwrap_or_else(|| "Uncategorized".to_owned());
+            let (_, rows) = groups
+                .entry(category.to_lowercase())
+                .or_insert_with(|| (category, vec![]));
+            rows.push(format!(
+                "| {} | `{}` | {} |\n",
+                member_reference(owner, &function.name),
+                owner.name,
+                doc_brief(&function.doc_comments, mdbook.brief).unwrap_or_default()
+            ));
+        }
+    }
+    if groups.is_empty() {
+        return None;
+    }
+    let mut result = "# Function Libraries\n".to_owned();
+    for (category, mut rows) in groups.into_values() {
+        rows.sort();
+        result.push_str(&format!("\n## {}\n\n", category));
+        result.push_str("| Function | Library | Description |\n");
+        result.push_str("| --- | --- | --- |\n");
+        for row in rows {
+            result.push_str(&row);
+        }
+    }
+    Some(result)
+}
+
+/// Inheritance trees of structs and classes, rooted at types that derive from nothing or from
+/// undocumented types, along with diagram of them. Types with neither parents nor children are
+/// left out.
+fn bake_class_hierarchy(
+    document: &Document,
+    mdbook: &BackendMdBook,
+    diagrams: &Diagrams,
+) -> Option<String> {
+    let roots = document
+        .classes
+        .iter()
+        .chain(document.structs.iter())
+        .filter_map(|item| match document.parent_chain(item).pop() {
+            Some(root) => Some(root),
+            None if !document.children(&item.name).is_empty() => Some(item.name.to_owned()),
+            None => None,
+        })
+        .collect::<BTreeSet<_>>();
+    if roots.is_empty() {
+        return None;
+    }
+    let mut result = "# Class Hierarchy\n\n".to_owned();
+    if let Some(diagrams) = diagrams.get(CLASS_HIERARCHY) {
+        for (title, path) in diagrams {
+            result.push_str(&format!("![{}](../{})\n\n", title, path));
+        }
+    }
+    if mdbook.mermaid
+        && let Some(chart) = hierarchy_chart(document)
+    {
+        result.push_str(&format!("```mermaid\n{}```\n\n", chart));
+    }
+    let mut visited = BTreeSet::new();
+    for root in &roots {
+        bake_hierarchy_tree(root, 0, document, &mut visited, &mut result);
+    }
+    Some(result)
+}
+
+fn bake_hierarchy_tree<'a>(
+    name: &'a str,
+    depth: usize,
+    document: &'a Document,
+    visited: &mut BTreeSet<&'a str>,
+    result: &mut String,
+) {
+    // inheritance cycles are listed once
+    if !visited.insert(name) {
+        return;
+    }
+    result.push_str(&format!(
+        "{}- {}\n",
+        "  ".repeat(depth),
+        type_reference(name, document)
+    ));
+    let mut children = document.children(name);
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+    for child in children {
+        bake_hierarchy_tree(&child.name, depth + 1, document, visited, result);
+    }
+}
+
+/// Node of tree of editor and Blueprint categories of members. Categories that differ only in
+/// case or whitespace share the same node.
+#[derive(Default)]
+struct CategoryNode {
+    title: String,
+    entries: Vec<String>,
+    children: BTreeMap<String, CategoryNode>,
+}
+
+fn category_tree(document: &Document, mdbook: &BackendMdBook) -> CategoryNode {
+    let mut result = CategoryNode {
+        title: "Categories".to_owned(),
+        ..Default::default()
+    };
+    for owner in document.structs.iter().chain(document.classes.iter()) {
+        let properties = owner
+            .properties
+            .iter()
+            .map(|item| ("Property", &item.name, item.category(), &item.doc_comments));
+        let methods = owner
+            .methods
+            .iter()
+            .map(|item| ("Function", &item.name, item.category(), &item.doc_comments));
+        for (kind, name, category, doc_comments) in properties.chain(methods) {
+            let Some(category) = category else {
+                continue;
+            };
+            let mut parts = category_parts(category);
+            if let Some(depth) = mdbook.category_depth {
+                parts.truncate(depth.max(1));
+            }
+            if parts.is_empty() {
+                continue;
+            }
+            let mut node = &mut result;
+            for part in parts {
+                node = node.children.entry(part.to_lowercase()).or_default();
+                // prefer capitalized spelling, so it does not depend on order of members
+                if node.title.is_empty() || part < node.title {
+                    node.title = part;
+                }
+            }
+            let entry = match doc_brief(doc_comments, mdbook.brief) {
+                Some(brief) => format!(
+                    "- {} ({}) — {}\n",
+                    member_reference(owner, name),
+                    kind,
+                    brief
+                ),
+                None => format!("- {} ({})\n", member_reference(owner, name), kind),
+            };
+            node.entries.push(entry);
+        }
+    }
+    result
+}
+
+/// Bakes listing page of category node and all its subcategories, nested in navigation.
+fn bake_category_pages(
+    node: &CategoryNode,
+    title: &str,
+    index_path: &str,
+    depth: usize,
+    index: &mut String,
+    files: &mut HashMap<String, String>,
+) {
+    let mut content = format!("# {}\n\n", title);
+    if !node.children.is_empty() {
+        let directory = index_path.rsplit('/').next().unwrap_or(index_path);
+        content.push_str("## Subcategories\n\n");
+        for (key, child) in &node.children {
+            let child_path = format!("{}/{}", directory, group_slug(key));
+            content.push_str(&format!("- [{}]({}.md)\n", child.title, child_path));
+        }
+        content.push('\n');
+    }
+    if !node.entries.is_empty() {
+        let mut entries = node.entries.to_owned();
+        entries.sort();
+        content.push_str("## Members\n\n");
+        for entry in entries {
+            content.push_str(&entry);
+        }
+    }
+    files.insert(format!("src/{}.md", index_path), content);
+    for (key, child) in &node.children {
+        let child_path = format!("{}/{}", index_path, group_slug(key));
+        index.push_str(&format!(
+            "{}- [{}]({}.md)\n",
+            "  ".repeat(depth + 1),
+            child.title,
+            child_path
+        ));
+        let child_title = if depth > 1 {
+            format!("{} › {}", title, child.title)
+        } else {
+            child.title.to_owned()
+        };
+        bake_category_pages(child, &child_title, &child_path, depth + 1, index, files);
+    }
+}
+
+/// Rendered diagram images of symbols, as pairs of title and path relative to book sources.
+pub type Diagrams = HashMap<String, Vec<(String, String)>>;
+
+/// Key of class hierarchy diagram in diagrams, that no symbol name can collide with.
+const CLASS_HIERARCHY: &str = "reference/hierarchy";
+
+/// When previewing changes, diagrams are not rendered and only already existing ones are linked.
+fn bake_diagrams(
+    document: &Document,
+    mdbook: &BackendMdBook,
+    source_dir: &Path,
+    diff_output: bool,
+) -> Diagrams {
+    let mut result = Diagrams::default();
+    if mdbook.class_hierarchy
+        && let Some(source) = hierarchy_graph(document)
+    {
+        let path = "assets/diagrams/hierarchy.svg".to_owned();
+        let output = source_dir.join(&path);
+        let available = if diff_output {
+            output.is_file()
+        } else {
+            render_svg(&source, &output)
+        };
+        if available {
+            result.insert(
+                CLASS_HIERARCHY.to_owned(),
+                vec![("Class hierarchy diagram".to_owned(), path)],
+            );
+        }
+    }
+    for item in document.structs.iter().chain(document.classes.iter()) {
+        let graphs = [
+            (
+                "inheritance",
+                "Inheritance",
+                inheritance_graph(document, item),
+            ),
+            (
+                "composition",
+                "Composition",
+                composition_graph(document, item),
+            ),
+        ];
+        for (kind, title, source) in graphs {
+            let Some(source) = source else {
+                continue;
+            };
+            let directory = match item.mode {
+                StructClassMode::Struct => "structs",
+                StructClassMode::Class => "classes",
+            };
+            let path = format!(
+                "assets/diagrams/{}-{}.svg",
+                page_stem(document, directory, &item.name),
+                kind
+            );
+            let output = source_dir.join(&path);
+            let available = if diff_output {
+                output.is_file()
+            } else {
+                render_svg(&source, &output)
+            };
+            if available {
+                result
+                    .entry(item.name.to_owned())
+                    .or_default()
+                    .push((format!("{} diagram of {}", title, item.name), path));
+            }
+        }
+    }
+    result
+}
+
+fn preprocess_content(
+    content: &str,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    page: &str,
+    relative_path: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    let content = resolve_directives(content, document, mdbook, page, diagnostics);
+    fix_site_references(
+        &content,
+        mdbook.site_url.as_deref().unwrap_or("/"),
+        relative_path,
+    )
+}
+
+/// Directives of pages and doc comments: `@example Name` lines, ` ```compare``` `,
+/// ` ```members``` ` and ` ```snippet``` ` blocks, and `[`class: Name`]()` code references.
+static DIRECTIVES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?m:^(?P<example_prefix>[ \t]*)@example[ \t]+(?P<example>\w+)[ \t]*$)",
+        r"|```[ \t]*compare\b(?P<compare>[^`]*)```",
+        r"|```[ \t]*members\b(?P<members>[^`]*)```",
+        r"|```\s*snippet[\n\r]+(?P<snippet_prefix>[\s/]*)(?P<snippet>\w+)[\r\n]+\s*```",
+        r"|\[`\s*(?P<element>\w+)\s*:\s*(?P<reference>\w+)\s*(::\s*(?P<section>\w+))?`\]s*\(\s*\)",
+    ))
+    .unwrap()
+});
+
+/// Resolves all directives of content in single pass over it. Tables generated from
+/// comparison and member list blocks get directives they contain (e.g. code references in
+/// member descriptions) resolved as well.
+fn resolve_directives(
+    content: &str,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    DIRECTIVES
+        .replace_all(content, |captures: &Captures| {
+            if let Some(name) = captures.name("example") {
+                let prefix = &captures["example_prefix"];
+                example_link(prefix, name.as_str(), document, page, diagnostics)
+            } else if let Some(options) = captures.name("compare") {
+                let table = comparison_table(options.as_str(), document, page, diagnostics);
+                resolve_directives(&table, document, mdbook, page, diagnostics)
+            } else if let Some(options) = captures.name("members") {
+                let table =
+                    member_list_table(options.as_str(), document, mdbook, page, diagnostics);
+                resolve_directives(&table, document, mdbook, page, diagnostics)
+            } else if let Some(name) = captures.name("snippet") {
+                let prefix = &captures["snippet_prefix"];
+                snippet_block(prefix, name.as_str(), document, mdbook, page, diagnostics)
+            } else {
+                code_reference_link(
+                    &captures["element"],
+                    &captures["reference"],
+                    captures.name("section").map(|section| section.as_str()),
+                    document,
+                    mdbook,
+                    page,
+                    diagnostics,
+                )
+            }
+        })
+        .into_owned()
+}
+
+/// Link to example page standing for `@example Name` doc tag.
+fn example_link(
+    prefix: &str,
+    name: &str,
+    document: &Document,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    if document.examples.contains_key(name) {
+        format!("{}**Example:** [`{}`](/examples/{}.md)", prefix, name, name)
+    } else {
+        diagnostics.warning(
+            "examples",
+            page,
+            format!("Trying to link non-existing example: {}", name),
+        );
+        format!("{}**Example:** `{}`", prefix, name)
+    }
+}
+
+/// Side-by-side table of members of structs or classes compared by ` ```compare``` ` block with
+/// given options. Members can be `properties` (default) or `methods`.
+fn comparison_table(
+    options: &str,
+    document: &Document,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    let mut names = vec![];
+    let mut members = "properties";
+    for (key, value) in block_options(options) {
+        match key {
+            "classes" | "structs" => names.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty()),
+            ),
+            "members" => members = value,
+            _ => diagnostics.warning(
+                "comparisons",
+                page,
+                format!("Unknown comparison option: {}", key),
+            ),
+        }
+    }
+    let items = names
+        .into_iter()
+        .filter_map(|name| {
+            let item = document
+                .structs
+                .iter()
+                .chain(document.classes.iter())
+                .find(|item| item.name == name);
+            if item.is_none() {
+                diagnostics.warning(
+                    "comparisons",
+                    page,
+                    format!("Trying to compare non-existing struct or class: {}", name),
+                );
+            }
+            item
+        })
+        .collect::<Vec<_>>();
+    // every cell tells how member is declared in compared type, if at all
+    let rows = match members {
+        "properties" => items
+            .iter()
+            .flat_map(|item| &item.properties)
+            .map(|property| property.name.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|name| {
+                let cells = items.iter().map(|item| {
+                    item.properties
+                        .iter()
+                        .find(|property| property.name == name)
+                        .map(|property| format!("`{}`", property.value_type))
+                });
+                (name, cells.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>(),
+        "methods" => items
+            .iter()
+            .flat_map(|item| &item.methods)
+            .map(|method| method.name.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|name| {
+                let cells = items.iter().map(|item| {
+                    item.methods
+                        .iter()
+                        .find(|method| method.name == name)
+                        .map(|method| {
+                            format!("`{}`", method.return_type.as_deref().unwrap_or("void"))
+                        })
+                });
+                (name, cells.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>(),
+        _ => {
+            diagnostics.warning(
+                "comparisons",
+                page,
+                format!("Unknown compared members: {}", members),
+            );
+            return String::default();
+        }
+    };
+    if items.is_empty() {
+        return String::default();
+    }
+    let mut result = "| Member |".to_owned();
+    for item in &items {
+        result.push_str(&format!(" {} |", type_reference(&item.name, document)));
+    }
+    result.push_str("\n| --- |");
+    result.push_str(&" --- |".repeat(items.len()));
+    result.push('\n');
+    for (name, cells) in rows {
+        result.push_str(&format!("| `{}` |", name));
+        for cell in cells {
+            result.push_str(&format!(" {} |", cell.as_deref().unwrap_or("—")));
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[test]
+fn test_comparison_table() {
+    let property = |name: &str, value_type: &str| Property {
+        name: name.to_owned(),
+        value_type: value_type.to_owned(),
+        ..Default::default()
+    };
+    let method = |name: &str, return_type: Option<&str>| Function {
+        name: name.to_owned(),
+        return_type: return_type.map(str::to_owned),
+        ..Default::default()
+    };
+    let document = Document {
+        structs: vec![
+            StructClass {
+                name: "FSlow".to_owned(),
+                properties: vec![property("Speed", "float"), property("Drag", "float")],
+                methods: vec![method("Reset", None)],
+                ..Default::default()
+            },
+            StructClass {
+                name: "FFast".to_owned(),
+                properties: vec![property("Speed", "double"), property("Boost", "int32")],
+                methods: vec![method("Reset", Some("bool"))],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let mut diagnostics = Diagnostics::default();
+    assert_eq!(
+        comparison_table(
+            " structs=FSlow, FFast\n",
+            &document,
+            "page.md",
+            &mut diagnostics
+        ),
+        "| Member | [`struct: FSlow`]() | [`struct: FFast`]() |\n\
+        | --- | --- | --- |\n\
+        | `Boost` | — | `int32` |\n\
+        | `Drag` | `float` | — |\n\
+        | `Speed` | `float` | `double` |\n"
+    );
+    assert_eq!(
+        comparison_table(
+            "structs=FSlow,FFast members=methods",
+            &document,
+            "page.md",
+            &mut diagnostics
+        ),
+        "| Member | [`struct: FSlow`]() | [`struct: FFast`]() |\n\
+        | --- | --- | --- |\n\
+        | `Reset` | `void` | `bool` |\n"
+    );
+    assert!(diagnostics.items.is_empty());
+
+    let table = comparison_table(
+        "structs=FSlow,FMissing members=fields",
+        &document,
+        "page.md",
+        &mut diagnostics,
+    );
+    assert!(table.is_empty());
+    let messages = diagnostics
+        .items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Warning in page.md: Trying to compare non-existing struct or class: FMissing",
+            "Warning in page.md: Unknown compared members: fields",
+        ]
+    );
+
+    let content = "Tuning:\n\n```compare structs=FSlow,FFast\n```\n";
+    let resolved = resolve_directives(
+        content,
+        &document,
+        &Default::default(),
+        "page.md",
+        &mut Default::default(),
+    );
+    assert_eq!(
+        resolved,
+        "Tuning:\n\n\
+        | Member | [**`FSlow`**](/reference/structs/FSlow.md) | [**`FFast`**](/reference/structs/FFast.md) |\n\
+        | --- | --- | --- |\n\
+        | `Boost` | — | `int32` |\n\
+        | `Drag` | `float` | — |\n\
+        | `Speed` | `float` | `double` |\n\n"
+    );
+}
+
+/// Summary table of members of struct or class listed by ` ```members``` ` block with given
+/// options. Kind can be `functions`, `properties` or `all` (default), and optional category
+/// includes its subcategories.
+fn member_list_table(
+    options: &str,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    let mut name = None;
+    let mut kind = "all";
+    let mut category = None;
+    for (key, value) in block_options(options) {
+        match key {
+            "class" | "struct" => name = Some(value),
+            "kind" => kind = value,
+            "category" => category = Some(category_parts(value)),
+            _ => diagnostics.warning(
+                "member-lists",
+                page,
+                format!("Unknown member list option: {}", key),
+            ),
+        }
+    }
+    let Some(name) = name else {
+        diagnostics.warning(
+            "member-lists",
+            page,
+            "Member list does not tell its struct or class",
+        );
+        return String::default();
+    };
+    let Some(owner) = document
+        .structs
+        .iter()
+        .chain(document.classes.iter())
+        .find(|item| item.name == name)
+    else {
+        diagnostics.warning(
+            "member-lists",
+            page,
+            format!(
+                "Trying to list members of non-existing struct or class: {}",
+                name
+            ),
+        );
+        return String::default();
+    };
+    let (properties, methods) = match kind {
+        "all" => (true, true),
+        "properties" => (true, false),
+        "functions" | "methods" => (false, true),
+        _ => {
+            diagnostics.warning(
+                "member-lists",
+                page,
+                format!("Unknown member list kind: {}", kind),
+            );
+            return String::default();
+        }
+    };
+    let in_category = |member_category: Option<&str>| match &category {
+        Some(category) => {
+            let parts = category_parts(member_category.unwrap_or_default());
+            parts.len() >= category.len()
+                && parts
+                    .iter()
+                    .zip(category)
+                    .all(|(part, expected)| part.eq_ignore_ascii_case(expected))
+        }
+        None => true,
+    };
+    let properties = owner
+        .properties
+        .iter()
+        .filter(|_| properties)
+        .filter(|item| in_category(item.category()))
+        .map(|item| (&item.name, item.value_type.as_str(), &item.doc_comments));
+    let methods = owner
+        .methods
+        .iter()
+        .filter(|_| methods)
+        .filter(|item| in_category(item.category()))
+        .map(|item| {
+            let return_type = item.return_type.as_deref().unwrap_or("void");
+            (&item.name, return_type, &item.doc_comments)
+        });
+    let mut result = "| Member | Type | Description |\n| --- | --- | --- |\n".to_owned();
+    for (name, value_type, doc_comments) in properties.chain(methods) {
+        result.push_str(&format!(
+            "| {} | `{}` | {} |\n",
+            member_reference(owner, name),
+            value_type,
+            doc_brief(doc_comments, mdbook.brief)
+                .unwrap_or_default()
+                .replace('|', "\\|")
+        ));
+    }
+    result
+}
+
+/// Options of directive blocks, as `key=value` pairs separated by whitespace. Values can contain
+/// whitespace too, they end where next option starts, e.g. `category=Public API kind=all`.
+fn block_options(content: &str) -> Vec<(&str, &str)> {
+    let mut result = Vec::<(&str, &str)>::default();
+    let mut rest = content.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .filter(|(_, c)| c.is_whitespace())
+            .map(|(index, _)| index)
+            .find(|index| {
+                let next = rest[*index..].trim_start();
+                next.split_whitespace()
+                    .next()
+                    .is_some_and(|word| word.contains('='))
+            })
+            .unwrap_or(rest.len());
+        if let Some((key, value)) = rest[..end].split_once('=') {
+            result.push((key.trim(), value.trim()));
+        }
+        rest = rest[end..].trim_start();
+    }
+    result
+}
+
+#[test]
+fn test_block_options() {
+    assert_eq!(
+        block_options(" class=UFoo kind=functions\ncategory=Public API|Items "),
+        vec![
+            ("class", "UFoo"),
+            ("kind", "functions"),
+            ("category", "Public API|Items"),
+        ]
+    );
+    assert_eq!(block_options("classes=A,B"), vec![("classes", "A,B")]);
+    assert!(block_options("").is_empty());
+}
+
+/// Anchor of method on page of its struct or class. Operators get explicit anchors with their
+/// symbols spelled out, other methods the ones generated from their headings.
+fn method_anchor(name: &str) -> String {
+    symbol_slug(name).to_lowercase()
+}
+
+/// Link to page of symbol that `[`element: Name::section`]()` code reference points to. Section
+/// of enum reference is its variant, of delegate reference its parameter.
+fn code_reference_link(
+    element: &str,
+    reference: &str,
+    section: Option<&str>,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    let (names, directory) = match element {
+        "enum" => (
+            document
+                .enums
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect(),
+            "enums",
+        ),
+        "struct" => (
+            document
+                .structs
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect(),
+            "structs",
+        ),
+        "class" => (
+            document
+                .classes
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect(),
+            "classes",
+        ),
+        "function" => (
+            document
+                .functions
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect(),
+            "functions",
+        ),
+        "delegate" => (
+            document
+                .delegates
+                .iter()
+                .map(|item| item.name.as_str())
+                .collect(),
+            "delegates",
+        ),
+        "property" | "method" => (
+            document
+                .structs
+                .iter()
+                .chain(document.classes.iter())
+                .map(|item| item.name.as_str())
+                .collect(),
+            "",
+        ),
+        _ => (vec![], ""),
+    };
+    let mut candidates = resolve_symbol_name(&names, reference, mdbook.prefix_tolerant_references);
+    if candidates.is_empty() {
+        candidates = renamed_symbols(document, element, reference);
+    }
+    if candidates.len() > 1 {
+        diagnostics.warning(
+            "code-references",
+            page,
+            format!(
+                "Ambiguous code reference `{}: {}` resolved to `{}`, other candidates: {}",
+                element,
+                reference,
+                candidates[0],
+                candidates[1..]
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+    }
+    let path = match (element, candidates.first()) {
+        ("property" | "method", Some(name)) => document.find_struct_class(name).and_then(|item| {
+            let section = section?;
+            let found = if element == "property" {
+                item.properties
+                    .iter()
+                    .any(|property| property.name == section)
+            } else {
+                item.methods.iter().any(|method| method.name == section)
+            };
+            let directory = match item.mode {
+                StructClassMode::Struct => "structs",
+                StructClassMode::Class => "classes",
+            };
+            found.then(|| {
+                format!(
+                    "/reference/{}/{}.md",
+                    directory,
+                    page_stem(document, directory, name)
+                )
+            })
+        }),
+        ("delegate", Some(name)) if section.is_some() => document
+            .delegates
+            .iter()
+            .find(|item| item.name == *name)
+            .filter(|item| {
+                item.arguments
+                    .iter()
+                    .any(|argument| argument.name.as_deref() == section)
+            })
+            .map(|_| {
+                format!(
+                    "/reference/delegates/{}.md",
+                    page_stem(document, "delegates", name)
+                )
+            }),
+        (_, Some(name)) => Some(format!(
+            "/reference/{}/{}.md",
+            directory,
+            page_stem(document, directory, name)
+        )),
+        _ => None,
+    };
+    let name = candidates.first().copied().unwrap_or(reference);
+    if let Some(path) = path {
+        if let Some(section) = section {
+            let anchor = if element == "method" {
+                method_anchor(section)
+            } else {
+                section.to_lowercase()
+            };
+            format!("[**`{}::{}`**]({}#{})", name, section, path, anchor)
+        } else {
+            format!("[**`{}`**]({})", name, path)
+        }
+    } else if let Some(section) = section {
+        format!("**`{}::{}`**", name, section)
+    } else {
+        format!("**`{}`**", name)
+    }
+}
+
+/// Names of symbols of given code reference kind that were renamed from given name.
+fn renamed_symbols<'a>(document: &'a Document, element: &str, alias: &str) -> Vec<&'a str> {
+    let renamed = |name: &'a String, aliases: &[String]| {
+        aliases
+            .iter()
+            .any(|other| other == alias)
+            .then_some(name.as_str())
+    };
+    match element {
+        "enum" => document
+            .enums
+            .iter()
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        "struct" => document
+            .structs
+            .iter()
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        "class" => document
+            .classes
+            .iter()
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        "function" => document
+            .functions
+            .iter()
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        "delegate" => document
+            .delegates
+            .iter()
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        "property" | "method" => document
+            .structs
+            .iter()
+            .chain(document.classes.iter())
+            .filter_map(|item| renamed(&item.name, &item.aliases))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Names of symbols that code reference name points to. Exact match wins over case-insensitive
+/// one, which wins over match ignoring Unreal type prefix (e.g. `MyActor` resolving to
+/// `AMyActor`), if enabled. More than one name means reference is ambiguous.
+fn resolve_symbol_name<'a>(names: &[&'a str], name: &str, prefix_tolerant: bool) -> Vec<&'a str> {
+    let unprefixed = |candidate: &str| {
+        let mut chars = candidate.chars();
+        matches!(chars.next(), Some('A' | 'U' | 'F' | 'E' | 'I' | 'S' | 'T'))
+            && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && candidate[1..].eq_ignore_ascii_case(name)
+    };
+    let exact = names.iter().copied().filter(|candidate| *candidate == name);
+    let result = exact.collect::<Vec<_>>();
+    if !result.is_empty() {
+        return result;
+    }
+    let result = names
+        .iter()
+        .copied()
+        .filter(|candidate| candidate.eq_ignore_ascii_case(name))
+        .collect::<Vec<_>>();
+    if !result.is_empty() || !prefix_tolerant {
+        return result;
+    }
+    names
+        .iter()
+        .copied()
+        .filter(|candidate| unprefixed(candidate))
+        .collect()
+}
+
+#[test]
+fn test_resolve_symbol_name() {
+    let names = ["AMyActor", "UMyActor", "FItem", "EState"];
+    assert_eq!(resolve_symbol_name(&names, "FItem", false), vec!["FItem"]);
+    assert_eq!(resolve_symbol_name(&names, "fitem", false), vec!["FItem"]);
+    assert!(resolve_symbol_name(&names, "State", false).is_empty());
+    assert_eq!(resolve_symbol_name(&names, "state", true), vec!["EState"]);
+    assert_eq!(
+        resolve_symbol_name(&names, "MyActor", true),
+        vec!["AMyActor", "UMyActor"]
+    );
+}
+
+/// Code block with content of snippet injected by ` ```snippet``` ` block, indented with given
+/// prefix.
+fn snippet_block(
+    prefix: &str,
+    name: &str,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    page: &str,
+    diagnostics: &mut Diagnostics,
+) -> String {
+    if let Some(content) = document.snippets.get(name) {
+        let content = content
+            .lines()
+            .map(|line| format!("{}{}", prefix, line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("```cpp\n{}\n{}```", content, prefix)
+    } else {
+        let message = format!("Trying to inject non-existing snippet: {}", name);
+        match mdbook.missing_snippet {
+            MissingSnippet::Placeholder => {
+                diagnostics.warning("snippets", page, message);
+                format!("```\n{}Missing snippet: {}\n{}```", prefix, name, prefix)
+            }
+            MissingSnippet::Drop => {
+                diagnostics.warning("snippets", page, message);
+                String::default()
+            }
+            MissingSnippet::Fail => {
+                diagnostics.error("snippets", page, message);
+                String::default()
+            }
+        }
+    }
+}
+
+/// Markdown link target pointing to page of the book.
+static SITE_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\]\s*\((\s*/)?(.*\.md(\s*#.*)?)\)").unwrap());
+
+fn fix_site_references(content: &str, site_url: &str, relative_path: &str) -> String {
+    SITE_REFERENCE
+        .replace_all(content, |captures: &Captures| {
+            let relative_path = captures.get(1).map(|_| "").unwrap_or_else(|| relative_path);
+            let reference = captures.get(2).unwrap().as_str().trim();
+            format!("]({}{}{})", site_url, relative_path, reference)
+        })
+        .into()
 }
 
 fn include_book_index(
@@ -400,10 +2539,14 @@ fn include_book_index(
     }
 }
 
-fn bake_specifiers(specifiers: &Specifiers, content: &mut String) {
+fn bake_specifiers(specifiers: &Specifiers, kind: &str, level: usize, content: &mut String) {
+    content.push_str(&format!(
+        "<div class=\"ue-specifiers {}\">\n\n",
+        kind_class(kind, true)
+    ));
     content.push_str("**_Reflection-enabled_**\n");
     if !specifiers.attributes.is_empty() {
-        content.push_str("\n### Specifiers:\n");
+        content.push_str(&format!("\n{} Specifiers:\n", heading(level)));
         for attribute in &specifiers.attributes {
             match attribute {
                 Attribute::Single(name) => {
@@ -416,7 +2559,7 @@ fn bake_specifiers(specifiers: &Specifiers, content: &mut String) {
         }
     }
     if !specifiers.meta.is_empty() {
-        content.push_str("\n### Meta Specifiers:\n");
+        content.push_str(&format!("\n{} Meta Specifiers:\n", heading(level)));
         for attribute in &specifiers.meta {
             match attribute {
                 Attribute::Single(name) => {
@@ -428,61 +2571,378 @@ fn bake_specifiers(specifiers: &Specifiers, content: &mut String) {
             }
         }
     }
-    content.push('\n');
+    content.push_str("\n</div>\n\n");
 }
 
-fn bake_enum(item: &Enum, content: &mut String) {
-    content.push_str(&format!("# **Enum: `{}`**\n\n", item.name));
-    content.push_str(&format!("```cpp\n//  {} : {}\n\n{}\n```\n\n", item.filename, item.fileline, item.signature()));
-    if let Some(specifiers) = &item.specifiers {
-        content.push_str("---\n\n");
-        bake_specifiers(specifiers, content);
+/// Template context of symbol section, with symbol itself and headings of its section and
+/// subsections.
+fn symbol_context(item: &impl Serialize, level: usize) -> Context {
+    let mut context = Context::new();
+    context.insert("item", item);
+    context.insert("heading", &heading(level));
+    context.insert("subheading", &heading(level + 1));
+    context
+}
+
+/// Part of page rendered on its own, to be put in page by template.
+fn fragment(bake: impl FnOnce(&mut String)) -> String {
+    let mut content = String::default();
+    bake(&mut content);
+    content
+}
+
+/// CSS class of symbol kind, e.g. `ue-uproperty` for reflected property and `ue-property` for
+/// plain one, so themes can tell them apart.
+fn kind_class(kind: &str, reflected: bool) -> String {
+    format!("ue-{}{}", if reflected { "u" } else { "" }, kind)
+}
+
+/// Code block of symbol signature, wrapped with CSS classes as styling hook for themes.
+fn bake_signature(code: &str, classes: &str, content: &mut String) {
+    content.push_str(&format!(
+        "<div class=\"ue-signature {}\">\n\n```cpp\n{}\n```\n\n</div>\n\n",
+        classes, code
+    ));
+}
+
+/// Signature preceded by `File.h : 123` location of declaration (file name, header path and line).
+/// With `location_links` enabled and `edit_url` set, location is link line above code fence
+/// pointing at declaration in repository, otherwise it is a comment in the code.
+fn bake_located_signature(
+    code: &str,
+    (filename, path, line): (&str, Option<&str>, usize),
+    classes: &str,
+    mdbook: &BackendMdBook,
+    content: &mut String,
+) {
+    match (&mdbook.edit_url, path) {
+        (Some(template), Some(path)) if mdbook.location_links => {
+            content.push_str(&format!(
+                "<div class=\"ue-signature {}\">\n\n[`{} : {}`]({})\n\n```cpp\n{}\n```\n\n</div>\n\n",
+                classes,
+                filename,
+                line,
+                source_url(template, path, line),
+                code
+            ));
+        }
+        _ => bake_signature(
+            &format!("//  {} : {}\n\n{}", filename, line, code),
+            classes,
+            content,
+        ),
     }
-    content.push_str("---\n\n");
-    content.push_str(&item.doc_comments.to_owned().unwrap_or_default());
-    content.push_str("\n\n");
 }
 
-fn bake_struct_class(item: &StructClass, content: &mut String) {
-    match item.mode {
-        StructClassMode::Struct => content.push_str(&format!("# **Struct: `{}`**\n\n", item.name)),
-        StructClassMode::Class => content.push_str(&format!("# **Class: `{}`**\n\n", item.name)),
+fn bake_enum(
+    item: &Enum,
+    mdbook: &BackendMdBook,
+    templates: &Templates,
+    level: usize,
+    content: &mut String,
+) {
+    let mut context = symbol_context(item, level);
+    context.insert(
+        "signature",
+        &fragment(|content| {
+            bake_located_signature(
+                &item.signature(),
+                (&item.filename, item.header_path.as_deref(), item.fileline),
+                &kind_class("enum", item.specifiers.is_some()),
+                mdbook,
+                content,
+            )
+        }),
+    );
+    context.insert(
+        "raw_header",
+        &fragment(|content| bake_raw_header(&item.source, content)),
+    );
+    context.insert(
+        "specifiers",
+        &fragment(|content| {
+            if let Some(specifiers) = &item.specifiers {
+                bake_specifiers(specifiers, "enum", level + 2, content);
+            }
+        }),
+    );
+    context.insert(
+        "badges",
+        &fragment(|content| {
+            bake_docs_badge(item.informal_docs, item.undocumented, content);
+            bake_stability_badge(item.stability, content);
+            bake_reflection_badge(item.specifiers.is_some(), content);
+        }),
+    );
+    context.insert(
+        "aliases",
+        &fragment(|content| bake_aliases(&item.aliases, &item.renamed_in, content)),
+    );
+    context.insert(
+        "owners",
+        &fragment(|content| bake_owners(&item.owners, content)),
+    );
+    context.insert(
+        "comments",
+        &item.doc_comments.to_owned().unwrap_or_default(),
+    );
+    context.insert(
+        "variants",
+        &fragment(|content| {
+            let variants = item.variant_names();
+            if variants.is_empty() {
+                return;
+            }
+            content.push_str("| Variant | Display name |\n| --- | --- |\n");
+            for variant in variants {
+                // designers see identifier when there is no display name set
+                let mut display_name = item
+                    .variant_display_name(variant)
+                    .unwrap_or(variant)
+                    .to_owned();
+                if item.is_variant_hidden(variant) {
+                    display_name.push_str(" <span class=\"ue-badge\">Hidden</span>");
+                }
+                content.push_str(&format!(
+                    "| <a id=\"{}\"></a>`{}` | {} |\n",
+                    variant.to_lowercase(),
+                    variant,
+                    display_name
+                ));
+            }
+        }),
+    );
+    content.push_str(&templates.render("enum", &context));
+}
+
+fn bake_struct_class(
+    item: &StructClass,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    templates: &Templates,
+    diagrams: &Diagrams,
+    content: &mut String,
+) {
+    let level = mdbook.heading_level;
+    let mut context = symbol_context(item, level);
+    let title = match item.mode {
+        _ if item.is_interface => "Interface",
+        StructClassMode::Struct => "Struct",
+        StructClassMode::Class => "Class",
+    };
+    context.insert("title", title);
+    context.insert(
+        "quick_facts",
+        &fragment(|content| bake_struct_class_quick_facts(item, document, content)),
+    );
+    context.insert(
+        "diagrams",
+        &fragment(|content| {
+            if let Some(diagrams) = diagrams.get(&item.name) {
+                for (title, path) in diagrams {
+                    content.push_str(&format!("![{}](../../{})\n\n", title, path));
+                }
+            }
+            if mdbook.mermaid
+                && let Some(chart) = inheritance_chart(document, item)
+            {
+                content.push_str(&format!("```mermaid\n{}```\n\n", chart));
+            }
+        }),
+    );
+    let kind = match item.mode {
+        StructClassMode::Struct => "struct",
+        StructClassMode::Class => "class",
+    };
+    context.insert(
+        "signature",
+        &fragment(|content| {
+            bake_located_signature(
+                &item.signature(&mdbook.signature_options()),
+                (&item.filename, item.header_path.as_deref(), item.fileline),
+                &kind_class(kind, item.specifiers.is_some()),
+                mdbook,
+                content,
+            )
+        }),
+    );
+    context.insert(
+        "raw_header",
+        &fragment(|content| bake_raw_header(&item.source, content)),
+    );
+    context.insert(
+        "specifiers",
+        &fragment(|content| {
+            if let Some(specifiers) = &item.specifiers {
+                bake_specifiers(specifiers, kind, level + 2, content);
+            }
+        }),
+    );
+    context.insert(
+        "badges",
+        &fragment(|content| {
+            bake_docs_badge(item.informal_docs, item.undocumented, content);
+            bake_stability_badge(item.stability, content);
+            bake_reflection_badge(item.specifiers.is_some(), content);
+        }),
+    );
+    context.insert(
+        "aliases",
+        &fragment(|content| bake_aliases(&item.aliases, &item.renamed_in, content)),
+    );
+    context.insert(
+        "owners",
+        &fragment(|content| bake_owners(&item.owners, content)),
+    );
+    context.insert(
+        "comments",
+        &fragment(|content| bake_struct_class_comments(item, content)),
+    );
+    let friends = item
+        .friends
+        .iter()
+        .map(|name| type_reference(name, document))
+        .collect::<Vec<_>>()
+        .join(", ");
+    context.insert("friends", &friends);
+    context.insert(
+        "designer_bindings",
+        &fragment(|content| bake_designer_bindings(item, level + 1, content)),
+    );
+    context.insert(
+        "properties",
+        &fragment(|content| {
+            for property in &item.properties {
+                bake_property(property, document, level + 2, content, Some(item));
+            }
+        }),
+    );
+    let members = |functions: &[&Function]| {
+        fragment(|content| {
+            for function in functions {
+                bake_function(
+                    function,
+                    document,
+                    mdbook,
+                    templates,
+                    level + 2,
+                    content,
+                    Some(item),
+                );
+            }
+        })
+    };
+    let constructors = item.constructors.iter().collect::<Vec<_>>();
+    context.insert("constructors", &members(&constructors));
+    let (operators, methods) = item
+        .methods
+        .iter()
+        .partition::<Vec<_>, _>(|method| method.is_operator());
+    context.insert("methods", &members(&methods));
+    context.insert("operators", &members(&operators));
+    content.push_str(&templates.render("class", &context));
+}
+
+/// Widgets and animations that UMG widget asset has to provide for `BindWidget*` properties.
+fn bake_designer_bindings(item: &StructClass, level: usize, content: &mut String) {
+    let rows = item
+        .properties
+        .iter()
+        .filter_map(|property| {
+            let (kind, required) = property.designer_binding()?;
+            let type_ref = TypeRef::parse(&property.value_type);
+            let element = type_ref
+                .held_types()
+                .first()
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| type_ref.name.to_owned());
+            Some(format!(
+                "| {} | {} | `{}` | {} |\n",
+                member_reference(item, &property.name),
+                kind,
+                element,
+                if required { "Yes" } else { "No" }
+            ))
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        return;
     }
-    content.push_str(&format!("```cpp\n//  {} : {}\n\n{}\n```\n\n", item.filename, item.fileline, item.signature()));
-    if let Some(specifiers) = &item.specifiers {
-        content.push_str("---\n\n");
-        bake_specifiers(specifiers, content);
+    content.push_str(&format!(
+        "---\n\n{} **Designer Bindings**\n\n",
+        heading(level)
+    ));
+    content.push_str(
+        "Widget asset has to provide elements with these names to bind them to properties.\n\n",
+    );
+    content.push_str("| Name | Kind | Type | Required |\n| --- | --- | --- | --- |\n");
+    for row in rows {
+        content.push_str(&row);
     }
-    content.push_str("---\n\n");
-    bake_struct_class_comments(&item, content);
     content.push_str("\n\n");
-    if !item.properties.is_empty() {
-        content.push_str("---\n\n# **Properties**\n\n");
-        for property in &item.properties {
-            bake_property(property, content, true);
-        }
-        content.push_str("\n\n");
+}
+
+fn bake_struct_class_quick_facts(item: &StructClass, document: &Document, content: &mut String) {
+    content.push_str("| Quick Facts | |\n|---|---|\n");
+    let parents = document.parent_chain(item);
+    if !parents.is_empty() {
+        let parents = parents
+            .iter()
+            .map(|name| type_reference(name, document))
+            .collect::<Vec<_>>()
+            .join(" → ");
+        content.push_str(&format!("| Parent classes | {} |\n", parents));
     }
-    if !item.constructors.is_empty() {
-        content.push_str("---\n\n# **Constructors**\n\n");
-        for constructor in &item.constructors {
-            bake_function(constructor, content, true);
-        }
-        content.push_str("\n\n");
+    if let Some(module) = &item.module {
+        content.push_str(&format!("| Module | `{}` |\n", module));
     }
-    if !item.methods.is_empty() {
-        content.push_str("---\n\n# **Methods**\n\n");
-        for method in &item.methods {
-            bake_function(method, content, true);
+    content.push_str(&format!("| Header | `{}` |\n", item.filename));
+    if let Some(specifiers) = &item.specifiers {
+        let flags = [
+            "Blueprintable",
+            "BlueprintType",
+            "NotBlueprintable",
+            "NotBlueprintType",
+        ]
+        .into_iter()
+        .filter(|key| specifiers.attribute(key).is_some())
+        .collect::<Vec<_>>();
+        if !flags.is_empty() {
+            content.push_str(&format!("| Blueprint | {} |\n", flags.join(", ")));
         }
-        content.push_str("\n\n");
     }
+    let status = [
+        ("Abstract", item.is_abstract()),
+        ("Interface", item.is_interface),
+        ("Final", item.is_final),
+        ("Editor only", item.is_editor_only),
+    ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    if !status.is_empty() {
+        content.push_str(&format!("| Status | {} |\n", status.join(", ")));
+    }
+    content.push_str(&format!(
+        "| Blueprint-exposed members | {} |\n\n",
+        item.blueprint_exposed_members_count()
+    ));
 }
 
+/// Content of `<summary>` tag of doc comments.
+static SUMMARY_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?ms).*<summary>(.*)</summary>.*").unwrap());
+
+/// Content of `<returns>` tag of doc comments.
+static RETURNS_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<returns>(.*)</returns>").unwrap());
+
+/// Comment inside signature of unnamed property, e.g. `/* Anonymous */`.
+static SIGNATURE_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?ms)/\*(.*)\*/").unwrap());
+
 fn bake_struct_class_comments(item: &StructClass, content: &mut String) {
     if let Some(comments) = item.doc_comments.to_owned() {
-        let re = Regex::new(r"(?ms).*<summary>(.*)</summary>.*").unwrap();
-        if let Some(caps) = re.captures(comments.as_str()) {
+        if let Some(caps) = SUMMARY_TAG.captures(comments.as_str()) {
             if caps.len() > 1 {
                 content.push_str(&caps[1]);
             }
@@ -492,81 +2952,355 @@ fn bake_struct_class_comments(item: &StructClass, content: &mut String) {
     }
 }
 
-fn bake_property(item: &Property, content: &mut String, member: bool) {
-    let level = if member {
-        content.push_str(&format!("* # __`{}`__\n\n", item.name));
+fn bake_property(
+    item: &Property,
+    document: &Document,
+    level: usize,
+    content: &mut String,
+    owner: Option<&StructClass>,
+) {
+    let indentation = if owner.is_some() {
+        content.push_str(&format!("* {} __`{}`__\n\n", heading(level), item.name));
         4
     } else {
-        content.push_str(&format!("# **Property: `{}`**\n\n", item.name));
+        content.push_str(&format!(
+            "{} **Property: `{}`**\n\n",
+            heading(level),
+            item.name
+        ));
         0
     };
-    let indented = indent(level, &{
+    let indented = indent(indentation, &{
         let mut content = String::default();
-        content.push_str(&format!("```cpp\n{}\n```\n\n", item.signature()));
+        bake_signature(
+            &item.signature(),
+            &kind_class("property", item.specifiers.is_some()),
+            &mut content,
+        );
+        bake_raw_header(&item.source, &mut content);
+        if let Some(value) = &item.constructor_default {
+            content.push_str(&format!("**Default (from constructor):** `{}`\n\n", value));
+        }
+        bake_type_description(&item.value_type, document, &mut content);
+        if item.is_union_member {
+            bake_badges(&["Union member"], &mut content);
+        }
+        if let Some(owner) = owner {
+            bake_property_edit_condition(item, owner, &mut content);
+        }
+        bake_property_asset_loading(item, &mut content);
         if let Some(specifiers) = &item.specifiers {
+            bake_property_constraints(specifiers, &mut content);
             content.push_str("---\n\n");
-            bake_specifiers(specifiers, &mut content);
+            bake_specifiers(specifiers, "property", level + 2, &mut content);
         }
         content.push_str("---\n\n");
+        bake_docs_badge(item.informal_docs, item.undocumented, &mut content);
+        bake_stability_badge(item.stability, &mut content);
         content.push_str(&item.doc_comments.to_owned().unwrap_or_default());
         content.push_str("\n\n");
+        if let Some(owner) = owner {
+            bake_property_replication(item, owner, level + 1, &mut content);
+            bake_property_access(item, owner, level + 1, &mut content);
+        }
         content
     });
     content.push_str(&indented);
     content.push_str("\n\n");
 }
 
-fn bake_delegate(item: &Delegate, content: &mut String) {
-    content.push_str(&format!("# **Delegate: `{}`**\n\n", item.name));
-
-    // declaration
-    content.push_str(&format!("```cpp\n// Delegate type\n{}\n\n// Compatible function signature\n{}\n\n```\n\n", item.signature(), item.callback_signature()));
-    // content.push_str("```cpp\n{}\n```\n\n", item.);
+fn bake_property_constraints(specifiers: &Specifiers, content: &mut String) {
+    let meta = |key| specifiers.meta(key).and_then(|attribute| attribute.value());
+    let units = meta("Units")
+        .or_else(|| meta("ForceUnits"))
+        .map(|units| format!(" {}", units))
+        .unwrap_or_default();
+    let range = |min: Option<&str>, max: Option<&str>| match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{}–{}{}", min, max, units)),
+        (Some(min), None) => Some(format!("≥ {}{}", min, units)),
+        (None, Some(max)) => Some(format!("≤ {}{}", max, units)),
+        (None, None) => None,
+    };
+    let clamp = range(meta("ClampMin"), meta("ClampMax"));
+    let slider = range(meta("UIMin"), meta("UIMax"));
+    let line = match (clamp, slider) {
+        (Some(clamp), Some(slider)) => format!("{} (UI slider: {})", clamp, slider),
+        (Some(clamp), None) => clamp,
+        (None, Some(slider)) => format!("UI slider: {}", slider),
+        (None, None) if !units.is_empty() => {
+            content.push_str(&format!("**Units:**{}\n\n", units));
+            return;
+        }
+        (None, None) => return,
+    };
+    content.push_str(&format!("**Constraints:** {}\n\n", line));
+}
 
-    // UDELEGATE specifiers
+fn bake_property_asset_loading(item: &Property, content: &mut String) {
+    let mut notes = vec![];
     if let Some(specifiers) = &item.specifiers {
-        content.push_str("\n\n---\n\n");
-        bake_specifiers(specifiers, content);
+        if specifiers.attribute("AssetRegistrySearchable").is_some() {
+            notes.push("Searchable in the Asset Registry without loading the asset.".to_owned());
+        }
+        if let Some(bundles) = specifiers
+            .meta("AssetBundles")
+            .and_then(|attribute| attribute.value())
+        {
+            let bundles = bundles
+                .split(',')
+                .map(|bundle| format!("`{}`", bundle.trim()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            notes.push(format!("Loaded with asset bundles: {}.", bundles));
+        }
+    }
+    if TypeRef::parse(&item.value_type).is_soft_reference() {
+        notes.push(
+            "Soft reference: referenced asset is not loaded with its owner and has to be \
+            loaded on demand or asynchronously."
+                .to_owned(),
+        );
     }
+    if !notes.is_empty() {
+        content.push_str("> **Asset loading**\n>\n");
+        for note in notes {
+            content.push_str(&format!("> - {}\n", note));
+        }
+        content.push('\n');
+    }
+}
 
-    // main comments
-    bake_delegate_comments(&item.doc_comments, content);
+fn bake_property_edit_condition(item: &Property, owner: &StructClass, content: &mut String) {
+    if let Some(condition) = item.edit_condition() {
+        let hides = item
+            .specifiers
+            .as_ref()
+            .and_then(|specifiers| specifiers.meta("EditConditionHides"))
+            .is_some();
+        let otherwise = if hides { " (hidden otherwise)" } else { "" };
+        let property = |name: &str| owner.properties.iter().any(|item| item.name == name);
+        let (negated, name) = match condition.strip_prefix('!') {
+            Some(name) => (true, name.trim()),
+            None => (false, condition),
+        };
+        if property(name) {
+            content.push_str(&format!(
+                "**Edit condition:** Only editable when {} is {}{}\n\n",
+                member_reference(owner, name),
+                if negated { "false" } else { "true" },
+                otherwise
+            ));
+        } else {
+            let controls = item
+                .edit_condition_names()
+                .into_iter()
+                .filter(|name| property(name))
+                .map(|name| member_reference(owner, name))
+                .collect::<Vec<_>>();
+            content.push_str(&format!(
+                "**Edit condition:** Only editable when `{}`{}\n\n",
+                condition, otherwise
+            ));
+            if !controls.is_empty() {
+                content.push_str(&format!("**Controlled by:** {}\n\n", controls.join(", ")));
+            }
+        }
+    }
+    let dependents = owner
+        .properties
+        .iter()
+        .filter(|property| {
+            property
+                .edit_condition_names()
+                .contains(&item.name.as_str())
+        })
+        .map(|property| member_reference(owner, &property.name))
+        .collect::<Vec<_>>();
+    if !dependents.is_empty() {
+        content.push_str(&format!(
+            "**Controls editability of:** {}\n\n",
+            dependents.join(", ")
+        ));
+    }
+}
 
-    // individual args
-    if !item.arguments.is_empty() {
-        content.push_str("\n\n---\n\n# **Parameters**\n\n");
-        for arg in &item.arguments {
-            bake_delegate_argument(arg, &item.doc_comments, content);
+fn bake_property_replication(
+    item: &Property,
+    owner: &StructClass,
+    level: usize,
+    content: &mut String,
+) {
+    let declared = item.replication();
+    let registered = item.lifetime_replication.as_ref();
+    if declared.is_none() && registered.is_none() {
+        return;
+    }
+    content.push_str(&format!("---\n\n{} **Replication**\n\n", heading(level)));
+    match declared {
+        Some(Some(notify)) => content.push_str(&format!(
+            "- Replicated, notifies with {}\n",
+            member_reference(owner, notify)
+        )),
+        Some(None) => content.push_str("- Replicated\n"),
+        None => {}
+    }
+    if let Some(registered) = registered {
+        let mut line = "- Registered in `GetLifetimeReplicatedProps`".to_owned();
+        if let Some(condition) = &registered.condition {
+            line.push_str(&format!(" with `{}` condition", condition));
+            if let Some(description) = registered.condition_description() {
+                line.push_str(&format!(" ({})", description));
+            }
         }
+        if registered.custom_parameters {
+            line.push_str(" with custom parameters");
+        }
+        if registered.notify.as_deref() == Some("REPNOTIFY_Always") {
+            line.push_str(", notifies even when received value is unchanged");
+        }
+        content.push_str(&line);
+        content.push('\n');
     }
+    content.push_str("\n\n");
+}
 
-    // return value
-    bake_delegate_return_type(&item.return_type, &item.doc_comments, content);
+fn bake_property_access(item: &Property, owner: &StructClass, level: usize, content: &mut String) {
+    let accessors = item.accessors();
+    if !accessors.is_empty() {
+        content.push_str(&format!("---\n\n{} **Access**\n\n", heading(level)));
+        for (label, method) in accessors {
+            content.push_str(&format!(
+                "- **{}**: {}\n",
+                label,
+                member_reference(owner, &method)
+            ));
+        }
+        content.push_str("\n\n");
+    }
+}
 
-    content.push_str("\n\n");
+fn bake_delegate(
+    item: &Delegate,
+    document: &Document,
+    templates: &Templates,
+    level: usize,
+    content: &mut String,
+) {
+    let mut context = symbol_context(item, level);
+    context.insert(
+        "signature",
+        &fragment(|content| {
+            bake_signature(
+                &format!(
+                    "// Delegate type\n{}\n\n// Compatible function signature\n{}\n",
+                    item.signature(),
+                    item.callback_signature()
+                ),
+                &kind_class("delegate", item.specifiers.is_some()),
+                content,
+            )
+        }),
+    );
+    context.insert(
+        "raw_header",
+        &fragment(|content| bake_raw_header(&item.source, content)),
+    );
+    context.insert(
+        "specifiers",
+        &fragment(|content| {
+            if let Some(specifiers) = &item.specifiers {
+                bake_specifiers(specifiers, "delegate", level + 2, content);
+            }
+        }),
+    );
+    context.insert(
+        "badges",
+        &fragment(|content| {
+            bake_docs_badge(item.informal_docs, item.undocumented, content);
+            bake_stability_badge(item.stability, content);
+        }),
+    );
+    context.insert(
+        "aliases",
+        &fragment(|content| bake_aliases(&item.aliases, &item.renamed_in, content)),
+    );
+    context.insert(
+        "owners",
+        &fragment(|content| bake_owners(&item.owners, content)),
+    );
+    context.insert(
+        "comments",
+        &fragment(|content| bake_delegate_comments(&item.doc_comments, content)),
+    );
+    context.insert(
+        "arguments",
+        &fragment(|content| {
+            let params = param_blocks(&item.doc_comments);
+            for arg in &item.arguments {
+                bake_delegate_argument(arg, document, &params, level + 2, content);
+            }
+        }),
+    );
+    context.insert(
+        "return_type",
+        &fragment(|content| {
+            bake_delegate_return_type(
+                &item.return_type,
+                document,
+                &item.doc_comments,
+                level + 1,
+                content,
+            )
+        }),
+    );
+    content.push_str(&templates.render("delegate", &context));
 }
 
 fn bake_delegate_comments(doc_comments: &Option<String>, content: &mut String) {
     if let Some(comments) = doc_comments {
-        let re = Regex::new(r"(?ms).*<summary>(.*)</summary>.*").unwrap();
-        if let Some(caps) = re.captures(comments) {
+        if let Some(caps) = SUMMARY_TAG.captures(comments) {
             if caps.len() > 1 {
                 content.push_str(format!("<summary>\n\n{}</summary>", &caps[1]).as_str());
             }
         } else {
-            content.push_str(&comments);
+            content.push_str(comments);
         }
     }
 }
 
-fn bake_delegate_argument(item: &Argument, fun_comments: &Option<String>, content: &mut String) {
+/// `<param name="Name">description</param>` block of doc comments.
+static PARAM_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?ms)<param name="([^"]*)">(.*?)</param>"#).unwrap());
+
+/// Descriptions of `<param>` blocks of function or delegate doc comments, mapped by names of
+/// arguments they describe. First block of every argument wins.
+fn param_blocks(doc_comments: &Option<String>) -> HashMap<&str, &str> {
+    let mut result = HashMap::new();
+    for captures in doc_comments
+        .iter()
+        .flat_map(|comments| PARAM_BLOCK.captures_iter(comments))
+    {
+        let (Some(name), Some(description)) = (captures.get(1), captures.get(2)) else {
+            continue;
+        };
+        result.entry(name.as_str()).or_insert(description.as_str());
+    }
+    result
+}
+
+fn bake_delegate_argument(
+    item: &Argument,
+    document: &Document,
+    params: &HashMap<&str, &str>,
+    level: usize,
+    content: &mut String,
+) {
     if let Some(name) = &item.name {
-        content.push_str(&format!("* ## __`{}`__\n\n", name));
+        content.push_str(&format!("* {} __`{}`__\n\n", heading(level), name));
     } else {
-        let re = Regex::new(r"(?ms)/\*(.*)\*/").unwrap();
-        if let Some(caps) = re.captures(&item.signature()) {
+        if let Some(caps) = SIGNATURE_COMMENT.captures(&item.signature()) {
             if caps.len() > 1 {
-                content.push_str(format!("* ## __`{}`__\n\n", &caps[1]).as_str());
+                content.push_str(&format!("* {} __`{}`__\n\n", heading(level), &caps[1]));
             }
         }
         else {
@@ -575,19 +3309,16 @@ fn bake_delegate_argument(item: &Argument, fun_comments: &Option<String>, conten
     }
     let indented = indent(4, &{
         let mut content = String::default();
-        content.push_str(&format!("```cpp\n{}\n```\n\n", item.signature()));
+        bake_signature(&item.signature(), "ue-argument", &mut content);
+        bake_type_description(&item.value_type, document, &mut content);
+        if let Some(unit) = &item.unit {
+            content.push_str(&format!("**Units:** {}\n\n", unit));
+        }
         content.push_str(&item.doc_comments.to_owned().unwrap_or_default());
 
-        if let Some(comments) = fun_comments {
-            if let Some(name) = &item.name {
-                let re = Regex::new(format!("(?ms)<param name=\"{}\">(.*?)</param>", &name).as_str()).unwrap();
-                if let Some(caps) = re.captures(comments) {
-                    if caps.len() > 1 {
-                        content.push_str("\n\n");
-                        content.push_str(&caps[1]);
-                    }
-                }
-            }
+        if let Some(param) = item.name.as_deref().and_then(|name| params.get(name)) {
+            content.push_str("\n\n");
+            content.push_str(param);
         }
 
         content.push_str("\n\n");
@@ -597,95 +3328,281 @@ fn bake_delegate_argument(item: &Argument, fun_comments: &Option<String>, conten
     content.push_str("\n\n");
 }
 
-fn bake_delegate_return_type(return_type: &Option<String>, doc_comments: &Option<String>, content: &mut String) {
-    if let Some(r) = return_type {
-        if r != "void" {
-            content.push_str("---\n\n# **Returns**\n\n");
-            content.push_str("*\n");
-            let indented = indent(4, &{
-                let mut content = String::default();
+fn bake_delegate_return_type(
+    return_type: &Option<String>,
+    document: &Document,
+    doc_comments: &Option<String>,
+    level: usize,
+    content: &mut String,
+) {
+    if let Some(r) = return_type
+        && r != "void"
+    {
+        content.push_str(&format!("---\n\n{} **Returns**\n\n", heading(level)));
+        content.push_str("*\n");
+        let indented = indent(4, &{
+            let mut content = String::default();
 
-                content.push_str(&format!("```cpp\n{}\n```\n\n", r));
+            bake_signature(r, "ue-return-type", &mut content);
+            bake_type_description(r, document, &mut content);
 
-                if let Some(comments) = doc_comments {
-                    let re = Regex::new(r"<returns>(.*)</returns>").unwrap();
-                    if let Some(caps) = re.captures(comments) {
-                        if caps.len() > 1 {
-                            content.push_str(&caps[1]);
-                        }
-                    }
-                }
-                content.push_str("\n\n");
-                content
-            });
-            content.push_str(&indented);
+            if let Some(comments) = doc_comments
+                && let Some(caps) = RETURNS_TAG.captures(comments)
+                && caps.len() > 1
+            {
+                content.push_str(&caps[1]);
+            }
             content.push_str("\n\n");
-        }
+            content
+        });
+        content.push_str(&indented);
+        content.push_str("\n\n");
     }
 }
 
-fn bake_function(item: &Function, content: &mut String, member: bool) {
-    let level = if member {
-        content.push_str(&format!("* # __`{}`__\n\n", item.name));
-        4
+fn bake_function(
+    item: &Function,
+    document: &Document,
+    mdbook: &BackendMdBook,
+    templates: &Templates,
+    level: usize,
+    content: &mut String,
+    owner: Option<&StructClass>,
+) {
+    let member = owner.is_some();
+    let mut context = symbol_context(item, level);
+    context.insert("member", &member);
+    context.insert("indentation", &if member { 4 } else { 0 });
+    let anchor = if member && item.is_operator() {
+        format!("<a id=\"{}\"></a>", method_anchor(&item.name))
     } else {
-        content.push_str(&format!("# **Function: `{}`**\n\n", item.name));
-        0
+        String::default()
     };
-    let indented = indent(level, &{
-        let mut content = String::default();
-        content.push_str(&format!("```cpp\n//  {} : {}\n\n{}\n```\n\n", item.filename, item.fileline, item.signature()));
-        if member {
-            content.push_str("<details>\n\n");
-        }
-        if let Some(specifiers) = &item.specifiers {
-            content.push_str("---\n\n");
-            bake_specifiers(specifiers, &mut content);
-        }
-
-        bake_function_comments(&item.doc_comments, &mut content);
-
-        content.push_str("\n\n");
-        if !item.arguments.is_empty() {
-            content.push_str("---\n\n# **Arguments**\n\n");
+    context.insert("anchor", &anchor);
+    context.insert(
+        "signature",
+        &fragment(|content| {
+            let signature = item.wrapped_signature(mdbook.signature_width);
+            bake_located_signature(
+                &signature,
+                (&item.filename, item.header_path.as_deref(), item.fileline),
+                &kind_class("function", item.specifiers.is_some()),
+                mdbook,
+                content,
+            );
+            // wrapped signatures keep single-line version around for copying into code
+            let single_line = item.single_line_signature();
+            if mdbook.signature_width.is_some() && signature != single_line {
+                content.push_str(&format!(
+                    "<details>\n<summary>Single-line signature</summary>\n\n```cpp\n{}\n```\n\n</details>\n\n",
+                    single_line
+                ));
+            }
+        }),
+    );
+    context.insert(
+        "raw_header",
+        &fragment(|content| bake_raw_header(&item.source, content)),
+    );
+    context.insert(
+        "qualifiers",
+        &fragment(|content| bake_badges(&item.badges(), content)),
+    );
+    context.insert(
+        "implementation",
+        &fragment(|content| {
+            if item.has_implementation {
+                content.push_str(&format!(
+                    "> Implemented in C++ by overriding `{}_Implementation`.\n\n",
+                    item.name
+                ));
+            }
+        }),
+    );
+    context.insert(
+        "specifiers",
+        &fragment(|content| {
+            if let Some(specifiers) = &item.specifiers {
+                bake_specifiers(specifiers, "function", level + 2, content);
+            }
+        }),
+    );
+    context.insert(
+        "badges",
+        &fragment(|content| {
+            bake_docs_badge(item.informal_docs, item.undocumented, content);
+            bake_stability_badge(item.stability, content);
+            if !member {
+                bake_reflection_badge(item.specifiers.is_some(), content);
+            }
+        }),
+    );
+    context.insert(
+        "aliases",
+        &fragment(|content| bake_aliases(&item.aliases, &item.renamed_in, content)),
+    );
+    context.insert(
+        "owners",
+        &fragment(|content| bake_owners(&item.owners, content)),
+    );
+    context.insert(
+        "comments",
+        &fragment(|content| bake_function_comments(&item.doc_comments, content)),
+    );
+    context.insert(
+        "arguments",
+        &fragment(|content| {
+            let params = param_blocks(&item.doc_comments);
             for argument in &item.arguments {
-                bake_function_argument(argument, &item.doc_comments, &mut content);
+                bake_function_argument(argument, document, &params, level + 2, content);
             }
-            content.push_str("\n\n");
-        }
+        }),
+    );
+    context.insert(
+        "return_type",
+        &fragment(|content| {
+            bake_function_return_type(
+                &item.return_type,
+                document,
+                &item.doc_comments,
+                level + 1,
+                content,
+            )
+        }),
+    );
+    context.insert(
+        "failure_modes",
+        &fragment(|content| {
+            bake_function_failure_modes(&item.failure_modes, document, level + 1, content)
+        }),
+    );
+    context.insert(
+        "library_example",
+        &fragment(|content| {
+            if let Some(owner) = owner {
+                bake_function_library_example(item, owner, document, level + 1, content);
+            }
+        }),
+    );
+    context.insert(
+        "access",
+        &fragment(|content| {
+            if let Some(owner) = owner {
+                bake_function_access(item, owner, level + 1, content);
+            }
+        }),
+    );
+    content.push_str(&templates.render("function", &context));
+}
 
-        bake_function_return_type(&item.return_type, &item.doc_comments, &mut content);
+/// Usage example of static Blueprint Function Library functions, as scripters read these the
+/// most.
+fn bake_function_library_example(
+    item: &Function,
+    owner: &StructClass,
+    document: &Document,
+    level: usize,
+    content: &mut String,
+) {
+    if !item.is_static
+        || !item.is_blueprint_exposed()
+        || !document.is_blueprint_function_library(owner)
+    {
+        return;
+    }
+    let arguments = item
+        .arguments
+        .iter()
+        .enumerate()
+        .map(|(index, argument)| {
+            let name = argument
+                .name
+                .to_owned()
+                .unwrap_or_else(|| format!("Arg{}", index));
+            if argument.passing() == ArgumentPassing::RvalueReference {
+                format!("MoveTemp({})", name)
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let call = format!("{}::{}({});", owner.name, item.name, arguments);
+    let call = match item.return_type.as_deref() {
+        Some(return_type) if return_type != "void" => format!("auto Result = {}", call),
+        _ => call,
+    };
+    content.push_str(&format!(
+        "---\n\n{} **Example**\n\n```cpp\n{}\n```\n\n",
+        heading(level),
+        call
+    ));
+    if let Some(category) = item.category() {
+        content.push_str(&format!(
+            "**Blueprint node category:** _{}_\n\n",
+            category_path(category)
+        ));
+    }
+}
 
-        if member {
-            content.push_str("</details>\n\n");
+fn bake_function_access(item: &Function, owner: &StructClass, level: usize, content: &mut String) {
+    let bindings = owner
+        .properties
+        .iter()
+        .flat_map(|property| {
+            property
+                .accessors()
+                .into_iter()
+                .filter(|(_, method)| method == &item.name)
+                .map(move |(label, _)| (label, &property.name))
+        })
+        .collect::<Vec<_>>();
+    if !bindings.is_empty() {
+        content.push_str(&format!("---\n\n{} **Access**\n\n", heading(level)));
+        for (label, property) in bindings {
+            content.push_str(&format!(
+                "- **{}** of {}\n",
+                label,
+                member_reference(owner, property)
+            ));
         }
-        content
-    });
-    content.push_str(&indented);
-    content.push_str("\n\n");
+        content.push_str("\n\n");
+    }
 }
 
-fn bake_function_argument(item: &Argument, fun_comments: &Option<String>, content: &mut String) {
+fn bake_function_argument(
+    item: &Argument,
+    document: &Document,
+    params: &HashMap<&str, &str>,
+    level: usize,
+    content: &mut String,
+) {
     if let Some(name) = &item.name {
-        content.push_str(&format!("* ## __`{}`__\n\n", name));
+        content.push_str(&format!("* {} __`{}`__\n\n", heading(level), name));
     } else {
         content.push_str("* _Unnamed_\n\n");
     }
     let indented = indent(4, &{
         let mut content = String::default();
-        content.push_str(&format!("```cpp\n{}\n```\n\n", item.signature()));
+        bake_signature(&item.signature(), "ue-argument", &mut content);
+        bake_type_description(&item.value_type, document, &mut content);
+        if let Some(unit) = &item.unit {
+            content.push_str(&format!("**Units:** {}\n\n", unit));
+        }
+        let mut badges = vec![];
+        let direction = item.direction();
+        if direction != ArgumentDirection::In || item.direction.is_some() {
+            badges.push(direction.label());
+        }
+        if item.passing() != ArgumentPassing::Value {
+            badges.push(item.passing().description());
+        }
+        bake_badges(&badges, &mut content);
         content.push_str(&item.doc_comments.to_owned().unwrap_or_default());
 
-        if let Some(comments) = fun_comments {
-            if let Some(name) = &item.name {
-                let re = Regex::new(format!(r#"(?ms)<param name=\"{}\">(.*?)</param>"#, &name).as_str()).unwrap();
-                if let Some(caps) = re.captures(comments) {
-                    if caps.len() > 1 {
-                        content.push_str("\n\n");
-                        content.push_str(&caps[1]);
-                    }
-                }
-            }
+        if let Some(param) = item.name.as_deref().and_then(|name| params.get(name)) {
+            content.push_str("\n\n");
+            content.push_str(param);
         }
 
         content.push_str("\n\n");
@@ -695,46 +3612,223 @@ fn bake_function_argument(item: &Argument, fun_comments: &Option<String>, conten
     content.push_str("\n\n");
 }
 
-fn bake_function_return_type(return_type: &Option<String>, doc_comments: &Option<String>, content: &mut String) {
-    if let Some(r) = return_type {
-        if r != "void" {
-            content.push_str("---\n\n# **Returns**\n\n");
-            content.push_str("*\n");
-            let indented = indent(4, &{
-                let mut content = String::default();
+fn bake_function_return_type(
+    return_type: &Option<String>,
+    document: &Document,
+    doc_comments: &Option<String>,
+    level: usize,
+    content: &mut String,
+) {
+    if let Some(r) = return_type
+        && r != "void"
+    {
+        content.push_str(&format!("---\n\n{} **Returns**\n\n", heading(level)));
+        content.push_str("*\n");
+        let indented = indent(4, &{
+            let mut content = String::default();
 
-                content.push_str(&format!("```cpp\n{}\n```\n\n", r));
+            bake_signature(r, "ue-return-type", &mut content);
+            bake_type_description(r, document, &mut content);
 
-                if let Some(comments) = doc_comments {
-                    let re = Regex::new(r"<returns>(.*)</returns>").unwrap();
-                    if let Some(caps) = re.captures(comments) {
-                        if caps.len() > 1 {
-                            content.push_str(&caps[1]);
-                        }
-                    }
-                }
-                content.push_str("\n\n");
-                content
-            });
-            content.push_str(&indented);
+            if let Some(comments) = doc_comments
+                && let Some(caps) = RETURNS_TAG.captures(comments)
+                && caps.len() > 1
+            {
+                content.push_str(&caps[1]);
+            }
             content.push_str("\n\n");
+            content
+        });
+        content.push_str(&indented);
+        content.push_str("\n\n");
+    }
+}
+
+fn bake_function_failure_modes(
+    failure_modes: &[FailureMode],
+    document: &Document,
+    level: usize,
+    content: &mut String,
+) {
+    if failure_modes.is_empty() {
+        return;
+    }
+    content.push_str(&format!("---\n\n{} **Failure modes**\n\n", heading(level)));
+    for item in failure_modes {
+        match &item.exception {
+            Some(exception) => content.push_str(&format!(
+                "- {} — {}\n",
+                type_reference(exception, document),
+                item.description
+            )),
+            None => content.push_str(&format!("- {}\n", item.description)),
         }
     }
+    content.push_str("\n\n");
 }
 
 fn bake_function_comments(doc_comments: &Option<String>, content: &mut String) {
     if let Some(comments) = doc_comments {
-        let re = Regex::new(r"(?ms).*<summary>(.*)</summary>.*").unwrap();
-        if let Some(caps) = re.captures(comments) {
+        if let Some(caps) = SUMMARY_TAG.captures(comments) {
             if caps.len() > 1 {
                 content.push_str(format!("<summary>\n\n{}</summary>", &caps[1]).as_str());
             }
         } else {
-            content.push_str(&comments);
+            content.push_str(comments);
         }
     }
 }
 
+fn bake_docs_badge(informal: bool, undocumented: bool, content: &mut String) {
+    if informal {
+        bake_badges(&["Informal documentation"], content);
+    } else if undocumented {
+        bake_badges(&["Undocumented"], content);
+    }
+}
+
+fn bake_stability_badge(stability: Option<Stability>, content: &mut String) {
+    if let Some(stability) = stability {
+        content.push_str(&format!(
+            "<span class=\"ue-badge ue-{}\">**_{}_**</span>\n\n",
+            stability.label().to_lowercase(),
+            stability.label()
+        ));
+    }
+}
+
+/// Flags plain C++ symbol, declared without reflection macro, so it is not mistaken for one
+/// that Blueprints and reflection system know about.
+fn bake_reflection_badge(reflected: bool, content: &mut String) {
+    if !reflected {
+        content.push_str("<span class=\"ue-badge ue-plain\">**_Plain C++_**</span>\n\n");
+    }
+}
+
+fn bake_owners(owners: &[String], content: &mut String) {
+    if !owners.is_empty() {
+        content.push_str(&format!("*Owned by:* {}\n\n", owners.join(", ")));
+    }
+}
+
+/// Lists former names of symbol, so they also lead to its page through search.
+fn bake_aliases(aliases: &[String], renamed_in: &BTreeMap<String, String>, content: &mut String) {
+    if !aliases.is_empty() {
+        let names = aliases
+            .iter()
+            .map(|alias| match renamed_in.get(alias) {
+                Some(version) => format!("`{}` (renamed in {})", alias, version),
+                None => format!("`{}`", alias),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        content.push_str(&format!("*Formerly known as:* {}\n\n", names));
+    }
+}
+
+/// "Improve this doc" link to source file of page, made from `edit_url` template.
+fn bake_edit_link(path: Option<&str>, line: usize, mdbook: &BackendMdBook, content: &mut String) {
+    if let (Some(template), Some(path)) = (&mdbook.edit_url, path) {
+        bake_edit_url(&source_url(template, path, line), content);
+    }
+}
+
+/// "Improve this doc" link to Markdown source of handbook page, made from `page_edit_url`
+/// template. Book pages usually live in other input directory than headers, so they can not
+/// share `edit_url` template.
+fn bake_page_edit_link(path: &str, mdbook: &BackendMdBook, content: &mut String) {
+    if let Some(template) = &mdbook.page_edit_url {
+        bake_edit_url(&source_url(template, path, 1), content);
+    }
+}
+
+fn bake_edit_url(url: &str, content: &mut String) {
+    content.push_str(&format!(
+        "\n\n<div class=\"ue-edit-link\">\n\n[Improve this doc]({})\n\n</div>\n\n",
+        url
+    ));
+}
+
+/// Fills `edit_url` template with path of source file and line in it.
+fn source_url(template: &str, path: &str, line: usize) -> String {
+    template
+        .replace("{path}", &path.replace(' ', "%20"))
+        .replace("{line}", &line.to_string())
+}
+
+fn bake_raw_header(source: &Option<String>, content: &mut String) {
+    if let Some(source) = source {
+        content.push_str(&format!(
+            "<details>\n<summary>Raw header</summary>\n\n```cpp\n{}\n```\n\n</details>\n\n",
+            source
+        ));
+    }
+}
+
+fn bake_badges(badges: &[&str], content: &mut String) {
+    if !badges.is_empty() {
+        let badges = badges
+            .iter()
+            .map(|badge| format!("<span class=\"ue-badge\">**_{}_**</span>", badge))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        content.push_str(&badges);
+        content.push_str("\n\n");
+    }
+}
+
+fn bake_type_description(value_type: &str, document: &Document, content: &mut String) {
+    let type_ref = TypeRef::parse(value_type);
+    if type_ref.is_wrapper() {
+        let description = type_ref.describe(&|name| type_reference(name, document));
+        content.push_str(&format!("**Type:** _{}_\n\n", description));
+    }
+}
+
+fn type_reference(name: &str, document: &Document) -> String {
+    let element = if document.classes.iter().any(|item| item.name == name) {
+        "class"
+    } else if document.structs.iter().any(|item| item.name == name) {
+        "struct"
+    } else if document.enums.iter().any(|item| item.name == name) {
+        "enum"
+    } else if document.delegates.iter().any(|item| item.name == name) {
+        "delegate"
+    } else {
+        return format!("`{}`", name);
+    };
+    format!("[`{}: {}`]()", element, name)
+}
+
+fn member_reference(owner: &StructClass, member: &str) -> String {
+    format!(
+        "[`{}: {}::{}`]()",
+        owner.mode.signature(),
+        owner.name,
+        member
+    )
+}
+
+/// Parts of nested category, with whitespace collapsed, e.g. `Audio| Music` gives `Audio` and
+/// `Music`.
+fn category_parts(category: &str) -> Vec<String> {
+    category
+        .split('|')
+        .map(|part| part.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Readable form of nested Blueprint category, e.g. `Game|Items` becomes `Game › Items`.
+fn category_path(category: &str) -> String {
+    category_parts(category).join(" › ")
+}
+
+/// Markdown heading marker of given level, clamped to levels supported by Markdown.
+fn heading(level: usize) -> String {
+    "#".repeat(level.clamp(1, 6))
+}
+
 fn indent(level: usize, content: &str) -> String {
     if level > 0 {
         content
@@ -747,9 +3841,7 @@ fn indent(level: usize, content: &str) -> String {
     }
 }
 
-fn write_manifest(config: &Config) {
-    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
-
+fn manifest(mdbook: &BackendMdBook, document: &Document, diagnostics: &mut Diagnostics) -> String {
     let manifest = Book {
         book: BookInner {
             authors: mdbook.authors.to_owned(),
@@ -763,19 +3855,354 @@ fn write_manifest(config: &Config) {
                 default_theme: "ayu".to_owned(),
                 preferred_dark_theme: "ayu".to_owned(),
                 mathjax_support: true,
-                no_section_label: true,
-                site_url: mdbook.site_url.unwrap_or("/".to_string()),
+                no_section_label: !mdbook.section_numbers,
+                site_url: mdbook.site_url.clone().unwrap_or("/".to_string()),
                 fold: BookFold {
                     enable: false,
                     level: 0,
                 },
+                additional_js: if mdbook.permalinks {
+                    vec!["permalinks.js".to_owned()]
+                } else {
+                    vec![]
+                },
+                additional_css: {
+                    let mut result = vec![];
+                    if mdbook.permalinks {
+                        result.push("permalinks.css".to_owned());
+                    }
+                    if has_stability(document) {
+                        result.push("stability.css".to_owned());
+                    }
+                    result
+                },
+                redirect: alias_redirects(document, diagnostics),
             },
         }
     };
 
-    let content = toml::to_string(&manifest).expect("Could not serialize mdbook manifest!");
-    let path = config.output_dir.join("book.toml");
-    ensure_dir(&path);
-    write(&path, content)
-        .unwrap_or_else(|_| panic!("Could not write mdbook manifest file: {:?}", path));
+    toml::to_string(&manifest).expect("Could not serialize mdbook manifest!")
+}
+
+#[test]
+fn test_prune_stale_files() {
+    let output_dir = std::env::temp_dir().join("unreal-doc-test-prune-stale-files");
+    let _ = std::fs::remove_dir_all(&output_dir);
+    for path in ["src/a.md", "src/old/b.md", "src/custom.md"] {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(path, "").unwrap();
+    }
+    write(
+        output_dir.join(GENERATED_MANIFEST),
+        "src/a.md\nsrc/old/b.md\n../outside.md\n",
+    )
+    .unwrap();
+    let generated = BTreeSet::from(["src/a.md".to_owned()]);
+    prune_stale_files(&output_dir, &generated).unwrap();
+    assert!(output_dir.join("src/a.md").is_file());
+    assert!(output_dir.join("src/custom.md").is_file());
+    assert!(!output_dir.join("src/old").exists());
+    assert_eq!(
+        read_file(output_dir.join(GENERATED_MANIFEST)).unwrap(),
+        "src/a.md\n"
+    );
+    let _ = std::fs::remove_dir_all(&output_dir);
+}
+
+#[test]
+fn test_print_structure() {
+    let document = Document {
+        enums: vec![Enum {
+            name: "EState".to_owned(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mdbook = BackendMdBook {
+        print_structure: true,
+        ..Default::default()
+    };
+    let rendered = render_to_string(&document, &mdbook);
+    assert!(rendered.contains(
+        "# C++ API Reference\n\n- [Overview](reference.md)\n- [Enums](reference/enums.md)\n  - \
+        [EState](reference/enums/EState.md)\n"
+    ));
+}
+
+#[test]
+fn test_alias_redirects() {
+    let mut document = Document {
+        classes: vec![StructClass {
+            name: "UInventory".to_owned(),
+            doc_comments: Some("Items.\n<alias>UItemBag</alias>\n".to_owned()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    document.extract_aliases();
+    let missing = document.add_aliases(&BTreeMap::from([
+        ("UInventory".to_owned(), "UInventory".to_owned()),
+        ("UBackpack".to_owned(), "UInventory".to_owned()),
+        ("UStash".to_owned(), "UMissing".to_owned()),
+    ]));
+    assert_eq!(missing, vec!["UStash".to_owned()]);
+    assert_eq!(document.classes[0].doc_comments.as_deref(), Some("Items."));
+    assert_eq!(
+        alias_redirects(&document, &mut Default::default()),
+        BTreeMap::from([
+            (
+                "/reference/classes/UBackpack.html".to_owned(),
+                "UInventory.html".to_owned()
+            ),
+            (
+                "/reference/classes/UItemBag.html".to_owned(),
+                "UInventory.html".to_owned()
+            ),
+        ])
+    );
+
+    let class = |name: &str, doc_comments: &str| StructClass {
+        name: name.to_owned(),
+        doc_comments: Some(doc_comments.to_owned()),
+        ..Default::default()
+    };
+    let mut document = Document {
+        classes: vec![
+            class("UInventory", "<alias>UItemBag</alias>"),
+            class("UItemBag", "Bag of items."),
+        ],
+        ..Default::default()
+    };
+    document.extract_aliases();
+    let mut diagnostics = Diagnostics::default();
+    assert!(alias_redirects(&document, &mut diagnostics).is_empty());
+    assert_eq!(
+        diagnostics.items[0].to_string(),
+        "Warning in book.toml: Alias `UItemBag` of `UInventory` is not redirected, as it would replace existing page"
+    );
+}
+
+#[test]
+fn test_render_symbol() {
+    let document = Document {
+        classes: vec![StructClass {
+            mode: StructClassMode::Class,
+            name: "UMyActor".to_owned(),
+            doc_comments: Some("Holds [`FItem`].".to_owned()),
+            ..Default::default()
+        }],
+        structs: vec![StructClass {
+            name: "FItem".to_owned(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let rendered = render_symbol(&document, "UMyActor", &BackendMdBook::default()).unwrap();
+    assert!(rendered.contains("**Class: `UMyActor`**"));
+    assert!(rendered.contains("Holds [`FItem`]"));
+    assert!(render_symbol(&document, "UMissing", &BackendMdBook::default()).is_none());
+}
+
+#[test]
+fn test_location_links() {
+    let document = Document {
+        enums: vec![Enum {
+            name: "EMode".to_owned(),
+            filename: "Mode.h".to_owned(),
+            header_path: Some("Public/Mode.h".to_owned()),
+            fileline: 12,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut mdbook = BackendMdBook {
+        edit_url: Some("https://example.com/{path}#L{line}".to_owned()),
+        ..Default::default()
+    };
+    let rendered = render_symbol(&document, "EMode", &mdbook).unwrap();
+    assert!(rendered.contains("```cpp\n//  Mode.h : 12\n"));
+    mdbook.location_links = true;
+    let rendered = render_symbol(&document, "EMode", &mdbook).unwrap();
+    assert!(
+        rendered.contains("[`Mode.h : 12`](https://example.com/Public/Mode.h#L12)\n\n```cpp\n")
+    );
+    assert!(!rendered.contains("//  Mode.h"));
+
+    let mut document = document;
+    document
+        .book
+        .insert("index.txt".to_owned(), "intro.md".to_owned());
+    document
+        .book
+        .insert("intro.md".to_owned(), "# Intro\n".to_owned());
+    let rendered = render_to_string(&document, &mdbook);
+    assert!(rendered.contains("[Improve this doc](https://example.com/Public/Mode.h#L12)"));
+    assert!(!rendered.contains("example.com/intro.md"));
+    mdbook.page_edit_url = Some("https://example.com/book/{path}".to_owned());
+    let rendered = render_to_string(&document, &mdbook);
+    assert!(rendered.contains("[Improve this doc](https://example.com/book/intro.md)"));
+}
+
+#[test]
+fn test_code_reference_link() {
+    let document = Document {
+        structs: vec![StructClass {
+            name: "FVec".to_owned(),
+            methods: vec![Function {
+                name: "operator==".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        delegates: vec![Delegate {
+            name: "FOnHit".to_owned(),
+            arguments: vec![Argument {
+                name: Some("Damage".to_owned()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut diagnostics = Diagnostics::default();
+    let mut link = |element: &str, reference: &str, section: &str| {
+        code_reference_link(
+            element,
+            reference,
+            Some(section),
+            &document,
+            &BackendMdBook::default(),
+            "page.md",
+            &mut diagnostics,
+        )
+    };
+    assert_eq!(
+        link("method", "FVec", "operator=="),
+        "[**`FVec::operator==`**](/reference/structs/FVec.md#operator_eq)"
+    );
+    assert_eq!(
+        link("delegate", "FOnHit", "Damage"),
+        "[**`FOnHit::Damage`**](/reference/delegates/FOnHit.md#damage)"
+    );
+    assert_eq!(
+        link("delegate", "FOnHit", "Missing"),
+        "**`FOnHit::Missing`**"
+    );
+}
+
+#[test]
+fn test_inject_header_footer() {
+    let header = "# Note\n\nPreview build.\n\n## Details\n\n```\n# not heading\n```\n";
+    let footer = "\nFooter\n";
+    let content = "# Title\n\nText\n";
+    assert_eq!(
+        inject_header_footer(content, header, footer, HeaderPosition::BeforeTitle, None),
+        format!("{}{}{}\n", header, content, footer)
+    );
+    assert_eq!(
+        inject_header_footer(content, header, footer, HeaderPosition::AfterTitle, None),
+        "# Title\n\n## Note\n\nPreview build.\n\n### Details\n\n```\n# not heading\n```\n\nText\n\nFooter\n\n"
+    );
+    assert_eq!(
+        inject_header_footer(content, header, footer, HeaderPosition::BeforeFooter, None),
+        "# Title\n\nText\n\n## Note\n\nPreview build.\n\n### Details\n\n```\n# not heading\n```\n\nFooter\n\n"
+    );
+    let front_matter = FrontMatter {
+        header: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        inject_header_footer(
+            content,
+            header,
+            footer,
+            HeaderPosition::AfterTitle,
+            Some(&front_matter)
+        ),
+        "# Title\n\nText\n\nFooter\n\n"
+    );
+}
+
+#[test]
+fn test_mark_glossary_terms() {
+    let glossary = BTreeMap::from([
+        ("Actor".to_owned(), "Object placed in \"level\".".to_owned()),
+        ("Game Mode".to_owned(), "Rules of game.".to_owned()),
+        ("Game".to_owned(), "Whole project.".to_owned()),
+    ]);
+    let glossary = glossary_patterns(&glossary);
+    let content = "# Actor\n\nSpawn `Actor` in game mode of [actor](a.md).\n\n```cpp\nActor\n```\n\nActor and game.\n";
+    assert_eq!(
+        mark_glossary_terms(content, &glossary, GlossaryTerms::Link, "guides/"),
+        "# Actor\n\nSpawn `Actor` in [game mode](../glossary.md#game_mode) of [actor](a.md).\n\n```cpp\nActor\n```\n\n[Actor](../glossary.md#actor) and [game](../glossary.md#game).\n"
+    );
+    assert_eq!(
+        mark_glossary_terms("An actor.", &glossary, GlossaryTerms::Tooltip, ""),
+        "An <abbr title=\"Object placed in &quot;level&quot;.\">actor</abbr>."
+    );
+}
+
+#[test]
+fn test_page_stem() {
+    let long_name = format!("T{}", "LongTemplateName".repeat(5));
+    let class = |name: &str| StructClass {
+        name: name.to_owned(),
+        ..Default::default()
+    };
+    let document = Document {
+        classes: vec![class("UFoo"), class("UFOO"), class(&long_name)],
+        ..Default::default()
+    };
+    assert_eq!(page_stem(&document, "classes", "UFOO"), "UFOO");
+    let stem = page_stem(&document, "classes", "UFoo");
+    assert!(stem.starts_with("UFoo_") && stem.len() == 13);
+    let stem = page_stem(&document, "classes", &long_name);
+    assert!(stem.starts_with("TLongTemplateName") && stem.chars().count() == MAX_PAGE_STEM);
+    let rendered = render_to_string(&document, &BackendMdBook::default());
+    assert!(rendered.contains(&format!(
+        "[UFoo](reference/classes/{}.md)",
+        page_stem(&document, "classes", "UFoo")
+    )));
+}
+
+/// Bakes every fixture header from `resources/source/` through the same pipeline stages the
+/// command runs, configured by `resources/Golden.toml`, and compares result with its golden file
+/// from `resources/golden/`. Run with `UNREAL_DOC_BLESS=1` environment variable to regenerate
+/// golden files after intended output changes.
+#[test]
+fn test_golden_files() {
+    use crate::pipeline::{finish_document, load_config, parse_document, process_document};
+
+    let bless = std::env::var("UNREAL_DOC_BLESS").is_ok();
+    let (config, root) = load_config(Path::new("resources/Golden.toml"), None);
+    for entry in std::fs::read_dir("resources/source").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map(|ext| ext != "h").unwrap_or(true) {
+            continue;
+        }
+        let mut config = config.clone();
+        config.input_dirs = vec![path.to_owned()];
+        let mut document = parse_document(&config, &root, &mut Default::default());
+        process_document(&mut document, &config, &root);
+        finish_document(&mut document, &mut config);
+        let mdbook = config.backend_mdbook.clone().unwrap_or_default();
+        let rendered = render_to_string(&document, &mdbook);
+        let golden = Path::new("resources/golden")
+            .join(path.file_stem().unwrap())
+            .with_extension("md");
+        if bless {
+            ensure_dir(&golden);
+            write(&golden, rendered).unwrap();
+        } else {
+            let expected = read_file(&golden)
+                .unwrap_or_else(|_| panic!("Golden file not found: {:?}", golden));
+            assert!(
+                rendered == expected,
+                "Baked {:?} differs from golden file {:?}",
+                path,
+                golden
+            );
+        }
+    }
 }
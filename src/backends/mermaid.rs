@@ -0,0 +1,57 @@
+use crate::document::*;
+
+/// Mermaid class diagram source of inheritance around given struct or class: its documented
+/// parents chain and direct children. Returns `None` if there are no relations to show.
+pub fn inheritance_chart(document: &Document, item: &StructClass) -> Option<String> {
+    let parents = document.parent_chain(item);
+    let children = document.children(&item.name);
+    if parents.is_empty() && children.is_empty() {
+        return None;
+    }
+    let mut result = chart_header();
+    let mut child = item.name.as_str();
+    for parent in &parents {
+        result.push_str(&chart_edge(child, parent));
+        child = parent;
+    }
+    for child in children {
+        result.push_str(&chart_edge(&child.name, &item.name));
+    }
+    Some(result)
+}
+
+/// Mermaid class diagram source of all inheritance relations between structs and classes, along
+/// with undocumented types they derive from. Returns `None` if no type derives from another.
+pub fn hierarchy_chart(document: &Document) -> Option<String> {
+    let edges = document.inheritance_edges();
+    if edges.is_empty() {
+        return None;
+    }
+    let mut result = chart_header();
+    for (child, parent) in edges {
+        result.push_str(&chart_edge(child, parent));
+    }
+    Some(result)
+}
+
+fn chart_header() -> String {
+    "classDiagram\n    direction BT\n".to_owned()
+}
+
+fn chart_edge(child: &str, parent: &str) -> String {
+    format!("    {} <|-- {}\n", chart_name(parent), chart_name(child))
+}
+
+/// Mermaid class names are plain identifiers, so anything else (e.g. namespace separators)
+/// becomes underscore.
+fn chart_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -1,2 +1,153 @@
+pub mod confluence;
+pub mod docusaurus;
+pub mod doxygen;
+pub mod graphviz;
+pub mod history;
+pub mod html;
 pub mod json;
+pub mod llms_txt;
+pub mod markdown;
 pub mod mdbook;
+pub mod mermaid;
+pub mod pdf;
+pub mod templates;
+
+use crate::{config::Config, document::Document, report::BakeStats};
+use std::{collections::BTreeMap, error::Error, path::Path};
+
+/// Bakes parsed document into documentation output. Implemented by built-in backends and by
+/// tools using this crate as library, that register their own backends in [`BackendRegistry`].
+pub trait Backend {
+    /// Bakes document into `config.output_dir`, or only prints changes to existing output when
+    /// `config.diff_output` is set. Relative paths in config are resolved against `root`.
+    fn bake(
+        &self,
+        document: &Document,
+        config: &Config,
+        root: &Path,
+    ) -> Result<BakeStats, Box<dyn Error>>;
+}
+
+/// Built-in backend baking with one of `bake_*` functions.
+struct BuiltinBackend(BakeFn);
+
+/// `bake_*` function of built-in backend, also told whether to only print changes to output.
+type BakeFn = fn(&Document, &Config, &Path, bool) -> Result<BakeStats, Box<dyn Error>>;
+
+impl Backend for BuiltinBackend {
+    fn bake(
+        &self,
+        document: &Document,
+        config: &Config,
+        root: &Path,
+    ) -> Result<BakeStats, Box<dyn Error>> {
+        (self.0)(document, config, root, config.diff_output)
+    }
+}
+
+/// Backends mapped by names that configs select them with. Starts with all built-in backends.
+pub struct BackendRegistry {
+    backends: BTreeMap<String, Box<dyn Backend>>,
+}
+
+impl Default for BackendRegistry {
+    fn default() -> Self {
+        let builtins = [
+            ("Json", BuiltinBackend(json::bake_json)),
+            ("MdBook", BuiltinBackend(mdbook::bake_mdbook)),
+            ("Html", BuiltinBackend(html::bake_html)),
+            ("Docusaurus", BuiltinBackend(docusaurus::bake_docusaurus)),
+            ("Doxygen", BuiltinBackend(doxygen::bake_doxygen)),
+            ("Markdown", BuiltinBackend(markdown::bake_markdown)),
+            ("Confluence", BuiltinBackend(confluence::bake_confluence)),
+            ("Pdf", BuiltinBackend(pdf::bake_pdf)),
+            ("LlmsTxt", BuiltinBackend(llms_txt::bake_llms_txt)),
+        ];
+        let mut result = Self {
+            backends: Default::default(),
+        };
+        for (name, backend) in builtins {
+            result.register(name, backend);
+        }
+        result
+    }
+}
+
+impl BackendRegistry {
+    /// Registers backend under given name, replacing backend registered under it before (so
+    /// built-in backends can be replaced too).
+    pub fn register(&mut self, name: impl ToString, backend: impl Backend + 'static) {
+        self.backends.insert(name.to_string(), Box::new(backend));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Backend> {
+        self.backends.get(name).map(|backend| backend.as_ref())
+    }
+
+    /// Names of registered backends, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.backends.keys().map(|name| name.as_str())
+    }
+}
+
+#[test]
+fn test_backend_registry() {
+    struct Counter;
+
+    impl Backend for Counter {
+        fn bake(
+            &self,
+            document: &Document,
+            _: &Config,
+            _: &Path,
+        ) -> Result<BakeStats, Box<dyn Error>> {
+            Ok(BakeStats {
+                pages: document.classes.len(),
+                ..Default::default()
+            })
+        }
+    }
+
+    let config = toml::from_str::<Config>(
+        "input_dirs = []\noutput_dir = \"docs\"\nbackends = [\"MdBook\", \"Counter\"]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.backends,
+        vec![
+            crate::config::Backend::MdBook,
+            crate::config::Backend::Custom("Counter".to_owned())
+        ]
+    );
+
+    let mut registry = BackendRegistry::default();
+    assert!(registry.get("Counter").is_none());
+    registry.register("Counter", Counter);
+    let stats = registry
+        .get(config.backends[1].name())
+        .unwrap()
+        .bake(&Document::default(), &config, Path::new("."))
+        .unwrap();
+    assert_eq!(stats.pages, 0);
+    assert!(registry.names().any(|name| name == "MdBook"));
+}
+
+#[test]
+fn test_builtin_backend_errors() {
+    let root = std::env::temp_dir().join(format!("unreal-doc-errors-{}", std::process::id()));
+    let output_dir = root.join("docs");
+    let mut config = toml::from_str::<Config>(&format!(
+        "input_dirs = []\noutput_dir = {:?}\nbackends = [\"Html\", \"Markdown\"]\n",
+        output_dir
+    ))
+    .unwrap();
+    config.hooks.post_bake = vec!["exit 1".to_owned()];
+    let registry = BackendRegistry::default();
+    for name in ["Html", "Markdown"] {
+        let backend = registry.get(name).unwrap();
+        let result = backend.bake(&Document::default(), &config, &root);
+        assert!(result.is_err(), "{} backend did not fail", name);
+    }
+    assert!(!output_dir.exists());
+    let _ = std::fs::remove_dir_all(&root);
+}
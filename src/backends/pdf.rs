@@ -0,0 +1,245 @@
+use crate::{
+    backends::{
+        docusaurus::summary_entries,
+        history::date_days_ago,
+        html::escape_html,
+        markdown::{book_order, page_anchor, relink_content},
+        mdbook::{bake_book_files, page_count, prune_stale_files},
+    },
+    config::Config,
+    diagnostics::Diagnostics,
+    diff::preview_changes,
+    document::{Document, markdown_options},
+    ensure_dir,
+    hooks::run_hooks,
+    report::BakeStats,
+    staging::Staging,
+};
+use fs_extra::{copy_items, dir::CopyOptions};
+use pulldown_cmark::{Parser, html::push_html};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs::write,
+    path::Path,
+    process::Command,
+};
+
+/// Name of print-ready HTML document in output directory, that PDF gets made of.
+const PRINT_FILE: &str = "documentation.html";
+
+/// Name of baked PDF document in output directory.
+const PDF_FILE: &str = "documentation.pdf";
+
+/// Bakes the same pages as MD Book backend (configured with the same `backend_mdbook` options)
+/// into single PDF document with cover page, table of contents and pages in book index order.
+/// Pages are put into print-ready HTML document first, which gets converted into PDF by external
+/// command (`weasyprint` by default).
+pub fn bake_pdf(
+    document: &Document,
+    config: &Config,
+    root: &Path,
+    diff_output: bool,
+) -> Result<BakeStats, Box<dyn Error>> {
+    let mdbook = config.backend_mdbook.as_ref().cloned().unwrap_or_default();
+    let options = config.backend_pdf.as_ref().cloned().unwrap_or_default();
+
+    // cleanup means staging starts empty instead of with copy of current output
+    let staging = (!diff_output)
+        .then(|| Staging::begin(&config.output_dir, !mdbook.cleanup))
+        .transpose()?;
+    let output_dir = staging
+        .as_ref()
+        .map(|staging| staging.path())
+        .unwrap_or(&config.output_dir);
+
+    let mut diagnostics = Diagnostics::default();
+    let (pages, diagrams) = bake_book_files(
+        document,
+        config,
+        root,
+        output_dir,
+        diff_output,
+        &mut diagnostics,
+    )?;
+    let stats = BakeStats {
+        pages: page_count(&pages),
+        diagnostics: diagnostics.items,
+    };
+    let site_url = mdbook.site_url.as_deref().unwrap_or("/");
+    let cover = Cover {
+        language: &mdbook.language,
+        title: &mdbook.title,
+        subtitle: options.subtitle.as_deref(),
+        date: &date_days_ago(0),
+    };
+    let files = BTreeMap::from([(
+        PRINT_FILE.to_owned(),
+        print_document(&pages, &cover, site_url),
+    )]);
+    if diff_output {
+        preview_changes(&config.output_dir, &files, None, "html");
+        return Ok(stats);
+    }
+    let generated = files
+        .keys()
+        .cloned()
+        .chain(diagrams.values().flatten().map(|(_, path)| path.to_owned()))
+        .chain(std::iter::once(PDF_FILE.to_owned()))
+        .collect::<BTreeSet<_>>();
+    for (path, content) in files {
+        let path = output_dir.join(path);
+        ensure_dir(&path);
+        write(&path, content)
+            .map_err(|error| format!("Could not write PDF file: {:?} ({})", path, error))?;
+    }
+    prune_stale_files(output_dir, &generated)?;
+
+    if let Some(assets) = mdbook.assets.as_ref() {
+        let from = root.join(assets);
+        let to = output_dir.join("assets");
+        ensure_dir(&to);
+        let mut options = CopyOptions::new();
+        options.overwrite = true;
+        options.copy_inside = true;
+        copy_items(&[from], &to, &options)
+            .map_err(|error| format!("Could not copy assets: {:?} ({})", assets, error))?;
+    }
+    render_pdf(&options.command, output_dir)?;
+    run_hooks(&config.hooks.post_bake, config, root, output_dir)?;
+    if let Some(staging) = staging {
+        staging.commit()?;
+    }
+    Ok(stats)
+}
+
+/// Content of document cover page.
+struct Cover<'a> {
+    language: &'a str,
+    title: &'a str,
+    subtitle: Option<&'a str>,
+    date: &'a str,
+}
+
+/// Print-ready HTML document made of book pages: cover page, table of contents (with page
+/// numbers filled in by PDF renderer) and pages in book index order, each one starting on new
+/// page.
+fn print_document(pages: &BTreeMap<String, String>, cover: &Cover, site_url: &str) -> String {
+    let summary = pages
+        .get("src/SUMMARY.md")
+        .map(|summary| summary.as_str())
+        .unwrap_or_default();
+    let mut result = format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>{}</title>\n<style>\n{}",
+        escape_html(cover.language),
+        escape_html(cover.title),
+        include_str!("pdf.css")
+    );
+    if let Some(stability) = pages.get("stability.css") {
+        result.push_str(stability);
+    }
+    result.push_str("</style>\n</head>\n<body>\n");
+    result.push_str(&format!(
+        "<section class=\"cover\">\n<h1>{}</h1>\n",
+        escape_html(cover.title)
+    ));
+    if let Some(subtitle) = cover.subtitle {
+        result.push_str(&format!(
+            "<p class=\"subtitle\">{}</p>\n",
+            escape_html(subtitle)
+        ));
+    }
+    result.push_str(&format!(
+        "<p class=\"date\">{}</p>\n</section>\n",
+        escape_html(cover.date)
+    ));
+
+    result.push_str("<nav class=\"toc\">\n<h1>Contents</h1>\n<ul>\n");
+    for (level, label, page) in summary_entries(summary) {
+        result.push_str(&format!(
+            "<li class=\"level-{}\"><a href=\"#{}\">{}</a></li>\n",
+            level,
+            page_anchor(&page),
+            escape_html(&label)
+        ));
+    }
+    result.push_str("</ul>\n</nav>\n");
+    for page in book_order(pages) {
+        let content = relink_content(&pages[&format!("src/{}.md", page)], &page, site_url);
+        result.push_str(&format!(
+            "<section class=\"page\" id=\"{}\">\n",
+            page_anchor(&page)
+        ));
+        push_html(&mut result, Parser::new_ext(&content, markdown_options()));
+        result.push_str("</section>\n");
+    }
+    result.push_str("</body>\n</html>\n");
+    result
+}
+
+/// Converts print-ready HTML document into PDF with given command, run in system shell.
+fn render_pdf(command: &str, output_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let command = command
+        .replace("{input}", PRINT_FILE)
+        .replace("{output}", PDF_FILE);
+    let mut process = if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(&command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(&command);
+        process
+    };
+    let status = process
+        .current_dir(output_dir)
+        .status()
+        .map_err(|error| format!("Could not run PDF command: {} ({})", command, error))?;
+    if !status.success() {
+        return Err(format!("PDF command failed with {}: {}", status, command).into());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_print_document() {
+    let pages = BTreeMap::from([
+        (
+            "src/SUMMARY.md".to_owned(),
+            "# Index\n\n- [Hello](hello.md)\n- [Reference](reference.md)\n  \
+            - [UFoo](reference/classes/UFoo.md)\n"
+                .to_owned(),
+        ),
+        (
+            "src/hello.md".to_owned(),
+            "# Hello\n\nSee [`UFoo`](/reference/classes/UFoo.md#methods).\n".to_owned(),
+        ),
+        ("src/reference.md".to_owned(), "# Reference\n".to_owned()),
+        (
+            "src/reference/classes/UFoo.md".to_owned(),
+            "# UFoo\n\n![Diagram](../../assets/diagrams/UFoo.svg)\n".to_owned(),
+        ),
+    ]);
+    let cover = Cover {
+        language: "en",
+        title: "Game & API",
+        subtitle: Some("Version 2.0"),
+        date: "2026-10-16",
+    };
+    let content = print_document(&pages, &cover, "/");
+    assert!(content.contains("<h1>Game &amp; API</h1>\n<p class=\"subtitle\">Version 2.0</p>"));
+    assert!(
+        content.contains("<li class=\"level-1\"><a href=\"#reference-classes-ufoo\">UFoo</a></li>")
+    );
+    assert!(content.contains("<a href=\"#reference-classes-ufoo\"><code>UFoo</code></a>"));
+    assert!(content.contains("<img src=\"assets/diagrams/UFoo.svg\" alt=\"Diagram\" />"));
+    assert!(
+        content
+            .find("<section class=\"page\" id=\"hello\">")
+            .unwrap()
+            < content
+                .find("<section class=\"page\" id=\"reference\">")
+                .unwrap()
+    );
+}
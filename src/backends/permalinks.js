@@ -0,0 +1,37 @@
+// Adds copy-link buttons next to member headings and anchors, so deep links to specific members
+// can be shared.
+(function () {
+    function copyLink(id, button) {
+        var url = location.href.split('#')[0] + '#' + id;
+        history.replaceState(null, '', '#' + id);
+        navigator.clipboard.writeText(url).then(function () {
+            button.classList.add('copied');
+            setTimeout(function () {
+                button.classList.remove('copied');
+            }, 1500);
+        });
+    }
+
+    function addButton(id, parent) {
+        var button = document.createElement('button');
+        button.className = 'permalink';
+        button.title = 'Copy link';
+        button.setAttribute('aria-label', 'Copy link');
+        button.textContent = '🔗';
+        button.addEventListener('click', function (event) {
+            event.preventDefault();
+            event.stopPropagation();
+            copyLink(id, button);
+        });
+        parent.appendChild(button);
+    }
+
+    document
+        .querySelectorAll('main h2[id], main h3[id], main h4[id], main h5[id], main h6[id]')
+        .forEach(function (heading) {
+            addButton(heading.id, heading);
+        });
+    document.querySelectorAll('main a[id]:not([href])').forEach(function (anchor) {
+        addButton(anchor.id, anchor.parentElement);
+    });
+})();
@@ -0,0 +1,56 @@
+// Searches page titles, tags and content of static site, listing best matches under search box.
+(function () {
+    var input = document.getElementById('search');
+    var results = document.getElementById('search-results');
+    if (!input || !results || !window.searchIndex) {
+        return;
+    }
+
+    function score(page, terms) {
+        var title = page.title.toLowerCase();
+        var text = page.text.toLowerCase();
+        var tags = (page.tags || []).join(' ').toLowerCase();
+        var result = 0;
+        for (var i = 0; i < terms.length; i++) {
+            if (title.indexOf(terms[i]) >= 0) {
+                result += 10;
+            } else if (tags.indexOf(terms[i]) >= 0) {
+                result += 5;
+            } else if (text.indexOf(terms[i]) >= 0) {
+                result += 1;
+            } else {
+                return 0;
+            }
+        }
+        return result;
+    }
+
+    input.addEventListener('input', function () {
+        var terms = input.value.toLowerCase().split(/\s+/).filter(function (term) {
+            return term.length > 0;
+        });
+        results.innerHTML = '';
+        if (terms.length === 0) {
+            return;
+        }
+        window.searchIndex
+            .map(function (page) {
+                return { page: page, score: score(page, terms) };
+            })
+            .filter(function (found) {
+                return found.score > 0;
+            })
+            .sort(function (a, b) {
+                return b.score - a.score || a.page.title.localeCompare(b.page.title);
+            })
+            .slice(0, 20)
+            .forEach(function (found) {
+                var link = document.createElement('a');
+                link.href = siteRoot + found.page.path;
+                link.textContent = found.page.title;
+                var entry = document.createElement('li');
+                entry.appendChild(link);
+                results.appendChild(entry);
+            });
+    });
+})();
@@ -0,0 +1,140 @@
+use crate::{config::PageTemplates, read_file};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+use tera::{Context, Tera, Value};
+
+/// Kinds of symbols rendered with templates, along with their built-in templates.
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    ("enum", include_str!("templates/enum.md")),
+    ("class", include_str!("templates/class.md")),
+    ("function", include_str!("templates/function.md")),
+    ("delegate", include_str!("templates/delegate.md")),
+];
+
+/// Templates rendering symbol sections of book pages, built-in ones replaced by user templates
+/// configured in `backend_mdbook.templates`. Templates get symbol as `item` along with parts of
+/// its page already rendered into Markdown (e.g. `signature`, `badges` or `comments`), and book
+/// variables configured in `backend_mdbook.variables` as `book`.
+pub struct Templates {
+    tera: Tera,
+    variables: BTreeMap<String, String>,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        Self::load(&Default::default(), &Default::default(), Path::new(""))
+    }
+}
+
+impl Templates {
+    /// Loads built-in templates, replaced by given user templates read relative to given root.
+    /// Templates get given book variables.
+    pub fn load(
+        templates: &PageTemplates,
+        variables: &BTreeMap<String, String>,
+        root: &Path,
+    ) -> Self {
+        let mut tera = Tera::default();
+        tera.register_filter("indent_lines", indent_lines);
+        let user = [
+            &templates.enumeration,
+            &templates.class,
+            &templates.function,
+            &templates.delegate,
+        ];
+        for ((kind, builtin), path) in BUILTIN_TEMPLATES.into_iter().zip(user) {
+            let source = match path {
+                Some(path) => read_file(root.join(path))
+                    .unwrap_or_else(|_| panic!("Could not read template file: {:?}", path)),
+                None => builtin.to_owned(),
+            };
+            // like in Jinja, single newline ending template file is not part of its content
+            let source = source
+                .strip_suffix('\n')
+                .map(|source| source.strip_suffix('\r').unwrap_or(source))
+                .unwrap_or(&source);
+            tera.add_raw_template(kind, source).unwrap_or_else(|error| {
+                panic!("Could not parse {} template: {}", kind, error_chain(&error))
+            });
+        }
+        Self {
+            tera,
+            variables: variables.to_owned(),
+        }
+    }
+
+    pub fn render(&self, kind: &str, context: &Context) -> String {
+        let mut context = context.to_owned();
+        context.insert("book", &self.variables);
+        self.tera.render(kind, &context).unwrap_or_else(|error| {
+            panic!(
+                "Could not render {} template: {}",
+                kind,
+                error_chain(&error)
+            )
+        })
+    }
+}
+
+/// Error message along with its causes, as Tera puts details of errors into their sources.
+fn error_chain(error: &tera::Error) -> String {
+    let mut result = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        result.push_str(&format!(": {}", error));
+        source = error.source();
+    }
+    result
+}
+
+/// Indents every line of text with given `width` of spaces, used to nest member sections in
+/// their list items.
+fn indent_lines(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let content = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg("`indent_lines` filter expects text"))?;
+    let width = args
+        .get("width")
+        .and_then(|width| width.as_u64())
+        .unwrap_or(4) as usize;
+    let result = if width > 0 {
+        content
+            .lines()
+            .map(|line| " ".repeat(width) + line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        content.to_owned()
+    };
+    Ok(Value::String(result))
+}
+
+#[test]
+fn test_user_templates() {
+    let root = std::env::temp_dir().join(format!("unreal-doc-templates-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(
+        root.join("enum.md"),
+        "# {{ item.name }}\n\n{{ comments }}\n{% filter indent_lines(width=2) %}a\nb{% endfilter %}\n\
+        Ask {{ book.support_email }}.\n",
+    )
+    .unwrap();
+    let templates = Templates::load(
+        &PageTemplates {
+            enumeration: Some("enum.md".into()),
+            ..Default::default()
+        },
+        &BTreeMap::from([("support_email".to_owned(), "help@acme.com".to_owned())]),
+        &root,
+    );
+    let _ = std::fs::remove_dir_all(&root);
+    let mut context = Context::new();
+    context.insert("item", &serde_json::json!({ "name": "EFoo" }));
+    context.insert("comments", "Foo <b>bar</b>.");
+    assert_eq!(
+        templates.render("enum", &context),
+        "# EFoo\n\nFoo <b>bar</b>.\n  a\n  b\nAsk help@acme.com."
+    );
+}
@@ -1,11 +1,116 @@
+use crate::document::SignatureOptions;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Backend {
     #[default]
     Json,
     MdBook,
+    Html,
+    Docusaurus,
+    Doxygen,
+    Markdown,
+    Confluence,
+    Pdf,
+    LlmsTxt,
+    /// Backend registered by name in backend registry by tools using this crate as library.
+    #[serde(untagged)]
+    Custom(String),
+}
+
+impl Backend {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Json => "Json",
+            Self::MdBook => "MdBook",
+            Self::Html => "Html",
+            Self::Docusaurus => "Docusaurus",
+            Self::Doxygen => "Doxygen",
+            Self::Markdown => "Markdown",
+            Self::Confluence => "Confluence",
+            Self::Pdf => "Pdf",
+            Self::LlmsTxt => "LlmsTxt",
+            Self::Custom(name) => name,
+        }
+    }
+
+    /// Tells if backend bakes book pages, configured with `backend_mdbook` section.
+    pub fn is_book(&self) -> bool {
+        matches!(
+            self,
+            Self::MdBook
+                | Self::Html
+                | Self::Docusaurus
+                | Self::Markdown
+                | Self::Confluence
+                | Self::Pdf
+                | Self::LlmsTxt
+        )
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BriefMode {
+    #[default]
+    FirstSentence,
+    FirstParagraph,
+}
+
+/// What to do when doc comments try to inject snippet that does not exist.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MissingSnippet {
+    /// Put "Missing snippet" placeholder text in place of the snippet.
+    #[default]
+    Placeholder,
+    /// Remove snippet code block from the page.
+    Drop,
+    /// Fail documentation baking.
+    Fail,
+}
+
+/// Named reference section listing all structs and classes deriving from given base class.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupRule {
+    pub base: String,
+    pub title: String,
+}
+
+/// How symbols without any doc comments are treated.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UndocumentedMode {
+    /// Include them like any other symbol.
+    #[default]
+    Include,
+    /// Include them, flagged with "Undocumented" badge.
+    Badge,
+    /// Leave them out of documentation.
+    Exclude,
+}
+
+/// How terms of book glossary are marked on pages, besides being listed on Glossary page.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GlossaryTerms {
+    /// Leave them as they are.
+    #[default]
+    Plain,
+    /// Link first occurrence of every term on page to its glossary entry.
+    Link,
+    /// Show definition of first occurrence of every term on page as tooltip.
+    Tooltip,
+}
+
+/// Where on pages shared header file gets injected.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HeaderPosition {
+    /// Above page title.
+    #[default]
+    BeforeTitle,
+    /// Right below page title, with its headings nested under the title.
+    AfterTitle,
+    /// At the end of page, above shared footer, with its headings nested under page title.
+    BeforeFooter,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -27,9 +132,71 @@ pub struct BackendMdBook {
     #[serde(default)]
     pub footer: Option<PathBuf>,
     #[serde(default)]
+    pub header_position: HeaderPosition,
+    #[serde(default)]
     pub assets: Option<PathBuf>,
     #[serde(default)]
     pub site_url: Option<String>,
+    /// Template of links to source headers of reference pages, with `{path}` (relative to input
+    /// directory of header) and `{line}` placeholders.
+    #[serde(default)]
+    pub edit_url: Option<String>,
+    /// Template of links to Markdown sources of handbook pages, with `{path}` placeholder
+    /// (relative to input directory of book pages).
+    #[serde(default)]
+    pub page_edit_url: Option<String>,
+    /// Turns `File.h : 123` comments in signatures into links made from `edit_url`.
+    #[serde(default)]
+    pub location_links: bool,
+    #[serde(default)]
+    pub glossary_terms: GlossaryTerms,
+    #[serde(default)]
+    pub strip_api_macros: bool,
+    #[serde(default)]
+    pub brief: BriefMode,
+    #[serde(default)]
+    pub graphviz: bool,
+    /// Embeds inheritance diagrams as Mermaid code blocks, rendered by site itself.
+    #[serde(default)]
+    pub mermaid: bool,
+    #[serde(default)]
+    pub editor_section: bool,
+    /// Adds "Class Hierarchy" reference page with inheritance trees of all structs and classes.
+    #[serde(default)]
+    pub class_hierarchy: bool,
+    #[serde(default)]
+    pub missing_snippet: MissingSnippet,
+    #[serde(default = "BackendMdBook::default_heading_level")]
+    pub heading_level: usize,
+    #[serde(default)]
+    pub groups: Vec<GroupRule>,
+    #[serde(default)]
+    pub category_depth: Option<usize>,
+    #[serde(default)]
+    pub prefix_tolerant_references: bool,
+    #[serde(default)]
+    pub permalinks: bool,
+    #[serde(default)]
+    pub section_numbers: bool,
+    #[serde(default)]
+    pub print_structure: bool,
+    #[serde(default)]
+    pub page_metrics: bool,
+    /// Column limit of function signatures, beyond which their arguments go one per line.
+    #[serde(default)]
+    pub signature_width: Option<usize>,
+    /// Former symbol names mapped to names of symbols they were renamed to.
+    #[serde(default)]
+    pub redirects: BTreeMap<String, String>,
+    /// How many days symbols stay listed on "Recently added APIs" page after they appeared.
+    #[serde(default = "BackendMdBook::default_recent_days")]
+    pub recent_days: usize,
+    #[serde(default)]
+    pub templates: PageTemplates,
+    /// Project-specific values (e.g. support email) that templates get as `book` object,
+    /// mapped by their names.
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
 }
 
 impl Default for BackendMdBook {
@@ -43,13 +210,197 @@ impl Default for BackendMdBook {
             cleanup: false,
             header: None,
             footer: None,
+            header_position: Default::default(),
             assets: None,
             site_url: None,
+            edit_url: None,
+            page_edit_url: None,
+            location_links: false,
+            glossary_terms: Default::default(),
+            strip_api_macros: false,
+            brief: Default::default(),
+            graphviz: false,
+            mermaid: false,
+            editor_section: false,
+            class_hierarchy: false,
+            missing_snippet: Default::default(),
+            heading_level: Self::default_heading_level(),
+            groups: vec![],
+            category_depth: None,
+            prefix_tolerant_references: false,
+            permalinks: false,
+            section_numbers: false,
+            print_structure: false,
+            page_metrics: false,
+            signature_width: None,
+            redirects: Default::default(),
+            recent_days: Self::default_recent_days(),
+            templates: Default::default(),
+            variables: Default::default(),
+        }
+    }
+}
+
+/// Paths of Tera templates (relative to config file) replacing built-in ones that render symbol
+/// pages, by kind of symbol.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageTemplates {
+    #[serde(default, rename = "enum")]
+    pub enumeration: Option<PathBuf>,
+    /// Renders structs and interfaces too.
+    #[serde(default)]
+    pub class: Option<PathBuf>,
+    /// Renders methods too.
+    #[serde(default)]
+    pub function: Option<PathBuf>,
+    #[serde(default)]
+    pub delegate: Option<PathBuf>,
+}
+
+/// Options of JSON backend, baking whole document into single `documentation.json` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendJson {
+    /// Wraps document into object telling version of its schema and generator version.
+    #[serde(default)]
+    pub versioned: bool,
+    #[serde(default = "BackendJson::default_pretty")]
+    pub pretty: bool,
+}
+
+impl Default for BackendJson {
+    fn default() -> Self {
+        Self {
+            versioned: false,
+            pretty: Self::default_pretty(),
+        }
+    }
+}
+
+impl BackendJson {
+    fn default_pretty() -> bool {
+        true
+    }
+}
+
+/// Options of HTML backend, baking MD Book pages into static site.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendHtml {
+    /// Packages site the way plugin marketplaces expect it: in `docs` directory, linked only
+    /// relatively and validated against package constraints.
+    #[serde(default)]
+    pub marketplace: bool,
+    /// Size limit in bytes of every file of marketplace package.
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+}
+
+/// Options of Docusaurus backend, baking MD Book pages into Docusaurus docs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendDocusaurus {
+    /// Name of sidebar with book index in generated `sidebars.js`.
+    #[serde(default = "BackendDocusaurus::default_sidebar")]
+    pub sidebar: String,
+}
+
+impl Default for BackendDocusaurus {
+    fn default() -> Self {
+        Self {
+            sidebar: Self::default_sidebar(),
+        }
+    }
+}
+
+impl BackendDocusaurus {
+    fn default_sidebar() -> String {
+        "docs".to_owned()
+    }
+}
+
+/// Options of Confluence backend, baking MD Book pages into Confluence storage format pages.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendConfluence {
+    /// Text put before title of every page, as page titles have to be unique in whole space.
+    #[serde(default)]
+    pub title_prefix: String,
+    /// Creates or updates baked pages in Confluence space with its REST API after baking.
+    #[serde(default)]
+    pub push: bool,
+    /// Confluence address REST API is served from, e.g. `https://studio.atlassian.net/wiki`.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Key of space pages are pushed to.
+    #[serde(default)]
+    pub space: Option<String>,
+    /// Id of page that top level pages are pushed under.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// Options of PDF backend, baking MD Book pages into single print-ready document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendPdf {
+    /// Command converting print-ready HTML document into PDF, run in output directory, with
+    /// `{input}` replaced by HTML file name and `{output}` by PDF file name.
+    #[serde(default = "BackendPdf::default_command")]
+    pub command: String,
+    /// Text put on cover page below title, e.g. version of documented API.
+    #[serde(default)]
+    pub subtitle: Option<String>,
+}
+
+impl Default for BackendPdf {
+    fn default() -> Self {
+        Self {
+            command: Self::default_command(),
+            subtitle: None,
         }
     }
 }
 
+impl BackendPdf {
+    fn default_command() -> String {
+        "weasyprint {input} {output}".to_owned()
+    }
+}
+
+/// Options of llms.txt backend, baking MD Book pages into plain text files for language models.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendLlmsTxt {
+    /// Short summary of documented project, put below title.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// URL of published book that pages are linked with, e.g. `https://docs.example.com/`.
+    /// Links are relative to `llms.txt` file when not set.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Max length of chunks in characters. Longer pages are split at headings.
+    #[serde(default = "BackendLlmsTxt::default_chunk_size")]
+    pub chunk_size: usize,
+}
+
+impl Default for BackendLlmsTxt {
+    fn default() -> Self {
+        Self {
+            description: None,
+            base_url: None,
+            chunk_size: Self::default_chunk_size(),
+        }
+    }
+}
+
+impl BackendLlmsTxt {
+    fn default_chunk_size() -> usize {
+        6000
+    }
+}
+
 impl BackendMdBook {
+    pub fn signature_options(&self) -> SignatureOptions {
+        SignatureOptions {
+            strip_api_macros: self.strip_api_macros,
+        }
+    }
+
     fn default_title() -> String {
         "Documentation".to_owned()
     }
@@ -57,6 +408,73 @@ impl BackendMdBook {
     fn default_language() -> String {
         "en".to_owned()
     }
+
+    fn default_heading_level() -> usize {
+        1
+    }
+
+    fn default_recent_days() -> usize {
+        30
+    }
+}
+
+/// Shell commands run at given stages of baking, from config file directory.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hooks {
+    /// Run once documentation files are baked, before they get built.
+    #[serde(default)]
+    pub post_bake: Vec<String>,
+    /// Run once MD Book bundle is built.
+    #[serde(default)]
+    pub post_build: Vec<String>,
+}
+
+/// Git repository whose directories get documented together with input directories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSource {
+    pub url: String,
+    /// Tag, branch or commit to fetch. Fetched revision is reused until its cache directory gets
+    /// removed, so tags and commits fit better than branches.
+    pub rev: String,
+    /// Directories of repository to document, relative to its root. Whole repository gets
+    /// documented when empty.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+/// Terms checked in doc comments and book pages, reported as warnings.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Terminology {
+    /// Terms that must be written exactly like this, e.g. `Blueprint` and not `blueprint`.
+    #[serde(default)]
+    pub terms: Vec<String>,
+    /// Terms that should not be used, mapped to suggested replacements (empty if none).
+    #[serde(default)]
+    pub banned: BTreeMap<String, String>,
+}
+
+/// Check of function parameters that should document units of their values.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Units {
+    /// Categories of functions, whose floating point parameters must have units, including
+    /// their subcategories.
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+/// Check of doc comments and book pages against bundled list of common misspellings. Words not
+/// listed as misspellings are never reported, so this is no replacement for dictionary
+/// spellcheck.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Spellcheck {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Words that are spelled correctly in this project, even if listed as misspellings.
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// Project-specific misspellings mapped to their corrections.
+    #[serde(default)]
+    pub misspellings: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -64,14 +482,185 @@ pub struct Config {
     #[serde(default)]
     pub dependencies: Vec<PathBuf>,
     pub input_dirs: Vec<PathBuf>,
+    /// Glob patterns of headers to document, matched against their paths relative to input
+    /// directories. All headers are documented when empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns of headers left out of documentation, even if included.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Git repositories, e.g. of upstream plugins, documented together with input directories.
+    #[serde(default)]
+    pub remote_sources: Vec<RemoteSource>,
+    /// Directory remote sources get fetched into, `.unreal-doc/remote` next to config file by
+    /// default.
+    #[serde(default)]
+    pub remote_cache_dir: Option<PathBuf>,
+    /// Keeps parsed headers in `.unreal-doc-cache` directory next to config file, so next runs
+    /// parse only headers that changed since.
+    #[serde(default)]
+    pub incremental: bool,
     pub output_dir: PathBuf,
+    /// Directory of full sample C++ files shown in "Examples" book section.
+    #[serde(default)]
+    pub examples_dir: Option<PathBuf>,
+    /// CODEOWNERS-like file with owners of headers, matched by paths relative to input
+    /// directories.
+    #[serde(default)]
+    pub code_owners: Option<PathBuf>,
     #[serde(default)]
     pub backend: Backend,
+    /// Backends that all bake the same parsed document in single run, each into its own
+    /// subdirectory of output directory. Used instead of `backend` when not empty.
+    #[serde(default)]
+    pub backends: Vec<Backend>,
+    /// Active profile, e.g. `public` or `internal`, that conditional book content is checked
+    /// against.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Active tags that conditional book content is checked against.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub parse: ParseSettings,
+    #[serde(default)]
+    pub render: RenderSettings,
+    /// Former `settings` section, replacing `parse` and `render` sections when present.
     #[serde(default)]
-    pub settings: Settings,
+    pub settings: Option<Settings>,
+    pub backend_json: Option<BackendJson>,
     pub backend_mdbook: Option<BackendMdBook>,
+    pub backend_html: Option<BackendHtml>,
+    pub backend_docusaurus: Option<BackendDocusaurus>,
+    pub backend_confluence: Option<BackendConfluence>,
+    pub backend_pdf: Option<BackendPdf>,
+    pub backend_llms_txt: Option<BackendLlmsTxt>,
+    #[serde(default)]
+    pub hooks: Hooks,
+    /// Commands of extraction plugins that get every parsed symbol before it is documented.
+    #[serde(default)]
+    pub plugins: Vec<String>,
+    /// Tokens replaced in doc comments and book pages, mapped by their names.
+    #[serde(default)]
+    pub substitutions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub terminology: Terminology,
+    #[serde(default)]
+    pub spellcheck: Spellcheck,
+    #[serde(default)]
+    pub units: Units,
+    /// Path of JSON report of the run, with symbol counts, coverage, baked pages, warnings and
+    /// phase durations.
+    #[serde(default)]
+    pub report: Option<PathBuf>,
+    /// Tells backends to print changes to existing output instead of writing it. Set by command
+    /// line only.
+    #[serde(skip)]
+    pub diff_output: bool,
 }
 
+impl Config {
+    /// Backends that bake documentation of this config, in order, without duplicates.
+    pub fn active_backends(&self) -> Vec<Backend> {
+        let mut result = vec![];
+        for backend in &self.backends {
+            if !result.contains(backend) {
+                result.push(backend.to_owned());
+            }
+        }
+        if result.is_empty() {
+            result.push(self.backend.to_owned());
+        }
+        result
+    }
+}
+
+/// Settings that change what gets read from source files, so changing them needs sources to be
+/// parsed again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseSettings {
+    #[serde(default)]
+    pub raw_header_view: bool,
+    #[serde(default)]
+    pub informal_comments: bool,
+    #[serde(default)]
+    pub constructor_defaults: bool,
+    #[serde(default)]
+    pub lifetime_replication: bool,
+    /// Regular expression matching export macros that tell module of symbols, with module name
+    /// in its first capture group.
+    #[serde(default = "ParseSettings::default_api_macro")]
+    pub api_macro: String,
+}
+
+impl Default for ParseSettings {
+    fn default() -> Self {
+        Self {
+            raw_header_view: false,
+            informal_comments: false,
+            constructor_defaults: false,
+            lifetime_replication: false,
+            api_macro: Self::default_api_macro(),
+        }
+    }
+}
+
+impl ParseSettings {
+    fn default_api_macro() -> String {
+        r"^(\w+)_API$".to_owned()
+    }
+
+    pub fn api_macro_regex(&self) -> Regex {
+        Regex::new(&self.api_macro)
+            .unwrap_or_else(|_| panic!("Invalid API macro pattern: {}", self.api_macro))
+    }
+}
+
+/// Settings that only change what parsed symbols are presented and how. Filters are applied to
+/// already parsed document, right before it gets baked.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RenderSettings {
+    #[serde(default)]
+    pub show_all: bool,
+    #[serde(default)]
+    pub document_protected: bool,
+    #[serde(default)]
+    pub document_private: bool,
+    #[serde(default)]
+    pub exclude_editor_only: bool,
+    /// Leaves out plain C++ enums, structs, classes and functions, declared without reflection
+    /// macros.
+    #[serde(default)]
+    pub require_reflection: bool,
+    #[serde(default)]
+    pub hide_experimental: bool,
+    #[serde(default)]
+    pub hide_deprecated: bool,
+    /// Tags, declared with `@tag` doc comment lines, that symbols need at least one of to be
+    /// documented. All symbols are documented when empty.
+    #[serde(default)]
+    pub include_tags: Vec<String>,
+    /// Tags that leave symbols having any of them out of documentation, even if included.
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    #[serde(default)]
+    pub raw_html: bool,
+    #[serde(default)]
+    pub audit: bool,
+    #[serde(default)]
+    pub undocumented: UndocumentedMode,
+}
+
+impl RenderSettings {
+    /// Tells if symbol with given tags is left out by `include_tags` or `exclude_tags`.
+    pub fn hides_tags(&self, tags: &[String]) -> bool {
+        tags.iter().any(|tag| self.exclude_tags.contains(tag))
+            || (!self.include_tags.is_empty()
+                && !tags.iter().any(|tag| self.include_tags.contains(tag)))
+    }
+}
+
+/// Former single section mixing parse and render settings, still read from older configs.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Settings {
     #[serde(default)]
@@ -80,4 +669,51 @@ pub struct Settings {
     pub document_protected: bool,
     #[serde(default)]
     pub document_private: bool,
+    #[serde(default)]
+    pub raw_header_view: bool,
+    #[serde(default)]
+    pub informal_comments: bool,
+    #[serde(default)]
+    pub exclude_editor_only: bool,
+    #[serde(default)]
+    pub hide_experimental: bool,
+    #[serde(default)]
+    pub raw_html: bool,
+    #[serde(default)]
+    pub constructor_defaults: bool,
+    #[serde(default)]
+    pub lifetime_replication: bool,
+    #[serde(default)]
+    pub audit: bool,
+    #[serde(default)]
+    pub undocumented: UndocumentedMode,
+}
+
+impl Settings {
+    pub fn split(self) -> (ParseSettings, RenderSettings) {
+        let parse = ParseSettings {
+            raw_header_view: self.raw_header_view,
+            informal_comments: self.informal_comments,
+            constructor_defaults: self.constructor_defaults,
+            lifetime_replication: self.lifetime_replication,
+            ..Default::default()
+        };
+        let render = RenderSettings {
+            show_all: self.show_all,
+            document_protected: self.document_protected,
+            document_private: self.document_private,
+            exclude_editor_only: self.exclude_editor_only,
+            hide_experimental: self.hide_experimental,
+            raw_html: self.raw_html,
+            audit: self.audit,
+            undocumented: self.undocumented,
+            // filters added together with `render` section have no counterparts here, so
+            // former configs keep documenting what they did
+            require_reflection: false,
+            hide_deprecated: false,
+            include_tags: vec![],
+            exclude_tags: vec![],
+        };
+        (parse, render)
+    }
 }
@@ -0,0 +1,75 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Kind of problem, e.g. `spelling` or `snippets`, used to group problems in run report.
+    pub category: &'static str,
+    /// Path of generated page or location of doc comments this diagnostic relates to.
+    pub page: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        };
+        write!(f, "{} in {}: {}", severity, self.page, self.message)
+    }
+}
+
+/// Collects problems found while baking documentation, so they can be reported together.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    pub items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn warning(&mut self, category: &'static str, page: &str, message: impl ToString) {
+        self.push(Severity::Warning, category, page, message);
+    }
+
+    pub fn error(&mut self, category: &'static str, page: &str, message: impl ToString) {
+        self.push(Severity::Error, category, page, message);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| item.severity == Severity::Error)
+    }
+
+    /// Prints all collected diagnostics, returning error if any of them is an error.
+    pub fn report(&self) -> Result<(), Box<dyn Error>> {
+        for item in &self.items {
+            println!("{}", item);
+        }
+        if self.has_errors() {
+            return Err("Documentation baking failed with errors!".into());
+        }
+        Ok(())
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        category: &'static str,
+        page: &str,
+        message: impl ToString,
+    ) {
+        self.items.push(Diagnostic {
+            severity,
+            category,
+            page: page.to_owned(),
+            message: message.to_string(),
+        });
+    }
+}
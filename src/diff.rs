@@ -0,0 +1,196 @@
+use crate::read_file;
+use similar::{Algorithm, DiffTag, capture_diff_slices};
+use std::{collections::BTreeMap, path::Path};
+
+/// Lines of unchanged context shown around changes.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Shortest list of line operations that turns old lines into new ones, found with Myers
+/// algorithm, which needs memory linear in number of lines even for big files.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let mut result = vec![];
+    for operation in capture_diff_slices(Algorithm::Myers, old, new) {
+        let (tag, old_range, new_range) = operation.as_tag_tuple();
+        match tag {
+            DiffTag::Equal => result.extend(old[old_range].iter().map(|line| Line::Same(line))),
+            DiffTag::Delete => result.extend(old[old_range].iter().map(|line| Line::Removed(line))),
+            DiffTag::Insert => result.extend(new[new_range].iter().map(|line| Line::Added(line))),
+            DiffTag::Replace => {
+                result.extend(old[old_range].iter().map(|line| Line::Removed(line)));
+                result.extend(new[new_range].iter().map(|line| Line::Added(line)));
+            }
+        }
+    }
+    result
+}
+
+fn in_old(line: &Line) -> bool {
+    !matches!(line, Line::Added(_))
+}
+
+fn in_new(line: &Line) -> bool {
+    !matches!(line, Line::Removed(_))
+}
+
+/// Unified diff of two versions of file, or `None` if they are the same.
+pub fn unified_diff(old: &str, new: &str, path: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let lines = diff_lines(&old_lines, &new_lines);
+    let changes = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Same(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let mut result = format!("--- a/{}\n+++ b/{}\n", path, path);
+    if changes.is_empty() {
+        result.push_str("(only line endings changed)\n");
+        return Some(result);
+    }
+    // group changes that are close enough to share their context.
+    let mut hunks = vec![];
+    let mut start = changes[0];
+    let mut end = changes[0];
+    for &index in &changes[1..] {
+        if index - end > CONTEXT * 2 {
+            hunks.push((start, end));
+            start = index;
+        }
+        end = index;
+    }
+    hunks.push((start, end));
+    for (start, end) in hunks {
+        let start = start.saturating_sub(CONTEXT);
+        let end = (end + CONTEXT + 1).min(lines.len());
+        let position =
+            |side: fn(&Line) -> bool| lines[..start].iter().filter(|line| side(line)).count() + 1;
+        let count =
+            |side: fn(&Line) -> bool| lines[start..end].iter().filter(|line| side(line)).count();
+        result.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            position(in_old),
+            count(in_old),
+            position(in_new),
+            count(in_new)
+        ));
+        for line in &lines[start..end] {
+            match line {
+                Line::Same(line) => result.push_str(&format!(" {}\n", line)),
+                Line::Removed(line) => result.push_str(&format!("-{}\n", line)),
+                Line::Added(line) => result.push_str(&format!("+{}\n", line)),
+            }
+        }
+    }
+    Some(result)
+}
+
+/// Prints what would change in output directory if given files were written there: unified
+/// diffs of changed files, lists of added and removed ones and a summary. Files with given
+/// extension found in `tracked_dir` of output directory, but missing from given files, are
+/// considered removed.
+pub fn preview_changes(
+    output_dir: &Path,
+    files: &BTreeMap<String, String>,
+    tracked_dir: Option<&str>,
+    tracked_extension: &str,
+) {
+    let mut added = vec![];
+    let mut changed = 0;
+    let mut unchanged = 0;
+    for (path, content) in files {
+        match read_file(output_dir.join(path)) {
+            Ok(existing) => match unified_diff(&existing, content, path) {
+                Some(diff) => {
+                    print!("{}", diff);
+                    changed += 1;
+                }
+                None => unchanged += 1,
+            },
+            Err(_) => added.push(path.as_str()),
+        }
+    }
+    let mut removed = vec![];
+    if let Some(tracked_dir) = tracked_dir {
+        collect_removed(
+            &output_dir.join(tracked_dir),
+            tracked_dir,
+            tracked_extension,
+            files,
+            &mut removed,
+        );
+    }
+    for path in &added {
+        println!("Added: {}", path);
+    }
+    for path in &removed {
+        println!("Removed: {}", path);
+    }
+    println!(
+        "Documentation changes: {} added, {} changed, {} removed, {} unchanged",
+        added.len(),
+        changed,
+        removed.len(),
+        unchanged
+    );
+}
+
+fn collect_removed(
+    dir: &Path,
+    relative: &str,
+    extension: &str,
+    files: &BTreeMap<String, String>,
+    result: &mut Vec<String>,
+) {
+    let Ok(entries) = dir.read_dir() else {
+        return;
+    };
+    let mut entries = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            continue;
+        };
+        let relative = format!("{}/{}", relative, name);
+        if path.is_dir() {
+            collect_removed(&path, &relative, extension, files, result);
+        } else if path.extension().is_some_and(|ext| ext == extension)
+            && !files.contains_key(&relative)
+        {
+            result.push(relative);
+        }
+    }
+}
+
+#[test]
+fn test_unified_diff() {
+    assert!(unified_diff("a\nb\n", "a\nb\n", "page.md").is_none());
+    let old = (1..=14)
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    let new = old.replace("\n5\n", "\nfive\n") + "15\n";
+    assert_eq!(
+        unified_diff(&old, &new, "page.md").unwrap(),
+        "--- a/page.md\n+++ b/page.md\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n\
+        @@ -12,3 +12,4 @@\n 12\n 13\n 14\n+15\n"
+    );
+    let old = (0..100_000)
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    let new = old.replace("\n50000\n", "\n") + "end\n";
+    let diff = unified_diff(&old, &new, "documentation.json").unwrap();
+    assert!(diff.contains("@@ -49998,7 +49998,6 @@\n 49997\n 49998\n 49999\n-50000\n 50001\n"));
+}
@@ -0,0 +1,213 @@
+//! Environment checks run by `unreal-doc doctor` command, telling up front what would make
+//! baking documentation fail midway.
+
+use crate::{
+    backends::BackendRegistry,
+    config::{Backend, Config},
+    read_file,
+};
+use std::{
+    fs::{remove_file, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Result of single environment check.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub passed: bool,
+    pub message: String,
+}
+
+impl Check {
+    fn new(passed: bool, message: impl Into<String>) -> Self {
+        Self {
+            passed,
+            message: message.into(),
+        }
+    }
+}
+
+/// Checks environment of given config file: that config parses, its input directories exist
+/// and contain headers, its backends are known, files it points to exist, output directory
+/// can be written and `mdbook` command is found when book gets built. Given output directory
+/// overrides the one from config.
+pub fn check_environment(input: &Path, output: Option<&Path>) -> Vec<Check> {
+    let mut result = vec![];
+    let content = match read_file(input) {
+        Ok(content) => content,
+        Err(error) => {
+            result.push(Check::new(
+                false,
+                format!("Config file can not be read: {:?} ({})", input, error),
+            ));
+            return result;
+        }
+    };
+    let config = match toml::from_str::<Config>(&content) {
+        Ok(config) => config,
+        Err(error) => {
+            result.push(Check::new(
+                false,
+                format!("Config file does not parse: {:?}\n{}", input, error),
+            ));
+            return result;
+        }
+    };
+    result.push(Check::new(true, format!("Config file parses: {:?}", input)));
+    let root = input.parent().unwrap_or(Path::new("")).to_owned();
+
+    for path in &config.dependencies {
+        let path = root.join(path);
+        let parses = read_file(&path)
+            .ok()
+            .is_some_and(|content| toml::from_str::<Config>(&content).is_ok());
+        result.push(if parses {
+            Check::new(true, format!("Dependency config parses: {:?}", path))
+        } else {
+            Check::new(
+                false,
+                format!("Dependency config does not parse: {:?}", path),
+            )
+        });
+    }
+    for path in &config.input_dirs {
+        let path = root.join(path);
+        result.push(if !path.exists() {
+            Check::new(false, format!("Input path does not exist: {:?}", path))
+        } else {
+            match count_headers(&path) {
+                0 => Check::new(false, format!("Input path has no headers: {:?}", path)),
+                count => Check::new(
+                    true,
+                    format!("Input path has headers ({} found): {:?}", count, path),
+                ),
+            }
+        });
+    }
+    if !config.remote_sources.is_empty() {
+        result.push(command_check("git", &["--version"]));
+    }
+
+    let registry = BackendRegistry::default();
+    for backend in config.active_backends() {
+        result.push(if registry.get(backend.name()).is_some() {
+            Check::new(true, format!("Backend is available: {}", backend.name()))
+        } else {
+            Check::new(false, format!("Unknown backend: {}", backend.name()))
+        });
+    }
+    let mut files = vec![
+        ("Examples directory", config.examples_dir.as_ref()),
+        ("Code owners file", config.code_owners.as_ref()),
+    ];
+    if let Some(mdbook) = &config.backend_mdbook {
+        files.push(("Header file", mdbook.header.as_ref()));
+        files.push(("Footer file", mdbook.footer.as_ref()));
+        files.push(("Assets directory", mdbook.assets.as_ref()));
+    }
+    for (name, path) in files {
+        if let Some(path) = path {
+            let path = root.join(path);
+            result.push(if path.exists() {
+                Check::new(true, format!("{} found: {:?}", name, path))
+            } else {
+                Check::new(false, format!("{} not found: {:?}", name, path))
+            });
+        }
+    }
+    let build = config
+        .backend_mdbook
+        .as_ref()
+        .is_some_and(|mdbook| mdbook.build);
+    if build && config.active_backends().contains(&Backend::MdBook) {
+        result.push(command_check("mdbook", &["--version"]));
+    }
+
+    let output_dir = match output {
+        Some(output) => output.to_owned(),
+        None => root.join(&config.output_dir),
+    };
+    result.push(if is_writable(&output_dir) {
+        Check::new(
+            true,
+            format!("Output directory is writable: {:?}", output_dir),
+        )
+    } else {
+        Check::new(
+            false,
+            format!("Output directory is not writable: {:?}", output_dir),
+        )
+    });
+    result
+}
+
+/// Number of headers in given directory and its subdirectories, or 1 if path is header itself.
+fn count_headers(path: &Path) -> usize {
+    if path.is_dir() {
+        path.read_dir()
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| count_headers(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or_default()
+    } else {
+        usize::from(path.extension().is_some_and(|ext| ext == "h"))
+    }
+}
+
+/// Checks that given command runs, telling what it prints, usually its version.
+fn command_check(command: &str, args: &[&str]) -> Check {
+    match Command::new(command).args(args).output() {
+        Ok(output) if output.status.success() => Check::new(
+            true,
+            format!(
+                "`{}` command found: {}",
+                command,
+                String::from_utf8_lossy(&output.stdout).trim()
+            ),
+        ),
+        _ => Check::new(false, format!("`{}` command not found", command)),
+    }
+}
+
+/// Tells if files can be written into given directory, or into its closest existing ancestor
+/// when it does not exist yet, so nothing gets created by checking it.
+fn is_writable(path: &Path) -> bool {
+    let mut dir = PathBuf::from(path);
+    while !dir.exists() {
+        if !dir.pop() {
+            dir = PathBuf::from(".");
+            break;
+        }
+    }
+    if !dir.is_dir() {
+        return false;
+    }
+    let probe = dir.join(format!(".unreal-doc-doctor-{}", std::process::id()));
+    let writable = write(&probe, "").is_ok();
+    let _ = remove_file(&probe);
+    writable
+}
+
+#[test]
+fn test_check_environment() {
+    let checks = check_environment(Path::new("resources/UnrealDoc.toml"), None);
+    let passed = |prefix: &str| {
+        checks
+            .iter()
+            .find(|check| check.message.starts_with(prefix))
+            .map(|check| check.passed)
+    };
+    assert_eq!(passed("Config file parses"), Some(true));
+    assert_eq!(passed("Input path has headers (1 found)"), Some(true));
+    assert_eq!(passed("Backend is available: MdBook"), Some(true));
+    assert_eq!(passed("Header file found"), Some(true));
+    assert_eq!(passed("Output directory is writable"), Some(true));
+
+    let checks = check_environment(Path::new("resources/Missing.toml"), None);
+    assert_eq!(checks.len(), 1);
+    assert!(!checks[0].passed);
+}
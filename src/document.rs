@@ -1,6 +1,13 @@
-use crate::config::Settings;
+use crate::config::{BriefMode, RenderSettings, UndocumentedMode};
+use once_cell::sync::{Lazy, OnceCell};
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
 pub type Type = String;
 pub type Template = String;
@@ -9,6 +16,1015 @@ fn replace_self_names(content: &str, owner: &str) -> String {
     content.replace("$Self$", owner)
 }
 
+/// Pointer part of function pointer type, e.g. `(*)` or `(UObject::*)`.
+static FUNCTION_POINTER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\(\s*([\w:]+::\s*)?\*\s*\)").unwrap());
+
+/// Position where name goes in function pointer type, e.g. right after `*` in `void (*)(int32)`.
+fn function_pointer_name_position(value_type: &str) -> Option<usize> {
+    FUNCTION_POINTER
+        .find(value_type)
+        .map(|found| found.end() - 1)
+}
+
+/// Tells if name is name of operator, e.g. `operator==` or `operator bool`, but not `operatorCount`.
+fn is_operator_name(name: &str) -> bool {
+    name.strip_prefix("operator")
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+/// Name usable in file names and anchors, with operator symbols spelled out, e.g.
+/// `operator_eq` for `operator==`.
+pub fn symbol_slug(name: &str) -> String {
+    if !is_operator_name(name) {
+        return name.to_owned();
+    }
+    let symbol = &name["operator".len()..];
+    let symbol = match symbol.trim() {
+        "==" => "eq",
+        "!=" => "ne",
+        "<=>" => "cmp",
+        "<" => "lt",
+        "<=" => "le",
+        ">" => "gt",
+        ">=" => "ge",
+        "+" => "add",
+        "-" => "sub",
+        "*" => "mul",
+        "/" => "div",
+        "%" => "rem",
+        "+=" => "add_assign",
+        "-=" => "sub_assign",
+        "*=" => "mul_assign",
+        "/=" => "div_assign",
+        "%=" => "rem_assign",
+        "=" => "assign",
+        "[]" => "index",
+        "()" => "call",
+        "<<" => "shl",
+        ">>" => "shr",
+        "!" => "not",
+        "&&" => "and",
+        "||" => "or",
+        "++" => "inc",
+        "--" => "dec",
+        "->" => "arrow",
+        "~" => "bit_not",
+        "&" => "bit_and",
+        "|" => "bit_or",
+        "^" => "bit_xor",
+        symbol => {
+            let result = symbol
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect::<String>();
+            return format!("operator_{}", result.trim_matches('_'));
+        }
+    };
+    format!("operator_{}", symbol)
+}
+
+/// HTML tags allowed in doc comments when raw HTML is not trusted. `param` and `returns` are
+/// doc comment conventions understood by backends.
+const ALLOWED_HTML_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "param",
+    "pre",
+    "returns",
+    "s",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Attributes allowed on allowed HTML tags. Event handlers and `style` never are.
+const ALLOWED_HTML_ATTRIBUTES: &[&str] = &[
+    "align", "alt", "class", "colspan", "height", "href", "name", "open", "rowspan", "src",
+    "start", "title", "width",
+];
+
+/// Attributes holding URLs, which are allowed only with safe schemes.
+const URL_HTML_ATTRIBUTES: &[&str] = &["href", "src"];
+
+/// URL schemes allowed in doc comments when raw HTML is not trusted. Relative URLs always are.
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Named character references decoded in attribute values and URLs, besides numeric ones.
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("colon", ':'),
+    ("gt", '>'),
+    ("lt", '<'),
+    ("newline", '\n'),
+    ("quot", '"'),
+    ("sol", '/'),
+    ("tab", '\t'),
+];
+
+/// Decodes character references of HTML text, e.g. `&#x61;` to `a`. Unknown named references
+/// are kept as they are.
+fn decode_html_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('&') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let (decoded, length) = if let Some(number) = rest.strip_prefix('#') {
+            let (digits, radix, prefix) = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => (hex, 16, 2),
+                None => (number, 10, 1),
+            };
+            let count = digits
+                .find(|c: char| !c.is_digit(radix))
+                .unwrap_or(digits.len());
+            let decoded = u32::from_str_radix(&digits[..count], radix)
+                .ok()
+                .map(|code| char::from_u32(code).unwrap_or('\u{FFFD}'));
+            (decoded, prefix + count)
+        } else {
+            let count = rest
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+            let name = rest[..count].to_ascii_lowercase();
+            let decoded = HTML_ENTITIES
+                .iter()
+                .find(|(entity, _)| *entity == name)
+                .map(|(_, c)| *c);
+            (decoded, count)
+        };
+        match decoded {
+            Some(decoded) => {
+                result.push(decoded);
+                rest = &rest[length..];
+                rest = rest.strip_prefix(';').unwrap_or(rest);
+            }
+            None => result.push('&'),
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Tells if decoded URL is relative or has allowed scheme. Whitespace and control characters,
+/// which browsers skip in schemes, are ignored.
+fn is_safe_url(url: &str) -> bool {
+    let url = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control() && *c != '\\')
+        .collect::<String>();
+    let scheme = &url[..url.find(['/', '?', '#']).unwrap_or(url.len())];
+    if scheme.contains('&') {
+        return false;
+    }
+    match scheme.split_once(':') {
+        Some((scheme, _)) => ALLOWED_URL_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()),
+        None => true,
+    }
+}
+
+/// HTML tag tokenized the way browsers do, with attribute values not decoded yet.
+struct HtmlTag<'a> {
+    closing: bool,
+    name: &'a str,
+    attributes: Vec<(&'a str, Option<&'a str>)>,
+}
+
+impl<'a> HtmlTag<'a> {
+    /// Tokenizes tag at the start of given text, returning it with its length in bytes. Text
+    /// that does not start with complete tag gives `None`.
+    fn parse(text: &'a str) -> Option<(Self, usize)> {
+        let bytes = text.as_bytes();
+        let skip = |mut index: usize, skipped: &dyn Fn(u8) -> bool| {
+            while bytes.get(index).is_some_and(|c| skipped(*c)) {
+                index += 1;
+            }
+            index
+        };
+        let closing = bytes.get(1) == Some(&b'/');
+        let start = if closing { 2 } else { 1 };
+        if !bytes.get(start)?.is_ascii_alphabetic() {
+            return None;
+        }
+        let mut index = skip(start, &|c| c.is_ascii_alphanumeric() || c == b'-');
+        let name = &text[start..index];
+        let mut attributes = vec![];
+        loop {
+            index = skip(index, &|c| c.is_ascii_whitespace() || c == b'/');
+            if *bytes.get(index)? == b'>' {
+                return Some((
+                    Self {
+                        closing,
+                        name,
+                        attributes,
+                    },
+                    index + 1,
+                ));
+            }
+            let start = index;
+            index = skip(index + 1, &|c| {
+                !c.is_ascii_whitespace() && !matches!(c, b'/' | b'>' | b'=')
+            });
+            let attribute = &text[start..index];
+            let mut value = None;
+            let after = skip(index, &|c| c.is_ascii_whitespace());
+            if bytes.get(after) == Some(&b'=') {
+                index = skip(after + 1, &|c| c.is_ascii_whitespace());
+                let quote = *bytes.get(index)?;
+                if quote == b'"' || quote == b'\'' {
+                    let length = text[index + 1..].find(quote as char)?;
+                    value = Some(&text[index + 1..index + 1 + length]);
+                    index += length + 2;
+                } else {
+                    let start = index;
+                    index = skip(index, &|c| !c.is_ascii_whitespace() && c != b'>');
+                    value = Some(&text[start..index]);
+                }
+            }
+            attributes.push((attribute, value));
+        }
+    }
+
+    /// Tag written back with only allowed attributes, or `None` when tag is not allowed or
+    /// links to URL with unsafe scheme.
+    fn sanitized(&self) -> Option<String> {
+        if !ALLOWED_HTML_TAGS.contains(&self.name.to_ascii_lowercase().as_str()) {
+            return None;
+        }
+        if self.closing {
+            return Some(format!("</{}>", self.name));
+        }
+        let mut result = format!("<{}", self.name);
+        for (attribute, value) in &self.attributes {
+            let attribute = attribute.to_ascii_lowercase();
+            if !ALLOWED_HTML_ATTRIBUTES.contains(&attribute.as_str()) {
+                continue;
+            }
+            let Some(value) = value else {
+                result.push_str(&format!(" {}", attribute));
+                continue;
+            };
+            let value = decode_html_entities(value);
+            if URL_HTML_ATTRIBUTES.contains(&attribute.as_str()) && !is_safe_url(&value) {
+                return None;
+            }
+            let value = value
+                .replace('&', "&amp;")
+                .replace('"', "&quot;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            result.push_str(&format!(" {}=\"{}\"", attribute, value));
+        }
+        result.push('>');
+        Some(result)
+    }
+}
+
+/// Escapes HTML tags that are not allowed, drops attributes that are not allowed from allowed
+/// ones and escapes anything else that could start tag, comment or declaration.
+fn sanitize_html_fragment(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(index) = rest.find('<') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some((tag, length)) = HtmlTag::parse(rest)
+            && let Some(sanitized) = tag.sanitized()
+        {
+            result.push_str(&sanitized);
+            rest = &rest[length..];
+            continue;
+        }
+        let starts_markup = rest[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        result.push_str(if starts_markup { "&lt;" } else { "<" });
+        rest = &rest[1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Markdown source of link with unsafe URL, escaped so it renders as plain text.
+fn escape_markdown_link(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    for c in source.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '[' | ']' | '(' | ')' | '!' | '`' => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Markdown extensions enabled wherever documentation Markdown gets parsed.
+pub fn markdown_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES
+}
+
+/// Sanitizes raw HTML and links of Markdown once, as found by the same Markdown parser the HTML
+/// backend renders with. Plain text is sanitized too, for backends that do not escape it.
+fn sanitize_markdown_pass(content: &str) -> String {
+    // Ranges of text and HTML (merged when adjacent, as parser splits text at `<`) and of links.
+    let mut ranges = Vec::<(Range<usize>, bool)>::new();
+    let mut code_block = false;
+    for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+        let link = match event {
+            Event::Start(Tag::CodeBlock(_)) => {
+                code_block = true;
+                continue;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                code_block = false;
+                continue;
+            }
+            Event::Text(_) if code_block => continue,
+            Event::Html(_) | Event::InlineHtml(_) | Event::Text(_) => false,
+            Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. })
+                if !is_safe_url(&dest_url) =>
+            {
+                true
+            }
+            _ => continue,
+        };
+        match ranges.last_mut() {
+            Some((last, false)) if !link && last.end == range.start => last.end = range.end,
+            Some((last, _)) if last.end > range.start => {}
+            _ => ranges.push((range, link)),
+        }
+    }
+    let mut result = content.to_owned();
+    for (range, link) in ranges.into_iter().rev() {
+        let replacement = if link {
+            escape_markdown_link(&content[range.clone()])
+        } else {
+            sanitize_html_fragment(&content[range.clone()])
+        };
+        result.replace_range(range, &replacement);
+    }
+    result
+}
+
+/// Escapes HTML tags that are not allowed, strips attributes other than allowed ones (such as
+/// event handlers and styles) and disables links with unsafe URL schemes, such as `javascript:`.
+/// Content of inline code and code blocks is left untouched. Sanitizing repeats until nothing
+/// changes, since escaped HTML blocks can reveal Markdown that was part of them.
+fn sanitize_html(content: &str) -> String {
+    let mut result = content.to_owned();
+    for _ in 0..8 {
+        let sanitized = sanitize_markdown_pass(&result);
+        if sanitized == result {
+            return result;
+        }
+        result = sanitized;
+    }
+    escape_markdown_link(&result)
+}
+
+/// `<alias>Name</alias>` tag of doc comments.
+static ALIAS_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<alias>\s*([^<]*?)\s*</alias>[ \t]*\n?").unwrap());
+
+/// Removes everything given doc tag pattern matches from doc comments and returns what each
+/// match gives, in order of appearance. Doc comments left empty are removed entirely.
+fn take_tag_lines<T>(
+    doc_comments: &mut Option<String>,
+    pattern: &Lazy<Regex>,
+    map: impl FnMut(Captures) -> T,
+) -> Vec<T> {
+    let Some(comments) = doc_comments.as_mut() else {
+        return vec![];
+    };
+    if !pattern.is_match(comments) {
+        return vec![];
+    }
+    let result = pattern.captures_iter(comments).map(map).collect();
+    *comments = pattern.replace_all(comments, "").trim_end().to_owned();
+    if comments.trim().is_empty() {
+        *doc_comments = None;
+    }
+    result
+}
+
+/// Removes `<alias>` tags from doc comments and returns names they contain.
+fn take_aliases(doc_comments: &mut Option<String>) -> Vec<String> {
+    take_tag_lines(doc_comments, &ALIAS_TAG, |captures| captures[1].to_owned())
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// `@renamed-from OldName [in version]` line of doc comments.
+static RENAMED_FROM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*@renamed-from[ \t]+(\S+)(?:[ \t]+(?:in[ \t]+)?(.*?))?[ \t]*(?:\n|$)")
+        .unwrap()
+});
+
+/// Removes `@renamed-from OldName [in version]` lines from doc comments and returns former
+/// names they declare, with optional versions of renames.
+fn take_renames(doc_comments: &mut Option<String>) -> Vec<(String, Option<String>)> {
+    take_tag_lines(doc_comments, &RENAMED_FROM, |captures| {
+        let version = captures
+            .get(2)
+            .map(|version| version.as_str().to_owned())
+            .filter(|version| !version.is_empty());
+        (captures[1].to_owned(), version)
+    })
+}
+
+/// Moves former names declared in doc comments into aliases, along with versions of renames.
+fn take_former_names(
+    doc_comments: &mut Option<String>,
+    aliases: &mut Vec<String>,
+    renamed_in: &mut BTreeMap<String, String>,
+) {
+    let mut add = |name: String| {
+        if !aliases.contains(&name) {
+            aliases.push(name);
+        }
+    };
+    for name in take_aliases(doc_comments) {
+        add(name);
+    }
+    for (name, version) in take_renames(doc_comments) {
+        if let Some(version) = version {
+            renamed_in.insert(name.to_owned(), version);
+        }
+        add(name);
+    }
+}
+
+/// `@owner Name` line of doc comments.
+static OWNER_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@owner[ \t]+(.*?)[ \t]*(?:\n|$)").unwrap());
+
+/// Removes `@owner` lines from doc comments and returns owners they name.
+fn take_owners(doc_comments: &mut Option<String>) -> Vec<String> {
+    take_tag_lines(doc_comments, &OWNER_LINE, |captures| captures[1].to_owned())
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// `@deprecated` line of doc comments.
+static DEPRECATED_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@deprecated\b").unwrap());
+
+/// Tells if symbol is marked deprecated in its specifiers or doc comments.
+fn is_deprecated(specifiers: &Option<Specifiers>, doc_comments: &Option<String>) -> bool {
+    specifiers.as_ref().is_some_and(|specifiers| {
+        specifiers.attribute("Deprecated").is_some()
+            || specifiers.meta("DeprecatedFunction").is_some()
+            || specifiers.meta("DeprecatedProperty").is_some()
+    }) || doc_comments
+        .as_deref()
+        .is_some_and(|doc_comments| DEPRECATED_LINE.is_match(doc_comments))
+}
+
+/// `@experimental`, `@beta` or `@stable` line of doc comments.
+static STABILITY_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@(experimental|beta|stable)[ \t]*(?:\n|$)").unwrap());
+
+/// `@example Name` line of doc comments.
+static EXAMPLE_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@example[ \t]+(.*?)[ \t]*$").unwrap());
+
+/// `@tag name` line of doc comments.
+static TAG_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@tag[ \t]+(\S+)[ \t]*(?:\n|$)").unwrap());
+
+/// Removes `@tag` lines from doc comments and returns tags they declare.
+fn take_tags(doc_comments: &mut Option<String>) -> Vec<String> {
+    take_tag_lines(doc_comments, &TAG_LINE, |captures| captures[1].to_owned())
+}
+
+/// Removes stability lines from doc comments and returns last stability they declare.
+fn take_stability(doc_comments: &mut Option<String>) -> Option<Stability> {
+    take_tag_lines(doc_comments, &STABILITY_LINE, |captures| {
+        match &captures[1] {
+            "experimental" => Stability::Experimental,
+            "beta" => Stability::Beta,
+            _ => Stability::Stable,
+        }
+    })
+    .pop()
+}
+
+/// Takes front matter, written between `---` lines at the very start of book page, out of
+/// page content. Only `key: value` lines are read, keys that are not known get ignored.
+fn take_front_matter(content: &mut String) -> Option<FrontMatter> {
+    let rest = content
+        .strip_prefix("---")?
+        .strip_prefix('\n')
+        .or_else(|| {
+            content
+                .strip_prefix("---")
+                .and_then(|rest| rest.strip_prefix("\r\n"))
+        })?;
+    let mut lines = rest.split_inclusive('\n');
+    let mut length = content.len() - rest.len();
+    let mut result = FrontMatter::default();
+    loop {
+        let line = lines.next()?;
+        length += line.len();
+        let line = line.trim();
+        if line == "---" {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "header" => result.header = value != "false",
+            "footer" => result.footer = value != "false",
+            "tags" => {
+                result.tags = value
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|tag| tag.trim().trim_matches(['"', '\'']).to_owned())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    content.replace_range(..length, "");
+    Some(result)
+}
+
+/// `@faq Question` line of doc comments, followed by lines of its answer.
+static FAQ_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*@faq[ \t]+(?:Q:[ \t]*)?(.*?)[ \t]*(?:\n|$)((?:[ \t]*[^@\s].*(?:\n|$))*)",
+    )
+    .unwrap()
+});
+
+/// Takes `@faq Question` blocks out of doc comments, each one answered by lines following its
+/// question up to empty line or next doc tag. Questions and answers can start with `Q:` and
+/// `A:` markers.
+fn take_faqs(doc_comments: &mut Option<String>) -> Vec<Faq> {
+    take_tag_lines(doc_comments, &FAQ_BLOCK, |captures| {
+        let answer = captures[2].trim();
+        Faq {
+            question: captures[1].to_owned(),
+            answer: answer
+                .strip_prefix("A:")
+                .unwrap_or(answer)
+                .trim_start()
+                .to_owned(),
+        }
+    })
+}
+
+/// `@throws` or `@fails` line, or `<exception>` tag of doc comments.
+static FAILURE_MODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*@throws[ \t]+(\S+)[ \t]*(.*?)[ \t]*(?:\n|$)|^[ \t]*@fails[ \t]+(.*?)[ \t]*(?:\n|$)|(?s:<exception\s+cref\s*=\s*"([^"]*)"\s*>\s*(.*?)\s*</exception>[ \t]*\n?)"#,
+    )
+    .unwrap()
+});
+
+/// Removes failure mode tags from doc comments and returns failure modes they declare, in
+/// order of appearance.
+fn take_failure_modes(doc_comments: &mut Option<String>) -> Vec<FailureMode> {
+    take_tag_lines(doc_comments, &FAILURE_MODE, |captures| {
+        if let Some(exception) = captures.get(1) {
+            FailureMode {
+                exception: Some(exception.as_str().to_owned()),
+                description: captures[2].to_owned(),
+            }
+        } else if let Some(description) = captures.get(3) {
+            FailureMode {
+                exception: None,
+                description: description.as_str().to_owned(),
+            }
+        } else {
+            FailureMode {
+                exception: Some(captures[4].to_owned()),
+                description: captures[5].split_whitespace().collect::<Vec<_>>().join(" "),
+            }
+        }
+    })
+}
+
+/// `[unit]` at start of argument doc comments.
+static LEADING_UNIT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[ \t]*\[([^\]\n]+)\](?:[ \t]+|\n|$)").unwrap());
+
+/// `@param Name [unit] description` line of doc comments, with optional direction.
+static PARAM_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*@param(?:\[[ \t]*(in|out|in[ \t]*,[ \t]*out)[ \t]*\])?[ \t]+(\w+)(?:[ \t]+\[([^\]\n]*)\])?[ \t]*(.*?)[ \t]*(?:\n|$)",
+    )
+    .unwrap()
+});
+
+/// `<param name="Name" unit="unit">` tag of doc comments.
+static PARAM_UNIT_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<param\s+name\s*=\s*"(\w+)"\s+unit\s*=\s*"([^"]*)"\s*>"#).unwrap());
+
+fn take_parameter_docs(doc_comments: &mut Option<String>, arguments: &mut [Argument]) {
+    for argument in arguments.iter_mut() {
+        if let Some(comments) = &mut argument.doc_comments
+            && let Some(captures) = LEADING_UNIT.captures(comments)
+        {
+            argument.unit = Some(captures[1].trim().to_owned());
+            *comments = comments[captures[0].len()..].to_owned();
+        }
+    }
+    let Some(comments) = doc_comments.as_mut() else {
+        return;
+    };
+    if comments.contains("@param") {
+        *comments = PARAM_LINE
+            .replace_all(comments, |captures: &Captures| {
+                let Some(argument) = arguments
+                    .iter_mut()
+                    .find(|argument| argument.name.as_deref() == Some(&captures[2]))
+                else {
+                    return captures[0].to_owned();
+                };
+                if let Some(direction) = captures.get(1) {
+                    argument.direction = Some(match direction.as_str() {
+                        "in" => ArgumentDirection::In,
+                        "out" => ArgumentDirection::Out,
+                        _ => ArgumentDirection::InOut,
+                    });
+                }
+                if let Some(unit) = captures.get(3) {
+                    argument.unit = Some(unit.as_str().trim().to_owned());
+                }
+                if !captures[4].is_empty() {
+                    argument.doc_comments = Some(match &argument.doc_comments {
+                        Some(existing) => format!("{}\n\n{}", existing, &captures[4]),
+                        None => captures[4].to_owned(),
+                    });
+                }
+                String::default()
+            })
+            .trim_end()
+            .to_owned();
+    }
+    if comments.contains("unit=") {
+        *comments = PARAM_UNIT_TAG
+            .replace_all(comments, |captures: &Captures| {
+                if let Some(argument) = arguments
+                    .iter_mut()
+                    .find(|argument| argument.name.as_deref() == Some(&captures[1]))
+                {
+                    argument.unit = Some(captures[2].trim().to_owned());
+                }
+                format!("<param name=\"{}\">", &captures[1])
+            })
+            .into_owned();
+    }
+    if comments.trim().is_empty() {
+        *doc_comments = None;
+    }
+}
+
+/// Regular expression matching paths that glob pattern matches: `*` matches within single
+/// path segment and `**` across segments, so `**/` also matches no segments at all.
+pub(crate) fn glob_expression(pattern: &str) -> String {
+    let mut result = String::new();
+    let mut rest = pattern;
+    while let Some(character) = rest.chars().next() {
+        if let Some(tail) = rest.strip_prefix("**/") {
+            result.push_str("(?:.*/)?");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("**") {
+            result.push_str(".*");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix('*') {
+            result.push_str("[^/]*");
+            rest = tail;
+        } else {
+            result.push_str(&regex::escape(&character.to_string()));
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+    result
+}
+
+/// Rules of CODEOWNERS-like file: path pattern followed by owners on each line. Patterns
+/// containing `/` (other than trailing one) are anchored at input directory, others match at
+/// any depth. `*` matches within single path segment and `**` across segments.
+fn code_owners_rules(content: &str) -> Vec<(Regex, Vec<String>)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?;
+            let anchored = pattern.trim_end_matches('/').contains('/');
+            let directory = pattern.ends_with('/');
+            let pattern = pattern.trim_matches('/');
+            let mut expression = if anchored {
+                "^".to_owned()
+            } else {
+                "(?:^|/)".to_owned()
+            };
+            expression.push_str(&glob_expression(pattern));
+            // patterns also match everything inside directories they name
+            expression.push_str(if directory { "/" } else { "(?:/|$)" });
+            let owners = parts.map(str::to_owned).collect();
+            Some((Regex::new(&expression).ok()?, owners))
+        })
+        .collect()
+}
+
+/// `//// [snippet: id]` line of example source.
+static SNIPPET_START: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*////\s*\[\s*snippet\s*:\s*(\w+)\s*\]\s*$").unwrap());
+
+/// `//// [/snippet]` line of example source.
+static SNIPPET_END: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*////\s*\[\s*/\s*snippet\s*\]\s*$").unwrap());
+
+/// Snippets marked in C++ source with `//// [snippet: id]` and `//// [/snippet]` lines, with
+/// common indentation removed. Unlike headers, example sources are not parsed, so markers are
+/// found line by line.
+fn example_snippets(content: &str) -> Vec<(String, String)> {
+    let mut result = vec![];
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in content.lines() {
+        if let Some(captures) = SNIPPET_START.captures(line) {
+            current = Some((captures[1].to_owned(), vec![]));
+        } else if SNIPPET_END.is_match(line) {
+            if let Some((id, lines)) = current.take() {
+                let level = lines
+                    .iter()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.chars().take_while(|c| c.is_whitespace()).count())
+                    .min()
+                    .unwrap_or_default();
+                let snippet = lines
+                    .iter()
+                    .map(|line| line.chars().skip(level).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                result.push((id, snippet));
+            }
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    result
+}
+
+/// Short single-line description of a symbol: content of `<summary>` tag if present, otherwise
+/// first sentence or paragraph of doc comments.
+pub fn doc_brief(doc_comments: &Option<String>, mode: BriefMode) -> Option<String> {
+    let comments = doc_comments.as_deref()?;
+    let comments = match (comments.find("<summary>"), comments.find("</summary>")) {
+        (Some(start), Some(end)) if start < end => &comments[(start + "<summary>".len())..end],
+        _ => comments,
+    };
+    let paragraph = comments
+        .trim()
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let result = match mode {
+        BriefMode::FirstParagraph => paragraph,
+        BriefMode::FirstSentence => paragraph
+            .match_indices(['.', '!', '?'])
+            .map(|(index, _)| index + 1)
+            .find(|index| {
+                paragraph[*index..]
+                    .chars()
+                    .next()
+                    .map(|c| c.is_whitespace())
+                    .unwrap_or(true)
+            })
+            .map(|index| paragraph[..index].to_owned())
+            .unwrap_or(paragraph),
+    };
+    Some(result).filter(|result| !result.is_empty())
+}
+
+/// Presentation options applied when rendering C++ signatures.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SignatureOptions {
+    pub strip_api_macros: bool,
+}
+
+/// `<!-- if ... -->`, `<!-- else -->` or `<!-- endif -->` directive.
+static CONDITION_DIRECTIVE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<!--\s*(?:if\s+(profile|tag)\s*(!?=)\s*(\S+?)|(else)|(endif))\s*-->").unwrap()
+});
+
+/// Evaluates `<!-- if profile=internal -->`, `<!-- if tag=beta -->` (or `!=` for negation),
+/// `<!-- else -->` and `<!-- endif -->` blocks, which can be nested. Directives standing on
+/// their own lines are removed along with these lines.
+fn evaluate_conditions(
+    content: &str,
+    profile: Option<&str>,
+    tags: &[String],
+) -> Result<String, String> {
+    let mut result = String::with_capacity(content.len());
+    // every open block remembers if its current branch is visible and if its condition held
+    let mut blocks: Vec<(bool, bool)> = vec![];
+    let visible = |blocks: &[(bool, bool)]| blocks.last().is_none_or(|(visible, _)| *visible);
+    let mut position = 0;
+    for caps in CONDITION_DIRECTIVE.captures_iter(content) {
+        let found = caps.get(0).unwrap();
+        let line_start = content[..found.start()]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let rest = &content[found.end()..];
+        let line_end = rest.find('\n').map_or(rest.len(), |index| index + 1);
+        let (start, end) = if content[line_start..found.start()].trim().is_empty()
+            && rest[..line_end].trim().is_empty()
+        {
+            (line_start, found.end() + line_end)
+        } else {
+            (found.start(), found.end())
+        };
+        if visible(&blocks) {
+            result.push_str(&content[position..start]);
+        }
+        let line = content[..found.start()].matches('\n').count() + 1;
+        if let Some(kind) = caps.get(1) {
+            let value = &caps[3];
+            let holds = match kind.as_str() {
+                "profile" => profile == Some(value),
+                _ => tags.iter().any(|tag| tag == value),
+            } == (&caps[2] == "=");
+            blocks.push((visible(&blocks) && holds, holds));
+        } else if caps.get(4).is_some() {
+            let Some((_, holds)) = blocks.pop() else {
+                return Err(format!("`else` without `if` at line {}", line));
+            };
+            blocks.push((visible(&blocks) && !holds, !holds));
+        } else if blocks.pop().is_none() {
+            return Err(format!("`endif` without `if` at line {}", line));
+        }
+        position = end;
+    }
+    if !blocks.is_empty() {
+        return Err("`if` without `endif`".to_owned());
+    }
+    result.push_str(&content[position..]);
+    Ok(result)
+}
+
+/// `@include path` line of doc comments.
+static INCLUDE_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*@include[ \t]+(\S+)[ \t]*$").unwrap());
+
+fn expand_includes(
+    content: &str,
+    root: &Path,
+    fragments: &mut HashMap<PathBuf, String>,
+    stack: &mut Vec<PathBuf>,
+) -> String {
+    INCLUDE_LINE
+        .replace_all(content, |caps: &Captures| {
+            let path = root.join(&caps[1]);
+            if stack.contains(&path) {
+                panic!("Doc comment fragment includes itself: {:?}", path);
+            }
+            let fragment = fragments
+                .entry(path.clone())
+                .or_insert_with(|| {
+                    crate::read_file(&path).unwrap_or_else(|_| {
+                        panic!("Could not read included doc fragment: {:?}", path)
+                    })
+                })
+                .trim_end()
+                .to_owned();
+            stack.push(path);
+            let result = expand_includes(&fragment, root, fragments, stack);
+            stack.pop();
+            result
+        })
+        .into_owned()
+}
+
+/// `{{kbd:...}}` or `{{menu:...}}` markup.
+static UI_MARKUP: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{(kbd|menu):([^{}\n]*)\}\}").unwrap());
+
+/// Replaces `{{kbd:Ctrl+Alt+P}}` keyboard shortcuts with `<kbd>` keys and
+/// `{{menu:Edit > Project Settings}}` UI paths with bold items, so they look the same in every
+/// backend.
+fn expand_ui_markup(content: &str) -> String {
+    UI_MARKUP
+        .replace_all(content, |caps: &Captures| {
+            if &caps[1] == "kbd" {
+                keyboard_keys(&caps[2])
+                    .into_iter()
+                    .map(|key| format!("<kbd>{}</kbd>", key))
+                    .collect::<Vec<_>>()
+                    .join("+")
+            } else {
+                caps[2]
+                    .split('>')
+                    .map(|item| format!("**{}**", item.trim()))
+                    .collect::<Vec<_>>()
+                    .join(" > ")
+            }
+        })
+        .into_owned()
+}
+
+/// Keys of keyboard shortcut separated with `+`, which can be a key on its own (`Ctrl++`).
+fn keyboard_keys(shortcut: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut rest = shortcut.trim();
+    while !rest.is_empty() {
+        // first character always belongs to key, so `+` key is not taken for separator
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '+')
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        result.push(rest[..end].trim());
+        rest = rest[end..]
+            .strip_prefix('+')
+            .unwrap_or_default()
+            .trim_start();
+    }
+    result
+}
+
+/// Parameter list of signature line, put on that line if whole line fits within given column
+/// limit, or one parameter per line otherwise. Without limit parameters always go one per line.
+fn layout_parameters(
+    head: &str,
+    parameters: &[String],
+    tail: &str,
+    width: Option<usize>,
+) -> String {
+    let single_line = format!("{}({}){}", head, parameters.join(", "), tail);
+    if parameters.is_empty() || width.is_some_and(|width| single_line.chars().count() <= width) {
+        return single_line;
+    }
+    format!("{}(\n    {}\n){}", head, parameters.join(",\n    "), tail)
+}
+
+/// Full sample source files from examples directory that share file name stem.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Example {
+    pub name: String,
+    /// Content of example files, with snippet and ignore markers removed, mapped by their paths
+    /// relative to examples directory.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Proxy<T> {
     #[serde(default)]
@@ -17,29 +1033,812 @@ pub struct Proxy<T> {
     pub item: T,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
-pub struct Document {
-    #[serde(default)]
-    pub enums: Vec<Enum>,
-    #[serde(default)]
-    pub classes: Vec<StructClass>,
-    #[serde(default)]
-    pub structs: Vec<StructClass>,
-    #[serde(default)]
-    pub functions: Vec<Function>,
-    #[serde(default)]
-    pub delegates: Vec<Delegate>,
-    #[serde(default)]
-    pub book: HashMap<String, String>,
-    #[serde(default)]
-    pub snippets: HashMap<String, String>,
-    #[serde(skip)]
-    pub proxy_functions: Vec<Proxy<Function>>,
-    #[serde(skip)]
-    pub proxy_properties: Vec<Proxy<Property>>,
-}
+/// Fields shared by symbols that have their own pages, borrowed together.
+struct SymbolDocs<'a> {
+    filename: &'a str,
+    header_path: &'a mut Option<String>,
+    doc_comments: &'a mut Option<String>,
+    stability: &'a mut Option<Stability>,
+    tags: &'a mut Vec<String>,
+    aliases: &'a mut Vec<String>,
+    renamed_in: &'a mut BTreeMap<String, String>,
+    owners: &'a mut Vec<String>,
+    faqs: &'a mut Vec<Faq>,
+}
+
+/// Borrows [`SymbolDocs`] of enum, struct, class, function or delegate, which have these fields
+/// named the same way.
+macro_rules! symbol_docs {
+    ($item:expr) => {
+        SymbolDocs {
+            filename: &$item.filename,
+            header_path: &mut $item.header_path,
+            doc_comments: &mut $item.doc_comments,
+            stability: &mut $item.stability,
+            tags: &mut $item.tags,
+            aliases: &mut $item.aliases,
+            renamed_in: &mut $item.renamed_in,
+            owners: &mut $item.owners,
+            faqs: &mut $item.faqs,
+        }
+    };
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Document {
+    #[serde(default)]
+    pub enums: Vec<Enum>,
+    #[serde(default)]
+    pub classes: Vec<StructClass>,
+    #[serde(default)]
+    pub structs: Vec<StructClass>,
+    #[serde(default)]
+    pub functions: Vec<Function>,
+    #[serde(default)]
+    pub delegates: Vec<Delegate>,
+    #[serde(default)]
+    pub book: HashMap<String, String>,
+    #[serde(default)]
+    pub snippets: HashMap<String, String>,
+    #[serde(default)]
+    pub examples: BTreeMap<String, Example>,
+    /// Definitions of terms from `glossary.toml` files of book, mapped by terms.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub glossary: BTreeMap<String, String>,
+    /// Front matter of book pages, mapped by their paths.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub front_matter: BTreeMap<String, FrontMatter>,
+    /// Proxies get resolved during processing, kept only in intermediate representation of
+    /// parsed document.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proxy_functions: Vec<Proxy<Function>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proxy_properties: Vec<Proxy<Property>>,
+    #[serde(skip)]
+    pub skipped: Vec<SkippedSymbol>,
+    /// First slugs (in byte order) of symbols of every reference directory, mapped by directory
+    /// and lowercase slug, so page stems are not looked up across all symbols every time. Filled
+    /// when page stems are first needed, once document got processed.
+    #[serde(skip)]
+    pub first_page_slugs: OnceCell<HashMap<(String, String), String>>,
+}
+
+impl Document {
+    /// Moves all items of other document into this one.
+    pub fn append(&mut self, other: Document) {
+        self.enums.extend(other.enums);
+        self.classes.extend(other.classes);
+        self.structs.extend(other.structs);
+        self.functions.extend(other.functions);
+        self.delegates.extend(other.delegates);
+        self.book.extend(other.book);
+        self.snippets.extend(other.snippets);
+        self.examples.extend(other.examples);
+        self.glossary.extend(other.glossary);
+        self.front_matter.extend(other.front_matter);
+        self.proxy_functions.extend(other.proxy_functions);
+        self.proxy_properties.extend(other.proxy_properties);
+        self.skipped.extend(other.skipped);
+    }
+
+    /// Parts of enums, structs, classes, functions and delegates, the symbols that have their own
+    /// pages, that doc tags get extracted into.
+    fn symbols_mut(&mut self) -> Vec<SymbolDocs<'_>> {
+        let mut result = vec![];
+        result.extend(self.enums.iter_mut().map(|item| symbol_docs!(item)));
+        result.extend(
+            self.structs
+                .iter_mut()
+                .chain(self.classes.iter_mut())
+                .map(|item| symbol_docs!(item)),
+        );
+        result.extend(self.functions.iter_mut().map(|item| symbol_docs!(item)));
+        result.extend(self.delegates.iter_mut().map(|item| symbol_docs!(item)));
+        result
+    }
+
+    /// All doc comments of documented items, including their members and arguments.
+    pub fn doc_comments_mut(&mut self) -> Vec<&mut String> {
+        let mut result = vec![];
+        for item in &mut self.enums {
+            result.extend(item.doc_comments.as_mut());
+        }
+        for item in self.classes.iter_mut().chain(self.structs.iter_mut()) {
+            result.extend(item.doc_comments.as_mut());
+            for property in &mut item.properties {
+                result.extend(property.doc_comments.as_mut());
+            }
+            for function in item.methods.iter_mut().chain(item.constructors.iter_mut()) {
+                result.extend(function.doc_comments.as_mut());
+                for argument in &mut function.arguments {
+                    result.extend(argument.doc_comments.as_mut());
+                }
+            }
+        }
+        for item in &mut self.functions {
+            result.extend(item.doc_comments.as_mut());
+            for argument in &mut item.arguments {
+                result.extend(argument.doc_comments.as_mut());
+            }
+        }
+        for item in &mut self.delegates {
+            result.extend(item.doc_comments.as_mut());
+            for argument in &mut item.arguments {
+                result.extend(argument.doc_comments.as_mut());
+            }
+        }
+        result
+    }
+
+    /// Doc comments of all symbols and content of all book pages, along with their location:
+    /// header file and symbol path for doc comments, or path of book page.
+    pub fn doc_texts(&self) -> Vec<(String, &str)> {
+        fn push<'a>(
+            result: &mut Vec<(String, &'a str)>,
+            filename: &str,
+            symbol: String,
+            text: &'a Option<String>,
+        ) {
+            if let Some(text) = text {
+                result.push((format!("{} (`{}`)", filename, symbol), text.as_str()));
+            }
+        }
+
+        let mut result = vec![];
+        for item in &self.enums {
+            push(
+                &mut result,
+                &item.filename,
+                item.name.to_owned(),
+                &item.doc_comments,
+            );
+        }
+        for item in self.structs.iter().chain(self.classes.iter()) {
+            push(
+                &mut result,
+                &item.filename,
+                item.name.to_owned(),
+                &item.doc_comments,
+            );
+            for property in &item.properties {
+                let symbol = format!("{}::{}", item.name, property.name);
+                push(&mut result, &item.filename, symbol, &property.doc_comments);
+            }
+            for function in item.constructors.iter().chain(item.methods.iter()) {
+                let symbol = format!("{}::{}", item.name, function.name);
+                push(
+                    &mut result,
+                    &item.filename,
+                    symbol.to_owned(),
+                    &function.doc_comments,
+                );
+                for argument in &function.arguments {
+                    let symbol = format!(
+                        "{}({})",
+                        symbol,
+                        argument.name.as_deref().unwrap_or_default()
+                    );
+                    push(&mut result, &item.filename, symbol, &argument.doc_comments);
+                }
+            }
+        }
+        for item in &self.functions {
+            push(
+                &mut result,
+                &item.filename,
+                item.name.to_owned(),
+                &item.doc_comments,
+            );
+            for argument in &item.arguments {
+                let symbol = format!(
+                    "{}({})",
+                    item.name,
+                    argument.name.as_deref().unwrap_or_default()
+                );
+                push(&mut result, &item.filename, symbol, &argument.doc_comments);
+            }
+        }
+        for item in &self.delegates {
+            push(
+                &mut result,
+                &item.filename,
+                item.name.to_owned(),
+                &item.doc_comments,
+            );
+            for argument in &item.arguments {
+                let symbol = format!(
+                    "{}({})",
+                    item.name,
+                    argument.name.as_deref().unwrap_or_default()
+                );
+                push(&mut result, &item.filename, symbol, &argument.doc_comments);
+            }
+        }
+        let mut pages = self
+            .book
+            .iter()
+            .filter(|(path, _)| path.ends_with(".md"))
+            .collect::<Vec<_>>();
+        pages.sort();
+        for (path, content) in pages {
+            result.push((path.to_owned(), content.as_str()));
+        }
+        result
+    }
+
+    pub fn sanitize_html_in_docs(&mut self) {
+        for content in self.doc_comments_mut() {
+            *content = sanitize_html(content);
+        }
+    }
+
+    /// Sets defaults of properties that are assigned in constructor bodies found in given C++
+    /// source, keeping the first value found for every property.
+    pub fn apply_constructor_defaults(&mut self, source: &str) {
+        for item in self.structs.iter_mut().chain(self.classes.iter_mut()) {
+            if !source.contains(&item.name) {
+                continue;
+            }
+            for body in constructor_bodies(source, &item.name) {
+                for (name, value) in member_assignments(body) {
+                    if let Some(property) = item
+                        .properties
+                        .iter_mut()
+                        .find(|property| property.name == name)
+                        && property.constructor_default.is_none()
+                    {
+                        property.constructor_default = Some(value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Marks properties registered for replication in `GetLifetimeReplicatedProps` found in
+    /// given C++ source.
+    pub fn apply_lifetime_replication(&mut self, source: &str) {
+        for (owner, name, replication) in lifetime_replications(source) {
+            if let Some(property) = self
+                .structs
+                .iter_mut()
+                .chain(self.classes.iter_mut())
+                .find(|item| item.name == owner)
+                .and_then(|item| {
+                    item.properties
+                        .iter_mut()
+                        .find(|property| property.name == name)
+                })
+            {
+                property.lifetime_replication = Some(replication);
+            }
+        }
+    }
+
+    /// Removes symbols and members that are not documented without `show_all` or have
+    /// visibility that is not documented, recording them as skipped.
+    pub fn remove_hidden(&mut self, settings: &RenderSettings) {
+        self.extract_tags();
+        let reason = |visibility: Visibility| {
+            if visibility.can_export(settings) {
+                SkipReason::Undocumented
+            } else {
+                SkipReason::Visibility(visibility)
+            }
+        };
+        let mut skipped = vec![];
+        let mut skip = |kind: String, name: String, filename: &str, reason: SkipReason| {
+            skipped.push(SkippedSymbol {
+                kind,
+                name,
+                filename: filename.to_owned(),
+                reason,
+            })
+        };
+        self.enums.retain(|item| {
+            let keep = item.can_export(settings);
+            if !keep {
+                skip(
+                    "enum".to_owned(),
+                    item.name.to_owned(),
+                    &item.filename,
+                    reason(Visibility::Public),
+                );
+            }
+            keep
+        });
+        for items in [&mut self.structs, &mut self.classes] {
+            items.retain_mut(|item| {
+                let owner = item.name.to_owned();
+                let filename = item.filename.to_owned();
+                item.properties.retain(|property| {
+                    let keep = property.can_export(settings);
+                    if !keep {
+                        let name = format!("{}::{}", owner, property.name);
+                        skip(
+                            "property".to_owned(),
+                            name,
+                            &filename,
+                            reason(property.visibility),
+                        );
+                    }
+                    keep
+                });
+                for functions in [&mut item.constructors, &mut item.methods] {
+                    functions.retain(|function| {
+                        let keep = function.can_export(settings);
+                        if !keep {
+                            let name = format!("{}::{}", owner, function.name);
+                            skip(
+                                "method".to_owned(),
+                                name,
+                                &filename,
+                                reason(function.visibility),
+                            );
+                        }
+                        keep
+                    });
+                }
+                let keep = item.can_export(settings);
+                if !keep {
+                    skip(
+                        item.mode.signature(),
+                        owner,
+                        &filename,
+                        reason(Visibility::Public),
+                    );
+                }
+                keep
+            });
+        }
+        self.functions.retain(|item| {
+            let keep = item.can_export(settings);
+            if !keep {
+                skip(
+                    "function".to_owned(),
+                    item.name.to_owned(),
+                    &item.filename,
+                    reason(item.visibility),
+                );
+            }
+            keep
+        });
+        self.delegates.retain(|item| {
+            let keep = item.can_export(settings);
+            if !keep {
+                skip(
+                    "delegate".to_owned(),
+                    item.name.to_owned(),
+                    &item.filename,
+                    reason(Visibility::Public),
+                );
+            }
+            keep
+        });
+        self.skipped.extend(skipped);
+        self.remove_tagged(settings);
+    }
+
+    /// Moves tags declared with `@tag` lines out of doc comments of symbols that have their own
+    /// pages.
+    pub fn extract_tags(&mut self) {
+        for symbol in self.symbols_mut() {
+            symbol.tags.extend(take_tags(symbol.doc_comments));
+        }
+    }
+
+    /// Removes symbols left out by `include_tags` and `exclude_tags` of given settings.
+    fn remove_tagged(&mut self, settings: &RenderSettings) {
+        let mut skipped = vec![];
+        let mut skip = |kind: String, name: String, filename: &str| {
+            skipped.push(SkippedSymbol {
+                kind,
+                name,
+                filename: filename.to_owned(),
+                reason: SkipReason::Tags,
+            })
+        };
+        self.enums.retain(|item| {
+            let keep = !settings.hides_tags(&item.tags);
+            if !keep {
+                skip("enum".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        for items in [&mut self.structs, &mut self.classes] {
+            items.retain(|item| {
+                let keep = !settings.hides_tags(&item.tags);
+                if !keep {
+                    skip(item.mode.signature(), item.name.to_owned(), &item.filename);
+                }
+                keep
+            });
+        }
+        self.functions.retain(|item| {
+            let keep = !settings.hides_tags(&item.tags);
+            if !keep {
+                skip("function".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.delegates.retain(|item| {
+            let keep = !settings.hides_tags(&item.tags);
+            if !keep {
+                skip("delegate".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.skipped.extend(skipped);
+    }
+
+    /// Removes symbols and members marked deprecated with `@deprecated` doc comment line,
+    /// `Deprecated` specifier or `DeprecatedFunction` and `DeprecatedProperty` metadata.
+    pub fn remove_deprecated(&mut self) {
+        let mut skipped = vec![];
+        let mut skip = |kind: String, name: String, filename: &str| {
+            skipped.push(SkippedSymbol {
+                kind,
+                name,
+                filename: filename.to_owned(),
+                reason: SkipReason::Deprecated,
+            })
+        };
+        self.enums.retain(|item| {
+            let keep = !is_deprecated(&item.specifiers, &item.doc_comments);
+            if !keep {
+                skip("enum".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        for items in [&mut self.structs, &mut self.classes] {
+            items.retain_mut(|item| {
+                let owner = item.name.to_owned();
+                let filename = item.filename.to_owned();
+                item.properties.retain(|property| {
+                    let keep = !is_deprecated(&property.specifiers, &property.doc_comments);
+                    if !keep {
+                        let name = format!("{}::{}", owner, property.name);
+                        skip("property".to_owned(), name, &filename);
+                    }
+                    keep
+                });
+                for functions in [&mut item.constructors, &mut item.methods] {
+                    functions.retain(|function| {
+                        let keep = !is_deprecated(&function.specifiers, &function.doc_comments);
+                        if !keep {
+                            let name = format!("{}::{}", owner, function.name);
+                            skip("method".to_owned(), name, &filename);
+                        }
+                        keep
+                    });
+                }
+                let keep = !is_deprecated(&item.specifiers, &item.doc_comments);
+                if !keep {
+                    skip(item.mode.signature(), owner, &filename);
+                }
+                keep
+            });
+        }
+        self.functions.retain(|item| {
+            let keep = !is_deprecated(&item.specifiers, &item.doc_comments);
+            if !keep {
+                skip("function".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.delegates.retain(|item| {
+            let keep = !is_deprecated(&item.specifiers, &item.doc_comments);
+            if !keep {
+                skip("delegate".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.skipped.extend(skipped);
+    }
+
+    pub fn remove_editor_only(&mut self) {
+        for item in self.structs.iter().chain(self.classes.iter()) {
+            if item.is_editor_only {
+                self.skipped.push(SkippedSymbol {
+                    kind: item.mode.signature(),
+                    name: item.name.to_owned(),
+                    filename: item.filename.to_owned(),
+                    reason: SkipReason::EditorOnly,
+                });
+            }
+        }
+        self.structs.retain(|item| !item.is_editor_only);
+        self.classes.retain(|item| !item.is_editor_only);
+    }
+
+    /// Removes enums, structs, classes and functions declared without reflection macros, i.e.
+    /// plain C++ ones, recording them as skipped.
+    pub fn remove_unreflected(&mut self) {
+        let skipped = self
+            .enums
+            .iter()
+            .filter(|item| item.specifiers.is_none())
+            .map(|item| ("enum".to_owned(), &item.name, &item.filename))
+            .chain(
+                self.structs
+                    .iter()
+                    .chain(self.classes.iter())
+                    .filter(|item| item.specifiers.is_none())
+                    .map(|item| (item.mode.signature(), &item.name, &item.filename)),
+            )
+            .chain(
+                self.functions
+                    .iter()
+                    .filter(|item| item.specifiers.is_none())
+                    .map(|item| ("function".to_owned(), &item.name, &item.filename)),
+            )
+            .map(|(kind, name, filename)| SkippedSymbol {
+                kind,
+                name: name.to_owned(),
+                filename: filename.to_owned(),
+                reason: SkipReason::Unreflected,
+            })
+            .collect::<Vec<_>>();
+        self.skipped.extend(skipped);
+        self.enums.retain(|item| item.specifiers.is_some());
+        self.structs.retain(|item| item.specifiers.is_some());
+        self.classes.retain(|item| item.specifiers.is_some());
+        self.functions.retain(|item| item.specifiers.is_some());
+    }
+
+    /// Moves stability declared with `@experimental`, `@beta` or `@stable` lines out of doc
+    /// comments of symbols that have their own pages and of their members.
+    pub fn extract_stability(&mut self) {
+        for item in self.structs.iter_mut().chain(self.classes.iter_mut()) {
+            for property in &mut item.properties {
+                property.stability = take_stability(&mut property.doc_comments);
+            }
+            for function in item.methods.iter_mut().chain(item.constructors.iter_mut()) {
+                function.stability = take_stability(&mut function.doc_comments);
+            }
+        }
+        for symbol in self.symbols_mut() {
+            *symbol.stability = take_stability(symbol.doc_comments);
+        }
+    }
+
+    /// Moves `@throws Type description`, `@fails description` lines and
+    /// `<exception cref="Type">description</exception>` tags out of doc comments of functions
+    /// and methods.
+    pub fn extract_failure_modes(&mut self) {
+        let functions = self.functions.iter_mut().chain(
+            self.structs
+                .iter_mut()
+                .chain(self.classes.iter_mut())
+                .flat_map(|item| item.methods.iter_mut().chain(item.constructors.iter_mut())),
+        );
+        for item in functions {
+            item.failure_modes = take_failure_modes(&mut item.doc_comments);
+        }
+    }
+
+    /// Moves `@faq` blocks out of doc comments of enums, structs, classes, their members,
+    /// functions and delegates.
+    pub fn extract_faqs(&mut self) {
+        for item in self.structs.iter_mut().chain(self.classes.iter_mut()) {
+            for property in &mut item.properties {
+                property.faqs = take_faqs(&mut property.doc_comments);
+            }
+            for function in item.methods.iter_mut().chain(item.constructors.iter_mut()) {
+                function.faqs = take_faqs(&mut function.doc_comments);
+            }
+        }
+        for symbol in self.symbols_mut() {
+            *symbol.faqs = take_faqs(symbol.doc_comments);
+        }
+    }
+
+    /// Takes front matter out of book pages.
+    pub fn extract_front_matter(&mut self) {
+        for (path, content) in &mut self.book {
+            if let Some(front_matter) = take_front_matter(content) {
+                self.front_matter.insert(path.to_owned(), front_matter);
+            }
+        }
+    }
+
+    /// Moves `@param Name [unit] description` lines of function and delegate doc comments into
+    /// doc comments of their arguments, and takes argument units from them, from
+    /// `<param name="Name" unit="unit">` tags and from `[unit]` at start of argument doc comments.
+    /// Explicit argument directions come from `@param[in]`, `@param[out]` and `@param[in,out]`.
+    pub fn extract_parameter_docs(&mut self) {
+        let functions = self.functions.iter_mut().chain(
+            self.structs
+                .iter_mut()
+                .chain(self.classes.iter_mut())
+                .flat_map(|item| item.methods.iter_mut().chain(item.constructors.iter_mut())),
+        );
+        for item in functions {
+            take_parameter_docs(&mut item.doc_comments, &mut item.arguments);
+        }
+        for item in &mut self.delegates {
+            take_parameter_docs(&mut item.doc_comments, &mut item.arguments);
+        }
+    }
+
+    /// Removes symbols and members marked experimental with `@experimental` doc comment line.
+    /// Stability has to be extracted first.
+    pub fn remove_experimental(&mut self) {
+        let experimental = Some(Stability::Experimental);
+        let mut skipped = vec![];
+        let mut skip = |kind: String, name: String, filename: &str| {
+            skipped.push(SkippedSymbol {
+                kind,
+                name,
+                filename: filename.to_owned(),
+                reason: SkipReason::Experimental,
+            })
+        };
+        self.enums.retain(|item| {
+            let keep = item.stability != experimental;
+            if !keep {
+                skip("enum".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        for items in [&mut self.structs, &mut self.classes] {
+            items.retain_mut(|item| {
+                let owner = item.name.to_owned();
+                let filename = item.filename.to_owned();
+                item.properties.retain(|property| {
+                    let keep = property.stability != experimental;
+                    if !keep {
+                        let name = format!("{}::{}", owner, property.name);
+                        skip("property".to_owned(), name, &filename);
+                    }
+                    keep
+                });
+                for functions in [&mut item.constructors, &mut item.methods] {
+                    functions.retain(|function| {
+                        let keep = function.stability != experimental;
+                        if !keep {
+                            let name = format!("{}::{}", owner, function.name);
+                            skip("method".to_owned(), name, &filename);
+                        }
+                        keep
+                    });
+                }
+                let keep = item.stability != experimental;
+                if !keep {
+                    skip(item.mode.signature(), owner, &filename);
+                }
+                keep
+            });
+        }
+        self.functions.retain(|item| {
+            let keep = item.stability != experimental;
+            if !keep {
+                skip("function".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.delegates.retain(|item| {
+            let keep = item.stability != experimental;
+            if !keep {
+                skip("delegate".to_owned(), item.name.to_owned(), &item.filename);
+            }
+            keep
+        });
+        self.skipped.extend(skipped);
+    }
+
+    /// Flags or removes symbols without doc comments, depending on given mode.
+    pub fn apply_undocumented_mode(&mut self, mode: UndocumentedMode) {
+        match mode {
+            UndocumentedMode::Include => {}
+            UndocumentedMode::Badge => {
+                for item in &mut self.enums {
+                    item.undocumented = item.doc_comments.is_none();
+                }
+                for item in self.structs.iter_mut().chain(self.classes.iter_mut()) {
+                    item.undocumented = item.doc_comments.is_none();
+                    for property in &mut item.properties {
+                        property.undocumented = property.doc_comments.is_none();
+                    }
+                    for function in item.constructors.iter_mut().chain(item.methods.iter_mut()) {
+                        function.undocumented = function.doc_comments.is_none();
+                    }
+                }
+                for item in &mut self.functions {
+                    item.undocumented = item.doc_comments.is_none();
+                }
+                for item in &mut self.delegates {
+                    item.undocumented = item.doc_comments.is_none();
+                }
+            }
+            UndocumentedMode::Exclude => {
+                let mut skipped = vec![];
+                let mut skip = |kind: &str, name: String, filename: &str| {
+                    skipped.push(SkippedSymbol {
+                        kind: kind.to_owned(),
+                        name,
+                        filename: filename.to_owned(),
+                        reason: SkipReason::UndocumentedExcluded,
+                    })
+                };
+                self.enums.retain(|item| {
+                    let keep = item.doc_comments.is_some();
+                    if !keep {
+                        skip("enum", item.name.to_owned(), &item.filename);
+                    }
+                    keep
+                });
+                for items in [&mut self.structs, &mut self.classes] {
+                    items.retain_mut(|item| {
+                        let owner = item.name.to_owned();
+                        let filename = item.filename.to_owned();
+                        item.properties.retain(|property| {
+                            let keep = property.doc_comments.is_some();
+                            if !keep {
+                                skip(
+                                    "property",
+                                    format!("{}::{}", owner, property.name),
+                                    &filename,
+                                );
+                            }
+                            keep
+                        });
+                        for functions in [&mut item.constructors, &mut item.methods] {
+                            functions.retain(|function| {
+                                let keep = function.doc_comments.is_some();
+                                if !keep {
+                                    skip(
+                                        "method",
+                                        format!("{}::{}", owner, function.name),
+                                        &filename,
+                                    );
+                                }
+                                keep
+                            });
+                        }
+                        let keep = item.doc_comments.is_some()
+                            || !item.properties.is_empty()
+                            || !item.constructors.is_empty()
+                            || !item.methods.is_empty();
+                        if !keep {
+                            skip(&item.mode.signature(), owner, &filename);
+                        }
+                        keep
+                    });
+                }
+                self.functions.retain(|item| {
+                    let keep = item.doc_comments.is_some();
+                    if !keep {
+                        skip("function", item.name.to_owned(), &item.filename);
+                    }
+                    keep
+                });
+                self.delegates.retain(|item| {
+                    let keep = item.doc_comments.is_some();
+                    if !keep {
+                        skip("delegate", item.name.to_owned(), &item.filename);
+                    }
+                    keep
+                });
+                self.skipped.extend(skipped);
+            }
+        }
+    }
+
+    /// Report of all parsed symbols that were left out of documentation, with reasons.
+    pub fn audit_report(&self) -> String {
+        if self.skipped.is_empty() {
+            return "No symbols were left out of documentation.\n".to_owned();
+        }
+        let mut result = format!(
+            "Symbols left out of documentation ({}):\n",
+            self.skipped.len()
+        );
+        for item in &self.skipped {
+            result.push_str(&format!("- {}\n", item));
+        }
+        result
+    }
 
-impl Document {
     pub fn sort_items_by_name(&mut self) {
         for item in &mut self.classes {
             item.sort_items_by_name();
@@ -55,6 +1854,291 @@ impl Document {
         self.delegates.sort_by(|a, b| a.name.cmp(&b.name));
     }
 
+    pub fn find_struct_class(&self, name: &str) -> Option<&StructClass> {
+        self.classes
+            .iter()
+            .chain(self.structs.iter())
+            .find(|item| item.name == name)
+    }
+
+    /// Structs and classes directly inheriting from type with given name.
+    pub fn children(&self, name: &str) -> Vec<&StructClass> {
+        self.classes
+            .iter()
+            .chain(self.structs.iter())
+            .filter(|item| item.parent() == Some(name))
+            .collect()
+    }
+
+    /// Direct inheritance relations between structs and classes, as pairs of child and parent
+    /// names. Parents do not have to be documented.
+    pub fn inheritance_edges(&self) -> Vec<(&str, &str)> {
+        self.classes
+            .iter()
+            .chain(self.structs.iter())
+            .filter_map(|item| Some((item.name.as_str(), item.parent()?)))
+            .collect()
+    }
+
+    /// Names of all parents of given struct or class, starting from the direct parent.
+    /// Chain ends at first parent that is not documented.
+    pub fn parent_chain(&self, item: &StructClass) -> Vec<String> {
+        let mut result = Vec::<String>::new();
+        let mut current = item.parent();
+        while let Some(name) = current {
+            if name == item.name || result.iter().any(|item| item == name) {
+                break;
+            }
+            result.push(name.to_owned());
+            current = self.find_struct_class(name).and_then(|item| item.parent());
+        }
+        result
+    }
+
+    /// Tells if given struct or class derives from `base`, directly or not.
+    pub fn derives_from(&self, item: &StructClass, base: &str) -> bool {
+        self.parent_chain(item).iter().any(|name| name == base)
+    }
+
+    /// Tells if given class is a Blueprint Function Library, a toolbox of static functions.
+    pub fn is_blueprint_function_library(&self, item: &StructClass) -> bool {
+        self.derives_from(item, "UBlueprintFunctionLibrary")
+    }
+
+    /// Moves former names declared with `<alias>OldName</alias>` doc tags or
+    /// `@renamed-from OldName [in version]` lines out of doc comments of symbols that have their
+    /// own pages.
+    pub fn extract_aliases(&mut self) {
+        for symbol in self.symbols_mut() {
+            take_former_names(symbol.doc_comments, symbol.aliases, symbol.renamed_in);
+        }
+    }
+
+    /// Merges reflection classes of interfaces (`U` prefixed ones declared with `UINTERFACE`)
+    /// into their `I` prefixed native classes, that declare interface functions, so each
+    /// interface is documented once. Reflection class lends its specifiers and doc comments to
+    /// native class that has none.
+    pub fn pair_interfaces(&mut self) {
+        let (shells, classes) = std::mem::take(&mut self.classes)
+            .into_iter()
+            .partition::<Vec<_>, _>(|item| item.is_interface);
+        self.classes = classes;
+        for shell in shells {
+            let name = shell
+                .name
+                .strip_prefix('U')
+                .map(|name| format!("I{}", name))
+                .unwrap_or_default();
+            match self.classes.iter_mut().find(|item| item.name == name) {
+                Some(item) => {
+                    item.is_interface = true;
+                    if item.specifiers.is_none() {
+                        item.specifiers = shell.specifiers;
+                    }
+                    if item.doc_comments.is_none() {
+                        item.doc_comments = shell.doc_comments;
+                        item.informal_docs = shell.informal_docs;
+                    }
+                }
+                None => self.classes.push(shell),
+            }
+        }
+    }
+
+    /// Records path of header file of given name on symbols with their own pages parsed from
+    /// it, that have no header path yet.
+    pub fn set_header_path(&mut self, filename: &str, path: &str) {
+        for symbol in self.symbols_mut() {
+            if symbol.filename == filename && symbol.header_path.is_none() {
+                *symbol.header_path = Some(path.to_owned());
+            }
+        }
+    }
+
+    /// Moves owners declared with `@owner TeamName` lines out of doc comments of symbols that
+    /// have their own pages. Symbols without declared owners get ones of last rule of given
+    /// CODEOWNERS-like file content that matches their header path.
+    pub fn extract_owners(&mut self, code_owners: Option<&str>) {
+        let rules = code_owners.map(code_owners_rules).unwrap_or_default();
+        for symbol in self.symbols_mut() {
+            let mut owners = take_owners(symbol.doc_comments);
+            if owners.is_empty() {
+                owners = symbol
+                    .header_path
+                    .as_ref()
+                    .and_then(|path| rules.iter().rev().find(|(re, _)| re.is_match(path)))
+                    .map(|(_, owners)| owners.to_owned())
+                    .unwrap_or_default();
+            }
+            *symbol.owners = owners;
+        }
+    }
+
+    /// Adds aliases from map of former symbol names to current ones. Returns former names whose
+    /// current symbol was not found.
+    pub fn add_aliases(&mut self, aliases: &BTreeMap<String, String>) -> Vec<String> {
+        let mut missing = vec![];
+        for (alias, name) in aliases {
+            let target = if let Some(item) = self.enums.iter_mut().find(|item| &item.name == name) {
+                &mut item.aliases
+            } else if let Some(item) = self
+                .structs
+                .iter_mut()
+                .chain(self.classes.iter_mut())
+                .find(|item| &item.name == name)
+            {
+                &mut item.aliases
+            } else if let Some(item) = self.functions.iter_mut().find(|item| &item.name == name) {
+                &mut item.aliases
+            } else if let Some(item) = self.delegates.iter_mut().find(|item| &item.name == name) {
+                &mut item.aliases
+            } else {
+                missing.push(alias.to_owned());
+                continue;
+            };
+            if !target.contains(alias) {
+                target.push(alias.to_owned());
+            }
+        }
+        missing
+    }
+
+    /// Adds source file of example, along with snippets it contains.
+    pub fn add_example_file(&mut self, relative_path: &str, content: &str) {
+        let name = relative_path
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.split('.').next())
+            .unwrap_or(relative_path)
+            .to_owned();
+        for (id, snippet) in example_snippets(content) {
+            if self.snippets.contains_key(&id) {
+                println!("Overwriting existing snippet: {}", id);
+            }
+            self.snippets.insert(id, snippet);
+        }
+        let content = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("////"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let example = self.examples.entry(name.to_owned()).or_default();
+        example.name = name;
+        example.files.insert(relative_path.to_owned(), content);
+    }
+
+    /// Symbols with `@example` doc tag naming given example, as code references.
+    pub fn example_references(&self, example: &str) -> Vec<String> {
+        let tagged = |doc_comments: &Option<String>| {
+            doc_comments.as_deref().is_some_and(|comments| {
+                EXAMPLE_LINE
+                    .captures_iter(comments)
+                    .any(|captures| &captures[1] == example)
+            })
+        };
+        let mut result = vec![];
+        for item in &self.enums {
+            if tagged(&item.doc_comments) {
+                result.push(format!("[`enum: {}`]()", item.name));
+            }
+        }
+        for item in self.structs.iter().chain(self.classes.iter()) {
+            let kind = match item.mode {
+                StructClassMode::Struct => "struct",
+                StructClassMode::Class => "class",
+            };
+            if tagged(&item.doc_comments) {
+                result.push(format!("[`{}: {}`]()", kind, item.name));
+            }
+            for property in &item.properties {
+                if tagged(&property.doc_comments) {
+                    result.push(format!("[`property: {}::{}`]()", item.name, property.name));
+                }
+            }
+            for function in item.constructors.iter().chain(item.methods.iter()) {
+                if tagged(&function.doc_comments) {
+                    result.push(format!("[`method: {}::{}`]()", item.name, function.name));
+                }
+            }
+        }
+        for item in &self.functions {
+            if tagged(&item.doc_comments) {
+                result.push(format!("[`function: {}`]()", item.name));
+            }
+        }
+        for item in &self.delegates {
+            if tagged(&item.doc_comments) {
+                result.push(format!("[`delegate: {}`]()", item.name));
+            }
+        }
+        result
+    }
+
+    /// Keeps only those conditional blocks of book pages that match given profile and tags.
+    pub fn resolve_conditions(&mut self, profile: Option<&str>, tags: &[String]) {
+        for (path, content) in &mut self.book {
+            if content.contains("<!--") {
+                *content = evaluate_conditions(content, profile, tags).unwrap_or_else(|error| {
+                    panic!(
+                        "Invalid conditional content in book page: {}\n{}",
+                        path, error
+                    )
+                });
+            }
+        }
+    }
+
+    /// Replaces `{{NAME}}` tokens in doc comments and book pages with their values.
+    pub fn apply_substitutions(&mut self, substitutions: &BTreeMap<String, String>) {
+        if substitutions.is_empty() {
+            return;
+        }
+        let tokens = substitutions
+            .iter()
+            .map(|(name, value)| (format!("{{{{{}}}}}", name), value))
+            .collect::<Vec<_>>();
+        let substitute = |content: &mut String| {
+            for (token, value) in &tokens {
+                if content.contains(token.as_str()) {
+                    *content = content.replace(token.as_str(), value);
+                }
+            }
+        };
+        for content in self.doc_comments_mut() {
+            substitute(content);
+        }
+        for content in self.book.values_mut() {
+            substitute(content);
+        }
+    }
+
+    /// Expands `{{kbd:...}}` and `{{menu:...}}` markup of doc comments and book pages.
+    pub fn resolve_ui_markup(&mut self) {
+        let expand = |content: &mut String| {
+            if content.contains("{{kbd:") || content.contains("{{menu:") {
+                *content = expand_ui_markup(content);
+            }
+        };
+        for content in self.doc_comments_mut() {
+            expand(content);
+        }
+        for content in self.book.values_mut() {
+            expand(content);
+        }
+    }
+
+    /// Replaces `@include path/to/fragment.md` lines of doc comments with content of shared
+    /// Markdown fragments. Paths are relative to given directory, and fragments can include
+    /// other fragments too.
+    pub fn resolve_includes(&mut self, root: &Path) {
+        let mut fragments = HashMap::new();
+        for content in self.doc_comments_mut() {
+            if content.contains("@include") {
+                *content = expand_includes(content, root, &mut fragments, &mut vec![]);
+            }
+        }
+    }
+
     pub fn resolve_injects(&mut self) {
         let proxy_functions = std::mem::take(&mut self.proxy_functions);
         let proxy_properties = std::mem::take(&mut self.proxy_properties);
@@ -90,12 +2174,42 @@ pub struct Specifiers {
     pub meta: Vec<Attribute>,
 }
 
+impl Specifiers {
+    pub fn attribute(&self, key: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key().eq_ignore_ascii_case(key))
+    }
+
+    pub fn meta(&self, key: &str) -> Option<&Attribute> {
+        self.meta
+            .iter()
+            .find(|attribute| attribute.key().eq_ignore_ascii_case(key))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Attribute {
     Single(String),
     Pair { key: String, value: String },
 }
 
+impl Attribute {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Single(key) => key,
+            Self::Pair { key, .. } => key,
+        }
+    }
+
+    pub fn value(&self) -> Option<&str> {
+        match self {
+            Self::Single(_) => None,
+            Self::Pair { value, .. } => Some(value),
+        }
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Visibility {
     Private,
@@ -105,7 +2219,7 @@ pub enum Visibility {
 }
 
 impl Visibility {
-    pub fn can_export(self, settings: &Settings) -> bool {
+    pub fn can_export(self, settings: &RenderSettings) -> bool {
         match self {
             Self::Public => true,
             Self::Protected => settings.document_protected,
@@ -122,6 +2236,141 @@ impl Visibility {
     }
 }
 
+/// Why parsed symbol was left out of documentation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    Undocumented,
+    UndocumentedExcluded,
+    Visibility(Visibility),
+    EditorOnly,
+    Unreflected,
+    Experimental,
+    Deprecated,
+    Tags,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Undocumented => {
+                write!(f, "no doc comments, enable `render.show_all` to include")
+            }
+            Self::UndocumentedExcluded => {
+                write!(f, "no doc comments, excluded by `render.undocumented`")
+            }
+            Self::Visibility(Visibility::Protected) => write!(
+                f,
+                "protected member, enable `render.document_protected` to include"
+            ),
+            Self::Visibility(Visibility::Private) => write!(
+                f,
+                "private member, enable `render.document_private` to include"
+            ),
+            Self::Visibility(Visibility::Public) => write!(f, "public member"),
+            Self::EditorOnly => {
+                write!(f, "editor-only, excluded by `render.exclude_editor_only`")
+            }
+            Self::Unreflected => {
+                write!(f, "plain C++, excluded by `render.require_reflection`")
+            }
+            Self::Experimental => {
+                write!(f, "experimental, excluded by `render.hide_experimental`")
+            }
+            Self::Deprecated => {
+                write!(f, "deprecated, excluded by `render.hide_deprecated`")
+            }
+            Self::Tags => write!(
+                f,
+                "tagged, excluded by `render.include_tags` or `render.exclude_tags`"
+            ),
+        }
+    }
+}
+
+/// Maturity of API, declared with `@experimental`, `@beta` or `@stable` doc comment line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stability {
+    Experimental,
+    Beta,
+    Stable,
+}
+
+impl Stability {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Experimental => "Experimental",
+            Self::Beta => "Beta",
+            Self::Stable => "Stable",
+        }
+    }
+}
+
+/// Way function can fail, e.g. exception it throws or error value it returns.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FailureMode {
+    /// Type of thrown exception, if any.
+    #[serde(default)]
+    pub exception: Option<String>,
+    pub description: String,
+}
+
+/// Frequently asked question about symbol, along with its answer.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Faq {
+    pub question: String,
+    pub answer: String,
+}
+
+/// Settings of book page, taken from its front matter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrontMatter {
+    /// Whether shared header gets injected into page.
+    #[serde(default = "FrontMatter::default_injected")]
+    pub header: bool,
+    /// Whether shared footer gets injected into page.
+    #[serde(default = "FrontMatter::default_injected")]
+    pub footer: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl FrontMatter {
+    fn default_injected() -> bool {
+        true
+    }
+}
+
+impl Default for FrontMatter {
+    fn default() -> Self {
+        Self {
+            header: true,
+            footer: true,
+            tags: vec![],
+        }
+    }
+}
+
+/// Parsed symbol that was left out of documentation.
+#[derive(Debug, Clone)]
+pub struct SkippedSymbol {
+    /// Symbol kind, e.g. `class` or `property`.
+    pub kind: String,
+    /// Symbol name, members being prefixed with their owner name.
+    pub name: String,
+    pub filename: String,
+    pub reason: SkipReason,
+}
+
+impl std::fmt::Display for SkippedSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} `{}` ({}): {}",
+            self.kind, self.name, self.filename, self.reason
+        )
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Enum {
     #[serde(default)]
@@ -131,12 +2380,49 @@ pub struct Enum {
     pub fileline: usize,
     #[serde(default)]
     pub variants: Vec<String>,
+    /// `UMETA` specifiers of variants, mapped by variant names.
+    #[serde(default)]
+    pub variant_meta: BTreeMap<String, Specifiers>,
+    /// Type of values, e.g. `uint8`.
+    #[serde(default)]
+    pub underlying_type: Option<String>,
+    /// Declared with plain `enum` instead of `enum class`.
+    #[serde(default)]
+    pub unscoped: bool,
     #[serde(default)]
     pub doc_comments: Option<String>,
+    #[serde(default)]
+    pub informal_docs: bool,
+    /// Has no doc comments and should be flagged as such.
+    #[serde(default)]
+    pub undocumented: bool,
+    /// Former names of symbol, that should still lead to it.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Versions in which symbol was renamed, mapped by former names.
+    #[serde(default)]
+    pub renamed_in: BTreeMap<String, String>,
+    /// Path of header declaring symbol, relative to its input directory.
+    #[serde(default)]
+    pub header_path: Option<String>,
+    /// Teams or people responsible for symbol.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Questions and answers about symbol, declared with `@faq` doc tags.
+    #[serde(default)]
+    pub faqs: Vec<Faq>,
+    #[serde(default)]
+    pub stability: Option<Stability>,
+    /// Tags declared with `@tag` doc comment lines, that `render.include_tags` and
+    /// `render.exclude_tags` filter symbols by.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 impl Enum {
-    pub fn can_export(&self, settings: &Settings) -> bool {
+    pub fn can_export(&self, settings: &RenderSettings) -> bool {
         settings.show_all || self.doc_comments.is_some()
     }
 
@@ -147,7 +2433,45 @@ impl Enum {
             .map(|v| format!("    {}", v))
             .collect::<Vec<_>>()
             .join("\n");
-        format!("enum class {} : uint8 {{\n{}\n}};", self.name, variants)
+        format!(
+            "{} {}{} {{\n{}\n}};",
+            if self.unscoped { "enum" } else { "enum class" },
+            self.name,
+            self.underlying_type
+                .as_ref()
+                .map(|value_type| format!(" : {}", value_type))
+                .unwrap_or_default(),
+            variants
+        )
+    }
+
+    /// Names of variants, without their values and metadata.
+    pub fn variant_names(&self) -> Vec<&str> {
+        self.variants
+            .iter()
+            .filter_map(|variant| {
+                let end = variant
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(variant.len());
+                Some(&variant[..end]).filter(|name| !name.is_empty())
+            })
+            .collect()
+    }
+
+    /// Name of variant shown to designers, set with `UMETA(DisplayName = "...")`.
+    pub fn variant_display_name(&self, variant: &str) -> Option<&str> {
+        self.variant_meta
+            .get(variant)?
+            .attribute("DisplayName")?
+            .value()
+            .map(|value| value.trim_matches('"'))
+    }
+
+    /// Tells if variant is hidden from designers with `UMETA(Hidden)`.
+    pub fn is_variant_hidden(&self, variant: &str) -> bool {
+        self.variant_meta
+            .get(variant)
+            .is_some_and(|meta| meta.attribute("Hidden").is_some())
     }
 
     pub fn resolve_self_names_in_docs(&mut self) {
@@ -184,13 +2508,24 @@ impl StructClassMode {
 pub struct StructClass {
     #[serde(default)]
     pub specifiers: Option<Specifiers>,
+    /// Export macros put before name, e.g. `GAME_API`.
     #[serde(default)]
     pub api: Option<String>,
+    /// Module name taken from export macro matching `api_macro` pattern of parse settings.
+    #[serde(default)]
+    pub module: Option<String>,
     pub mode: StructClassMode,
     pub name: String,
     pub filename: String,
     pub fileline: usize,
     #[serde(default)]
+    pub is_final: bool,
+    #[serde(default)]
+    pub is_editor_only: bool,
+    /// Declared with `UINTERFACE`, with its `U` prefixed reflection class merged into it.
+    #[serde(default)]
+    pub is_interface: bool,
+    #[serde(default)]
     pub inherits: Vec<(Visibility, String)>,
     #[serde(default)]
     pub template: Option<Template>,
@@ -202,12 +2537,42 @@ pub struct StructClass {
     pub methods: Vec<Function>,
     #[serde(default)]
     pub doc_comments: Option<String>,
-    #[serde(skip)]
+    #[serde(default)]
+    pub informal_docs: bool,
+    /// Has no doc comments and should be flagged as such.
+    #[serde(default)]
+    pub undocumented: bool,
+    /// Former names of symbol, that should still lead to it.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Versions in which symbol was renamed, mapped by former names.
+    #[serde(default)]
+    pub renamed_in: BTreeMap<String, String>,
+    /// Path of header declaring symbol, relative to its input directory.
+    #[serde(default)]
+    pub header_path: Option<String>,
+    /// Teams or people responsible for symbol.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Questions and answers about symbol, declared with `@faq` doc tags.
+    #[serde(default)]
+    pub faqs: Vec<Faq>,
+    #[serde(default)]
+    pub stability: Option<Stability>,
+    /// Tags declared with `@tag` doc comment lines, that `render.include_tags` and
+    /// `render.exclude_tags` filter symbols by.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub friends: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     pub injects: HashSet<String>,
 }
 
 impl StructClass {
-    pub fn can_export(&self, settings: &Settings) -> bool {
+    pub fn can_export(&self, settings: &RenderSettings) -> bool {
         settings.show_all
             || self.doc_comments.is_some()
             || self.properties.iter().any(|e| e.can_export(settings))
@@ -215,7 +2580,7 @@ impl StructClass {
             || self.methods.iter().any(|e| e.can_export(settings))
     }
 
-    pub fn signature(&self) -> String {
+    pub fn signature(&self, options: &SignatureOptions) -> String {
         let mut result = String::new();
         if let Some(template) = &self.template {
             result.push_str(template);
@@ -223,11 +2588,14 @@ impl StructClass {
         }
         result.push_str(&self.mode.signature());
         result.push(' ');
-        if let Some(api) = &self.api {
+        if let Some(api) = self.api.as_ref().filter(|_| !options.strip_api_macros) {
             result.push_str(api);
             result.push(' ');
         }
         result.push_str(&self.name);
+        if self.is_final {
+            result.push_str(" final");
+        }
         if !self.inherits.is_empty() {
             for (i, (visibility, name)) in self.inherits.iter().enumerate() {
                 result.push('\n');
@@ -246,11 +2614,73 @@ impl StructClass {
         result
     }
 
+    /// Name of the first inherited type, without template arguments.
+    pub fn parent(&self) -> Option<&str> {
+        self.inherits
+            .first()
+            .map(|(_, name)| name.split('<').next().unwrap_or(name).trim())
+    }
+
+    pub fn is_abstract(&self) -> bool {
+        self.specifiers
+            .as_ref()
+            .map(|specifiers| specifiers.attribute("Abstract").is_some())
+            .unwrap_or_default()
+    }
+
+    pub fn blueprint_exposed_members_count(&self) -> usize {
+        self.properties
+            .iter()
+            .filter(|item| item.is_blueprint_exposed())
+            .count()
+            + self
+                .methods
+                .iter()
+                .filter(|item| item.is_blueprint_exposed())
+                .count()
+    }
+
+    /// Merges `_Implementation` companions into functions they implement, so each function of
+    /// Blueprint native event or interface is documented once. Companion only lends its doc
+    /// comments to function that has none.
+    pub fn pair_implementations(&mut self) {
+        let same_arguments = |a: &Function, b: &Function| {
+            a.arguments
+                .iter()
+                .map(|argument| &argument.value_type)
+                .eq(b.arguments.iter().map(|argument| &argument.value_type))
+        };
+        let (companions, methods) = std::mem::take(&mut self.methods)
+            .into_iter()
+            .partition::<Vec<_>, _>(|method| method.name.ends_with("_Implementation"));
+        self.methods = methods;
+        for companion in companions {
+            let name = companion
+                .name
+                .strip_suffix("_Implementation")
+                .unwrap_or_default();
+            let paired = self
+                .methods
+                .iter_mut()
+                .find(|method| method.name == name && same_arguments(method, &companion));
+            match paired {
+                Some(method) => {
+                    method.has_implementation = true;
+                    if method.doc_comments.is_none() {
+                        method.doc_comments = companion.doc_comments;
+                        method.informal_docs = companion.informal_docs;
+                    }
+                }
+                None => self.methods.push(companion),
+            }
+        }
+    }
+
     pub fn sort_items_by_name(&mut self) {
         self.properties.sort_by(|a, b| a.name.cmp(&b.name));
         self.methods.sort_by(|a, b| a.name.cmp(&b.name));
         // sort constructors by argument count rather than name
-        self.constructors.sort_by(|a, b| a.arguments.iter().count().cmp(&b.arguments.iter().count()));
+        self.constructors.sort_by_key(|a| a.arguments.len());
     }
 
     pub fn resolve_injects(
@@ -305,28 +2735,197 @@ pub struct Property {
     pub array: PropertyArray,
     #[serde(default)]
     pub default_value: Option<String>,
+    /// Default value assigned in constructor body.
+    #[serde(default)]
+    pub constructor_default: Option<String>,
+    /// Replication registration found in `GetLifetimeReplicatedProps`.
+    #[serde(default)]
+    pub lifetime_replication: Option<LifetimeReplication>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    #[serde(default)]
+    pub is_static: bool,
+    #[serde(default)]
+    pub is_union_member: bool,
+    #[serde(default)]
+    pub doc_comments: Option<String>,
+    /// Questions and answers about property, declared with `@faq` doc tags.
+    #[serde(default)]
+    pub faqs: Vec<Faq>,
+    #[serde(default)]
+    pub stability: Option<Stability>,
+    #[serde(default)]
+    pub informal_docs: bool,
+    /// Has no doc comments and should be flagged as such.
+    #[serde(default)]
+    pub undocumented: bool,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Replication registration of property made with one of `DOREPLIFETIME*` macros.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LifetimeReplication {
+    /// Replication condition, e.g. `COND_OwnerOnly`.
     #[serde(default)]
-    pub visibility: Visibility,
+    pub condition: Option<String>,
+    /// RepNotify policy, e.g. `REPNOTIFY_Always`.
     #[serde(default)]
-    pub is_static: bool,
+    pub notify: Option<String>,
+    /// Registered with `FDoRepLifetimeParams` that are known only at runtime.
     #[serde(default)]
-    pub doc_comments: Option<String>,
+    pub custom_parameters: bool,
+}
+
+impl LifetimeReplication {
+    pub fn condition_description(&self) -> Option<&'static str> {
+        Some(match self.condition.as_deref()? {
+            "COND_None" => "always replicated",
+            "COND_InitialOnly" => "replicated only with initial bunch",
+            "COND_OwnerOnly" => "replicated only to actor owner",
+            "COND_SkipOwner" => "replicated to every connection except actor owner",
+            "COND_SimulatedOnly" => "replicated only to simulated proxies",
+            "COND_AutonomousOnly" => "replicated only to autonomous proxy",
+            "COND_SimulatedOrPhysics" => "replicated to simulated proxies or physics actors",
+            "COND_InitialOrOwner" => "replicated with initial bunch or to actor owner",
+            "COND_Custom" => "replication toggled at runtime",
+            "COND_ReplayOrOwner" => "replicated only to replays or actor owner",
+            "COND_ReplayOnly" => "replicated only to replays",
+            "COND_SimulatedOnlyNoReplay" => "replicated only to simulated proxies, not replays",
+            "COND_SimulatedOrPhysicsNoReplay" => {
+                "replicated to simulated proxies or physics actors, not replays"
+            }
+            "COND_SkipReplay" => "not replicated to replays",
+            "COND_Never" => "never replicated",
+            _ => return None,
+        })
+    }
 }
 
 impl Property {
-    pub fn can_export(&self, settings: &Settings) -> bool {
+    pub fn can_export(&self, settings: &RenderSettings) -> bool {
         (self.doc_comments.is_some() || settings.show_all) && self.visibility.can_export(settings)
     }
 
+    pub fn is_blueprint_exposed(&self) -> bool {
+        self.specifiers
+            .as_ref()
+            .map(|specifiers| {
+                [
+                    "BlueprintReadOnly",
+                    "BlueprintReadWrite",
+                    "BlueprintGetter",
+                    "BlueprintSetter",
+                ]
+                .iter()
+                .any(|key| specifiers.attribute(key).is_some())
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn edit_condition(&self) -> Option<&str> {
+        self.specifiers
+            .as_ref()
+            .and_then(|specifiers| specifiers.meta("EditCondition"))
+            .and_then(|attribute| attribute.value())
+            .map(|value| value.trim())
+    }
+
+    /// Names referenced by the `EditCondition` expression of this property.
+    pub fn edit_condition_names(&self) -> Vec<&str> {
+        self.edit_condition()
+            .map(|condition| {
+                condition
+                    .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == ':'))
+                    .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_'))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replication declared with `Replicated` or `ReplicatedUsing` specifiers, as optional
+    /// RepNotify function name.
+    pub fn replication(&self) -> Option<Option<&str>> {
+        let specifiers = self.specifiers.as_ref()?;
+        if let Some(Attribute::Pair { value, .. }) = specifiers.attribute("ReplicatedUsing") {
+            Some(Some(value.as_str()))
+        } else if specifiers.attribute("Replicated").is_some() {
+            Some(None)
+        } else {
+            None
+        }
+    }
+
+    /// Editor category, with nested categories separated by `|`.
+    pub fn category(&self) -> Option<&str> {
+        self.specifiers
+            .as_ref()
+            .and_then(|specifiers| specifiers.attribute("Category"))
+            .and_then(|attribute| attribute.value())
+            .map(|value| value.trim())
+    }
+
+    /// UMG designer binding of this property, as bound element kind and whether widget asset
+    /// has to provide it.
+    pub fn designer_binding(&self) -> Option<(&'static str, bool)> {
+        let specifiers = self.specifiers.as_ref()?;
+        let optional_widget = specifiers
+            .meta("OptionalWidget")
+            .is_some_and(|attribute| attribute.value().is_none_or(|value| value == "true"));
+        if specifiers.meta("BindWidget").is_some() {
+            Some(("Widget", !optional_widget))
+        } else if specifiers.meta("BindWidgetOptional").is_some() {
+            Some(("Widget", false))
+        } else if specifiers.meta("BindWidgetAnim").is_some() {
+            Some(("Animation", true))
+        } else if specifiers.meta("BindWidgetAnimOptional").is_some() {
+            Some(("Animation", false))
+        } else {
+            None
+        }
+    }
+
+    /// Accessor methods bound to this property, as pairs of accessor label and method name.
+    pub fn accessors(&self) -> Vec<(&'static str, String)> {
+        let Some(specifiers) = &self.specifiers else {
+            return vec![];
+        };
+        let mut result = vec![];
+        for (key, label, prefix) in [
+            ("BlueprintGetter", "Blueprint getter", None),
+            ("BlueprintSetter", "Blueprint setter", None),
+            ("Getter", "Getter", Some("Get")),
+            ("Setter", "Setter", Some("Set")),
+        ] {
+            match (specifiers.attribute(key), prefix) {
+                (Some(Attribute::Pair { value, .. }), _) => result.push((label, value.to_owned())),
+                (Some(Attribute::Single(_)), Some(prefix)) => {
+                    result.push((label, format!("{}{}", prefix, self.name)))
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
     pub fn signature(&self) -> String {
         let mut result = self.visibility.signature();
         result.push_str(":\n");
         if self.is_static {
             result.push_str("static ");
         }
-        result.push_str(&self.value_type);
-        result.push(' ');
-        result.push_str(&self.name);
+        match function_pointer_name_position(&self.value_type) {
+            Some(index) => {
+                result.push_str(&self.value_type[..index]);
+                result.push_str(&self.name);
+                result.push_str(&self.value_type[index..]);
+            }
+            None => {
+                result.push_str(&self.value_type);
+                result.push(' ');
+                result.push_str(&self.name);
+            }
+        }
         match &self.array {
             PropertyArray::None => {}
             PropertyArray::Unsized => result.push_str("[]"),
@@ -347,6 +2946,12 @@ impl Property {
 pub struct Function {
     #[serde(default)]
     pub specifiers: Option<Specifiers>,
+    /// Export macro, e.g. `GAME_API`, left out of rendered signatures.
+    #[serde(default)]
+    pub api: Option<String>,
+    /// Module name taken from export macro of function, or of its owner type.
+    #[serde(default)]
+    pub module: Option<String>,
     pub name: String,
     pub filename: String,
     pub fileline: usize,
@@ -364,17 +2969,65 @@ pub struct Function {
     #[serde(default)]
     pub is_const_this: bool,
     #[serde(default)]
+    pub ref_qualifier: Option<String>,
+    #[serde(default)]
     pub is_override: bool,
     #[serde(default)]
+    pub is_explicit: bool,
+    #[serde(default)]
+    pub is_defaulted: bool,
+    #[serde(default)]
+    pub is_deleted: bool,
+    /// Declared with `= 0` or `PURE_VIRTUAL` macro.
+    #[serde(default)]
+    pub is_pure_virtual: bool,
+    /// Has `_Implementation` companion that C++ classes override, like Blueprint native events
+    /// and interface functions do.
+    #[serde(default)]
+    pub has_implementation: bool,
+    #[serde(default)]
     pub doc_comments: Option<String>,
+    #[serde(default)]
+    pub informal_docs: bool,
+    /// Has no doc comments and should be flagged as such.
+    #[serde(default)]
+    pub undocumented: bool,
+    /// Former names of symbol, that should still lead to it.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Versions in which symbol was renamed, mapped by former names.
+    #[serde(default)]
+    pub renamed_in: BTreeMap<String, String>,
+    /// Path of header declaring symbol, relative to its input directory.
+    #[serde(default)]
+    pub header_path: Option<String>,
+    /// Teams or people responsible for symbol.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Questions and answers about symbol, declared with `@faq` doc tags.
+    #[serde(default)]
+    pub faqs: Vec<Faq>,
+    #[serde(default)]
+    pub stability: Option<Stability>,
+    /// Tags declared with `@tag` doc comment lines, that `render.include_tags` and
+    /// `render.exclude_tags` filter symbols by.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ways function can fail, declared with `@throws`, `@fails` or `<exception>` doc tags.
+    #[serde(default)]
+    pub failure_modes: Vec<FailureMode>,
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 impl Function {
-    pub fn can_export(&self, settings: &Settings) -> bool {
+    pub fn can_export(&self, settings: &RenderSettings) -> bool {
         (self.doc_comments.is_some() || settings.show_all) && self.visibility.can_export(settings)
     }
 
-    pub fn signature(&self) -> String {
+    /// Signature with arguments on single line if it fits within given column limit, or one
+    /// argument per line otherwise. Without limit arguments always go one per line.
+    pub fn wrapped_signature(&self, width: Option<usize>) -> String {
         let mut result = self.visibility.signature();
         result.push_str(":\n");
         if let Some(template) = &self.template {
@@ -387,29 +3040,127 @@ impl Function {
         if self.is_virtual {
             result.push_str("virtual ");
         }
-        if let Some(return_type) = &self.return_type {
+        if self.is_explicit {
+            result.push_str("explicit ");
+        }
+        if let Some(return_type) = &self.return_type
+            && !self.is_conversion_operator()
+        {
             result.push_str(return_type);
             result.push(' ');
         }
         result.push_str(&self.name);
-        result.push('(');
-        for (i, argument) in self.arguments.iter().enumerate() {
-            result.push_str("\n    ");
-            result.push_str(&argument.signature());
-            if i < self.arguments.len() - 1 {
-                result.push(',');
-            } else {
-                result.push('\n');
-            }
-        }
-        result.push(')');
+        let head = result
+            .split('\n')
+            .next_back()
+            .unwrap_or_default()
+            .to_owned();
+        result.truncate(result.len() - head.len());
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| argument.signature())
+            .collect::<Vec<_>>();
+        let mut tail = String::default();
         if self.is_const_this {
-            result.push_str(" const");
+            tail.push_str(" const");
+        }
+        if let Some(ref_qualifier) = &self.ref_qualifier {
+            tail.push(' ');
+            tail.push_str(ref_qualifier);
         }
         if self.is_override {
-            result.push_str(" override");
+            tail.push_str(" override");
+        }
+        if self.is_defaulted {
+            tail.push_str(" = default");
+        }
+        if self.is_deleted {
+            tail.push_str(" = delete");
+        }
+        if self.is_pure_virtual {
+            tail.push_str(" = 0");
+        }
+        tail.push(';');
+        result.push_str(&layout_parameters(&head, &arguments, &tail, width));
+        result
+    }
+
+    /// Signature with all arguments on single line, no matter how long it gets.
+    pub fn single_line_signature(&self) -> String {
+        self.wrapped_signature(Some(usize::MAX))
+    }
+
+    pub fn is_blueprint_exposed(&self) -> bool {
+        self.specifiers
+            .as_ref()
+            .map(|specifiers| {
+                [
+                    "BlueprintCallable",
+                    "BlueprintPure",
+                    "BlueprintImplementableEvent",
+                    "BlueprintNativeEvent",
+                    "BlueprintGetter",
+                    "BlueprintSetter",
+                ]
+                .iter()
+                .any(|key| specifiers.attribute(key).is_some())
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_operator(&self) -> bool {
+        is_operator_name(&self.name)
+    }
+
+    /// Tells if this function is conversion operator, e.g. `operator bool`.
+    pub fn is_conversion_operator(&self) -> bool {
+        self.name.starts_with("operator ")
+            && self.name["operator ".len()..].starts_with(|c: char| c.is_alphabetic() || c == '_')
+    }
+
+    /// Blueprint node category, with nested categories separated by `|`.
+    pub fn category(&self) -> Option<&str> {
+        self.specifiers
+            .as_ref()
+            .and_then(|specifiers| specifiers.attribute("Category"))
+            .and_then(|attribute| attribute.value())
+            .map(|value| value.trim())
+    }
+
+    /// Tells if this function is a console command, marked with `Exec` specifier.
+    pub fn is_exec(&self) -> bool {
+        self.specifiers
+            .as_ref()
+            .map(|specifiers| specifiers.attribute("Exec").is_some())
+            .unwrap_or_default()
+    }
+
+    pub fn badges(&self) -> Vec<&'static str> {
+        let mut result = vec![];
+        if self.is_exec() {
+            result.push("Console command");
+        }
+        if self.is_explicit {
+            result.push("Explicit");
+        }
+        if self.is_defaulted {
+            result.push("Defaulted");
+        }
+        if self.is_deleted {
+            result.push("Deleted");
+        }
+        if self.is_pure_virtual {
+            result.push("Pure virtual");
+        }
+        if self.is_const_this {
+            result.push("Const method");
+        }
+        match self.ref_qualifier.as_deref() {
+            Some("&") => result.push("Callable on lvalues only"),
+            Some("&&") => result.push("Callable on rvalues only"),
+            _ => {}
         }
-        result.push(';');
         result
     }
 
@@ -437,10 +3188,38 @@ pub struct Delegate {
     pub arguments: Vec<Argument>,
     #[serde(default)]
     pub doc_comments: Option<String>,
+    #[serde(default)]
+    pub informal_docs: bool,
+    /// Has no doc comments and should be flagged as such.
+    #[serde(default)]
+    pub undocumented: bool,
+    /// Former names of symbol, that should still lead to it.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Versions in which symbol was renamed, mapped by former names.
+    #[serde(default)]
+    pub renamed_in: BTreeMap<String, String>,
+    /// Path of header declaring symbol, relative to its input directory.
+    #[serde(default)]
+    pub header_path: Option<String>,
+    /// Teams or people responsible for symbol.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Questions and answers about symbol, declared with `@faq` doc tags.
+    #[serde(default)]
+    pub faqs: Vec<Faq>,
+    #[serde(default)]
+    pub stability: Option<Stability>,
+    /// Tags declared with `@tag` doc comment lines, that `render.include_tags` and
+    /// `render.exclude_tags` filter symbols by.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 impl Delegate {
-    pub fn can_export(&self, settings: &Settings) -> bool {
+    pub fn can_export(&self, settings: &RenderSettings) -> bool {
         self.doc_comments.is_some() || settings.show_all
     }
 
@@ -456,7 +3235,7 @@ impl Delegate {
         }
         result.push_str("DELEGATE");
 
-        result.push_str(self.num_to_param_name(&self.arguments.iter().count()));
+        result.push_str(self.num_to_param_name(&self.arguments.len()));
         result.push('(');
 
         // if there's a return value, return type is first arg
@@ -468,7 +3247,7 @@ impl Delegate {
         // name is first arg after return value
         result.push_str(&self.name);
         if !self.arguments.is_empty() {
-            result.push_str(",");
+            result.push(',');
         }
 
         for (i, argument) in self.arguments.iter().enumerate() {
@@ -547,9 +3326,94 @@ pub struct Argument {
     pub default_value: Option<String>,
     #[serde(default)]
     pub doc_comments: Option<String>,
+    /// Units of value, e.g. `cm/s`.
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// `UPARAM` specifiers.
+    #[serde(default)]
+    pub specifiers: Option<Specifiers>,
+    /// Direction declared in doc comments, overriding one inferred from argument type.
+    #[serde(default)]
+    pub direction: Option<ArgumentDirection>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArgumentPassing {
+    Value,
+    ConstReference,
+    Reference,
+    RvalueReference,
+    Pointer,
+}
+
+impl ArgumentPassing {
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Value => "Passed by value",
+            Self::ConstReference => "Passed by const reference",
+            Self::Reference => "Passed by reference",
+            Self::RvalueReference => "Passed by rvalue reference",
+            Self::Pointer => "Passed by pointer",
+        }
+    }
+}
+
+/// Direction of data flow through argument, like Blueprint input and output pins.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArgumentDirection {
+    In,
+    Out,
+    InOut,
+}
+
+impl ArgumentDirection {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::In => "In",
+            Self::Out => "Out",
+            Self::InOut => "In/Out",
+        }
+    }
 }
 
 impl Argument {
+    /// Direction declared in doc comments, or inferred like Blueprint does: `UPARAM(ref)`
+    /// arguments are in-out, other non-const references are out and everything else is in.
+    pub fn direction(&self) -> ArgumentDirection {
+        if let Some(direction) = self.direction {
+            return direction;
+        }
+        let is_ref = self
+            .specifiers
+            .as_ref()
+            .is_some_and(|specifiers| specifiers.attribute("ref").is_some());
+        if is_ref {
+            ArgumentDirection::InOut
+        } else if self.passing() == ArgumentPassing::Reference {
+            ArgumentDirection::Out
+        } else {
+            ArgumentDirection::In
+        }
+    }
+
+    /// Tells how this argument is passed.
+    pub fn passing(&self) -> ArgumentPassing {
+        let type_ref = TypeRef::parse(&self.value_type);
+        if self.value_type.trim_end().ends_with("&&") {
+            ArgumentPassing::RvalueReference
+        } else if type_ref.is_reference {
+            if type_ref.is_const {
+                ArgumentPassing::ConstReference
+            } else {
+                ArgumentPassing::Reference
+            }
+        } else if type_ref.pointers > 0 {
+            ArgumentPassing::Pointer
+        } else {
+            ArgumentPassing::Value
+        }
+    }
+
     pub fn signature(&self) -> String {
         let mut result = self.value_type.to_owned();
         if let Some(name) = &self.name {
@@ -569,3 +3433,821 @@ impl Argument {
         }
     }
 }
+
+/// Structured view over a C++ type string, used to describe Unreal smart pointers and containers.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TypeRef {
+    pub name: String,
+    pub arguments: Vec<TypeRef>,
+    pub is_const: bool,
+    pub pointers: usize,
+    pub is_reference: bool,
+}
+
+impl TypeRef {
+    pub fn parse(value_type: &str) -> Self {
+        let mut result = Self::default();
+        let mut value_type = value_type.trim();
+        for prefix in ["const ", "constexpr ", "typename ", "struct ", "class "] {
+            if let Some(rest) = value_type.strip_prefix(prefix) {
+                result.is_const |= prefix == "const ";
+                value_type = rest.trim_start();
+            }
+        }
+        loop {
+            if let Some(rest) = value_type.strip_suffix('*') {
+                result.pointers += 1;
+                value_type = rest.trim_end();
+            } else if let Some(rest) = value_type.strip_suffix('&') {
+                result.is_reference = true;
+                value_type = rest.trim_end();
+            } else if let Some(rest) = value_type.strip_suffix(" const") {
+                value_type = rest.trim_end();
+                // `const` right after type (east const) makes value const, after `*` only pointer
+                result.is_const |= !value_type.ends_with(['*', '&']);
+            } else {
+                break;
+            }
+        }
+        match (value_type.find('<'), value_type.rfind('>')) {
+            (Some(start), Some(end)) if start < end => {
+                result.name = value_type[..start].trim().to_owned();
+                result.arguments = split_top_level(&value_type[(start + 1)..end])
+                    .into_iter()
+                    .map(Self::parse)
+                    .collect();
+            }
+            _ => result.name = value_type.to_owned(),
+        }
+        result
+    }
+
+    /// Name without namespace qualification, e.g. `TArray` for `::TArray`.
+    pub fn short_name(&self) -> &str {
+        self.name.rsplit("::").next().unwrap_or(&self.name).trim()
+    }
+
+    /// Tells if this type is one of the Unreal wrappers we know how to describe.
+    pub fn is_wrapper(&self) -> bool {
+        !self.arguments.is_empty() && Self::wrapper_nouns(self.short_name()).is_some()
+    }
+
+    /// Tells if this type holds a soft reference anywhere, meaning referenced asset is not
+    /// loaded together with the owner.
+    pub fn is_soft_reference(&self) -> bool {
+        matches!(
+            self.short_name(),
+            "TSoftObjectPtr" | "TSoftClassPtr" | "FSoftObjectPath" | "FSoftClassPath"
+        ) || self
+            .arguments
+            .iter()
+            .any(|argument| argument.is_soft_reference())
+    }
+
+    /// Plain types held by this type, unwrapped from containers and smart pointers, together
+    /// with the way they are held, e.g. `UTexture2D` held by soft reference for
+    /// `TArray<TSoftObjectPtr<UTexture2D>>`.
+    pub fn held_types(&self) -> Vec<(&str, Holding)> {
+        let mut result = vec![];
+        self.collect_held_types(Holding::Value, &mut result);
+        result
+    }
+
+    fn collect_held_types<'a>(&'a self, holding: Holding, result: &mut Vec<(&'a str, Holding)>) {
+        let holding = if self.pointers > 0 {
+            holding.max(Holding::Pointer)
+        } else {
+            holding
+        };
+        if !self.is_wrapper() {
+            result.push((self.short_name(), holding));
+            return;
+        }
+        let holding = match self.short_name() {
+            "TObjectPtr" | "TSharedPtr" | "TSharedRef" | "TUniquePtr" | "TSubclassOf" => {
+                holding.max(Holding::Pointer)
+            }
+            "TWeakObjectPtr" => holding.max(Holding::Weak),
+            "TSoftObjectPtr" | "TSoftClassPtr" => holding.max(Holding::Soft),
+            _ => holding,
+        };
+        for argument in &self.arguments {
+            argument.collect_held_types(holding, result);
+        }
+    }
+
+    /// Human-readable description, e.g. "array of soft references to `UTexture2D`".
+    /// `link` decides how referenced type names are rendered.
+    pub fn describe(&self, link: &dyn Fn(&str) -> String) -> String {
+        self.describe_inner(false, link)
+    }
+
+    fn describe_inner(&self, plural: bool, link: &dyn Fn(&str) -> String) -> String {
+        let Some((singular_noun, plural_noun, preposition)) =
+            Self::wrapper_nouns(self.short_name()).filter(|_| !self.arguments.is_empty())
+        else {
+            return link(&self.name);
+        };
+        let noun = if plural { plural_noun } else { singular_noun };
+        match (self.short_name(), self.arguments.as_slice()) {
+            ("TMap", [key, value, ..]) => format!(
+                "{} from {} to {}",
+                noun,
+                key.describe_inner(true, link),
+                value.describe_inner(true, link)
+            ),
+            ("TOptional", [inner, ..]) => {
+                format!("{} {}", noun, inner.describe_inner(plural, link))
+            }
+            ("TArray" | "TSet", [inner, ..]) => {
+                format!(
+                    "{} {} {}",
+                    noun,
+                    preposition,
+                    inner.describe_inner(true, link)
+                )
+            }
+            (_, [inner, ..]) => {
+                format!(
+                    "{} {} {}",
+                    noun,
+                    preposition,
+                    inner.describe_inner(false, link)
+                )
+            }
+            _ => link(&self.name),
+        }
+    }
+
+    fn wrapper_nouns(name: &str) -> Option<(&'static str, &'static str, &'static str)> {
+        match name {
+            "TObjectPtr" => Some(("object pointer", "object pointers", "to")),
+            "TSoftObjectPtr" => Some(("soft reference", "soft references", "to")),
+            "TSoftClassPtr" => Some(("soft class reference", "soft class references", "to")),
+            "TWeakObjectPtr" => Some(("weak pointer", "weak pointers", "to")),
+            "TSubclassOf" => Some(("subclass", "subclasses", "of")),
+            "TSharedPtr" => Some(("shared pointer", "shared pointers", "to")),
+            "TSharedRef" => Some(("shared reference", "shared references", "to")),
+            "TUniquePtr" => Some(("unique pointer", "unique pointers", "to")),
+            "TArray" => Some(("array", "arrays", "of")),
+            "TSet" => Some(("set", "sets", "of")),
+            "TMap" => Some(("map", "maps", "from")),
+            "TOptional" => Some(("optional", "optional", "")),
+            _ => None,
+        }
+    }
+}
+
+/// Way in which one type holds another, from strongest to weakest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Holding {
+    Value,
+    Pointer,
+    Weak,
+    Soft,
+}
+
+impl Holding {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Value => "by value",
+            Self::Pointer => "by pointer",
+            Self::Weak => "by weak pointer",
+            Self::Soft => "by soft reference",
+        }
+    }
+}
+
+fn split_top_level(content: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0_isize;
+    let mut start = 0;
+    for (index, c) in content.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(content[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = content[start..].trim();
+    if !last.is_empty() {
+        result.push(last);
+    }
+    result
+}
+
+/// Position right after bracket closing the one opened at `start`, skipping nested brackets.
+fn matching_bracket_end(content: &str, start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in content[start..].char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(start + index + 1);
+            }
+        }
+    }
+    None
+}
+
+/// Name followed by opening parenthesis, e.g. of constructor declaration or definition.
+static CALLABLE_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b([A-Za-z_]\w*)\s*\(").unwrap());
+
+/// Bodies of constructors of `owner` type defined in C++ source, both inline in class and out
+/// of class. Brace initializers in member initializer lists are skipped.
+fn constructor_bodies<'a>(source: &'a str, owner: &str) -> Vec<&'a str> {
+    let mut result = vec![];
+    for captures in CALLABLE_NAME.captures_iter(source) {
+        if &captures[1] != owner {
+            continue;
+        }
+        let found = captures.get(0).unwrap();
+        let Some(mut position) = matching_bracket_end(source, found.end() - 1, '(', ')') else {
+            continue;
+        };
+        let mut previous = ')';
+        while let Some((index, c)) = source[position..]
+            .char_indices()
+            .find(|(_, c)| matches!(c, '{' | ';'))
+        {
+            let index = position + index;
+            if c == ';' {
+                break;
+            }
+            if let Some(last) = source[position..index].trim_end().chars().last() {
+                previous = last;
+            }
+            let Some(end) = matching_bracket_end(source, index, '{', '}') else {
+                break;
+            };
+            if matches!(previous, ')' | '}') {
+                result.push(&source[(index + 1)..(end - 1)]);
+                break;
+            }
+            previous = '}';
+            position = end;
+        }
+    }
+    result
+}
+
+/// `Member = Value;` statement of C++ source.
+static MEMBER_ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*(?:this\s*->\s*)?([A-Za-z_]\w*)\s*=\s*([^;=\s][^;]*);").unwrap()
+});
+
+/// Simple `Member = Value;` statements, with values of multiline statements collapsed into
+/// single line.
+fn member_assignments(body: &str) -> Vec<(&str, String)> {
+    MEMBER_ASSIGNMENT
+        .captures_iter(body)
+        .map(|captures| {
+            let value = captures[2].split_whitespace().collect::<Vec<_>>().join(" ");
+            (captures.get(1).unwrap().as_str(), value)
+        })
+        .collect()
+}
+
+/// Definition of `GetLifetimeReplicatedProps` function of C++ source.
+static LIFETIME_PROPS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(\w+)\s*::\s*GetLifetimeReplicatedProps\s*\(").unwrap());
+
+/// `DOREPLIFETIME*` macro registering replicated property.
+static REPLICATION_REGISTRATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(DOREPLIFETIME(?:_CONDITION_NOTIFY|_CONDITION|_WITH_PARAMS_FAST|_WITH_PARAMS)?)\s*\(\s*(\w+)\s*,\s*(\w+)\s*(?:,([^;]*))?\)\s*;",
+    )
+    .unwrap()
+});
+
+/// Replication registrations made with `DOREPLIFETIME*` macros in bodies of
+/// `GetLifetimeReplicatedProps` of given C++ source, as owner and property names paired with
+/// their registration.
+fn lifetime_replications(source: &str) -> Vec<(&str, &str, LifetimeReplication)> {
+    let mut result = vec![];
+    for captures in LIFETIME_PROPS.captures_iter(source) {
+        let start = captures.get(0).unwrap().end() - 1;
+        let Some(body_start) = matching_bracket_end(source, start, '(', ')')
+            .and_then(|end| source[end..].find('{').map(|index| end + index))
+        else {
+            continue;
+        };
+        let Some(body_end) = matching_bracket_end(source, body_start, '{', '}') else {
+            continue;
+        };
+        for registration in REPLICATION_REGISTRATION.captures_iter(&source[body_start..body_end]) {
+            let arguments = registration
+                .get(4)
+                .map(|arguments| arguments.as_str().split(',').map(str::trim).collect())
+                .unwrap_or_else(Vec::new);
+            let (condition, notify) = match &registration[1] {
+                "DOREPLIFETIME_CONDITION" => (arguments.first(), None),
+                "DOREPLIFETIME_CONDITION_NOTIFY" => (arguments.first(), arguments.get(1)),
+                _ => (None, None),
+            };
+            result.push((
+                registration.get(2).unwrap().as_str(),
+                registration.get(3).unwrap().as_str(),
+                LifetimeReplication {
+                    condition: condition.map(|item| item.to_string()),
+                    notify: notify.map(|item| item.to_string()),
+                    custom_parameters: registration[1].contains("WITH_PARAMS"),
+                },
+            ));
+        }
+    }
+    result
+}
+
+#[test]
+fn test_lifetime_replications() {
+    let source = r#"
+        void AFoo::GetLifetimeReplicatedProps(TArray<FLifetimeProperty>& OutLifetimeProps) const
+        {
+            Super::GetLifetimeReplicatedProps(OutLifetimeProps);
+            DOREPLIFETIME(AFoo, Health);
+            DOREPLIFETIME_CONDITION_NOTIFY(AFoo, Ammo, COND_OwnerOnly, REPNOTIFY_Always);
+        }
+    "#;
+    let result = lifetime_replications(source);
+    assert_eq!(result.len(), 2);
+    assert_eq!((result[0].0, result[0].1), ("AFoo", "Health"));
+    assert!(result[0].2.condition.is_none());
+    assert_eq!(result[1].1, "Ammo");
+    assert_eq!(result[1].2.condition.as_deref(), Some("COND_OwnerOnly"));
+    assert_eq!(result[1].2.notify.as_deref(), Some("REPNOTIFY_Always"));
+}
+
+#[test]
+fn test_constructor_defaults() {
+    let source = r#"
+        UFoo::UFoo(const FObjectInitializer& ObjectInitializer)
+            : Super(ObjectInitializer), Bar{1, 2}
+        {
+            Speed = 600.f;
+            this->Name =
+                TEXT("Foo");
+            if (Speed == 0.f) {}
+        }
+        void UFoo::Reset() { Speed = UFoo(Other).Speed; }
+    "#;
+    let bodies = constructor_bodies(source, "UFoo");
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(
+        member_assignments(bodies[0]),
+        vec![
+            ("Speed", "600.f".to_owned()),
+            ("Name", "TEXT(\"Foo\")".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn test_undocumented_exclude() {
+    let mut document = Document::default();
+    document.structs.push(StructClass {
+        name: "FFoo".to_owned(),
+        properties: vec![
+            Property {
+                name: "A".to_owned(),
+                doc_comments: Some("Documented.".to_owned()),
+                ..Default::default()
+            },
+            Property {
+                name: "B".to_owned(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    });
+    document.enums.push(Enum {
+        name: "EBar".to_owned(),
+        ..Default::default()
+    });
+    document.functions.push(Function {
+        name: "Baz".to_owned(),
+        filename: "Baz.h".to_owned(),
+        ..Default::default()
+    });
+    document.apply_undocumented_mode(UndocumentedMode::Exclude);
+    assert!(document.enums.is_empty());
+    assert!(document.functions.is_empty());
+    assert_eq!(document.structs[0].properties.len(), 1);
+    assert_eq!(document.skipped.len(), 3);
+    assert_eq!(document.skipped[2].filename, "Baz.h");
+}
+
+#[test]
+fn test_remove_deprecated() {
+    let mut document = Document::default();
+    document.classes.push(StructClass {
+        name: "UFoo".to_owned(),
+        methods: vec![
+            Function {
+                name: "Old".to_owned(),
+                doc_comments: Some("Old way.\n@deprecated Use `New` instead.".to_owned()),
+                ..Default::default()
+            },
+            Function {
+                name: "New".to_owned(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    });
+    document.functions.push(Function {
+        name: "Legacy".to_owned(),
+        specifiers: Some(Specifiers {
+            meta: vec![Attribute::Single("DeprecatedFunction".to_owned())],
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    document.remove_deprecated();
+    assert!(document.functions.is_empty());
+    assert_eq!(document.classes[0].methods.len(), 1);
+    assert_eq!(document.classes[0].methods[0].name, "New");
+    assert_eq!(document.skipped.len(), 2);
+}
+
+#[test]
+fn test_remove_experimental() {
+    let mut document = Document::default();
+    document.classes.push(StructClass {
+        name: "UFoo".to_owned(),
+        properties: vec![
+            Property {
+                name: "Draft".to_owned(),
+                doc_comments: Some("Draft value.\n@experimental".to_owned()),
+                ..Default::default()
+            },
+            Property {
+                name: "Settled".to_owned(),
+                doc_comments: Some("Settled value.\n@beta".to_owned()),
+                ..Default::default()
+            },
+        ],
+        methods: vec![Function {
+            name: "Try".to_owned(),
+            doc_comments: Some("@experimental".to_owned()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    document.extract_stability();
+    let class = &document.classes[0];
+    assert_eq!(class.properties[0].stability, Some(Stability::Experimental));
+    assert_eq!(class.properties[1].stability, Some(Stability::Beta));
+    assert_eq!(
+        class.properties[1].doc_comments.as_deref(),
+        Some("Settled value.")
+    );
+    assert_eq!(class.methods[0].stability, Some(Stability::Experimental));
+    assert!(class.methods[0].doc_comments.is_none());
+    document.remove_experimental();
+    let class = &document.classes[0];
+    assert_eq!(class.properties.len(), 1);
+    assert_eq!(class.properties[0].name, "Settled");
+    assert!(class.methods.is_empty());
+    let skipped = document
+        .skipped
+        .iter()
+        .map(|item| (item.kind.as_str(), item.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        skipped,
+        vec![("property", "UFoo::Draft"), ("method", "UFoo::Try")]
+    );
+}
+
+#[test]
+fn test_remove_tagged() {
+    let symbol = |name: &str, doc_comments: &str| Function {
+        name: name.to_owned(),
+        doc_comments: Some(doc_comments.to_owned()),
+        ..Default::default()
+    };
+    let document = Document {
+        functions: vec![
+            symbol("Shipped", "Shipped.\n@tag public"),
+            symbol("Hidden", "Hidden.\n@tag public\n@tag internal"),
+            symbol("Untagged", "Untagged."),
+        ],
+        ..Default::default()
+    };
+    let names = |settings: RenderSettings| {
+        let mut document = document.to_owned();
+        document.remove_hidden(&settings);
+        document
+            .functions
+            .iter()
+            .map(|item| item.name.to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(Default::default()), ["Shipped", "Hidden", "Untagged"]);
+    assert_eq!(
+        names(RenderSettings {
+            include_tags: vec!["public".to_owned()],
+            exclude_tags: vec!["internal".to_owned()],
+            ..Default::default()
+        }),
+        ["Shipped"]
+    );
+    assert_eq!(
+        names(RenderSettings {
+            exclude_tags: vec!["internal".to_owned()],
+            ..Default::default()
+        }),
+        ["Shipped", "Untagged"]
+    );
+
+    let mut document = document.to_owned();
+    document.remove_hidden(&Default::default());
+    assert_eq!(document.functions[1].tags, ["public", "internal"]);
+    assert_eq!(
+        document.functions[1].doc_comments.as_deref(),
+        Some("Hidden.")
+    );
+}
+
+#[test]
+fn test_sanitize_html() {
+    assert_eq!(
+        sanitize_html("<summary>Hi</summary><script>alert(1)</script>"),
+        "<summary>Hi</summary>&lt;script>alert(1)&lt;/script>"
+    );
+    assert_eq!(
+        sanitize_html("<img src=\"a.png\" onerror=\"alert(1)\"> `TArray<int32>`"),
+        "<img src=\"a.png\"> `TArray<int32>`"
+    );
+    assert_eq!(
+        sanitize_html("```cpp\nTArray<int32> A;\n```\n<a href=\"javascript:x\">"),
+        "```cpp\nTArray<int32> A;\n```\n&lt;a href=\"javascript:x\">"
+    );
+    assert_eq!(
+        sanitize_html("Icon <img/onerror=alert(1) src=a.png> here"),
+        "Icon <img src=\"a.png\"> here"
+    );
+    assert_eq!(
+        sanitize_html("See <a href=\"jav&#x61;script&colon;x\">this</a>"),
+        "See &lt;a href=\"jav&#x61;script&colon;x\">this</a>"
+    );
+    assert_eq!(
+        sanitize_html("See [this](jav&#x61;script:x)"),
+        "See \\[this\\]\\(jav&#x61;script:x\\)"
+    );
+    assert_eq!(
+        sanitize_html("Tick ` then <img src=x onerror=alert(1)>"),
+        "Tick ` then <img src=\"x\">"
+    );
+    assert_eq!(
+        sanitize_html("Text <span style=\"position:fixed\" title='a>b'>"),
+        "Text <span title=\"a&gt;b\">"
+    );
+    assert_eq!(
+        sanitize_html("<script>\n[x](javascript:x)\n</script>"),
+        "&lt;script>\n\\[x\\]\\(javascript:x\\)\n&lt;/script>"
+    );
+}
+
+#[test]
+fn test_symbol_slug() {
+    assert_eq!(symbol_slug("operator=="), "operator_eq");
+    assert_eq!(symbol_slug("operator bool"), "operator_bool");
+    assert_eq!(symbol_slug("operatorCount"), "operatorCount");
+    assert_eq!(symbol_slug("operator_Count"), "operator_Count");
+}
+
+#[test]
+fn test_type_ref_describe() {
+    let link = |name: &str| format!("`{}`", name);
+    let type_ref = TypeRef::parse("const TArray<TSoftObjectPtr<UTexture2D>>&");
+    assert!(type_ref.is_const && type_ref.is_reference);
+    let east_const = TypeRef::parse("FVector const&");
+    assert!(east_const.is_const && east_const.is_reference);
+    assert_eq!(east_const.name, "FVector");
+    assert!(!TypeRef::parse("FVector* const").is_const);
+    assert!(!TypeRef::parse("constexpr int").is_const);
+    assert_eq!(
+        type_ref.describe(&link),
+        "array of soft references to `UTexture2D`"
+    );
+    let type_ref = TypeRef::parse("TMap<FName, TSubclassOf<AActor>>");
+    assert_eq!(
+        type_ref.describe(&link),
+        "map from `FName` to subclasses of `AActor`"
+    );
+    assert_eq!(
+        TypeRef::parse("TOptional<TObjectPtr<UObject>>").describe(&link),
+        "optional object pointer to `UObject`"
+    );
+    assert!(!TypeRef::parse("UObject*").is_wrapper());
+}
+
+#[test]
+fn test_type_ref_held_types() {
+    assert_eq!(
+        TypeRef::parse("TArray<TSoftObjectPtr<UTexture2D>>").held_types(),
+        vec![("UTexture2D", Holding::Soft)]
+    );
+    assert_eq!(
+        TypeRef::parse("TMap<FName, UObject*>").held_types(),
+        vec![("FName", Holding::Value), ("UObject", Holding::Pointer)]
+    );
+}
+
+#[test]
+fn test_layout_parameters() {
+    let parameters = vec!["int32 Count".to_owned(), "float Scale = 1.0f".to_owned()];
+    assert_eq!(
+        layout_parameters("void Spawn", &parameters, " const;", Some(60)),
+        "void Spawn(int32 Count, float Scale = 1.0f) const;"
+    );
+    assert_eq!(
+        layout_parameters("void Spawn", &parameters, " const;", Some(40)),
+        "void Spawn(\n    int32 Count,\n    float Scale = 1.0f\n) const;"
+    );
+    assert_eq!(
+        layout_parameters("void Spawn", &parameters, ";", None),
+        "void Spawn(\n    int32 Count,\n    float Scale = 1.0f\n);"
+    );
+    assert_eq!(
+        layout_parameters("void Reset", &[], ";", None),
+        "void Reset();"
+    );
+}
+
+#[test]
+fn test_evaluate_conditions() {
+    let content = "Intro\n<!-- if profile=internal -->\nSecret\n<!-- else -->\nPublic\n<!-- endif -->\n\
+        Tagged: <!-- if tag=beta -->yes<!-- endif --><!-- if tag!=beta -->no<!-- endif -->\n";
+    assert_eq!(
+        evaluate_conditions(content, Some("internal"), &["beta".to_owned()]).unwrap(),
+        "Intro\nSecret\nTagged: yes\n"
+    );
+    assert_eq!(
+        evaluate_conditions(content, None, &[]).unwrap(),
+        "Intro\nPublic\nTagged: no\n"
+    );
+    assert!(evaluate_conditions("<!-- if tag=beta -->", None, &[]).is_err());
+    assert!(evaluate_conditions("<!-- endif -->", None, &[]).is_err());
+}
+
+#[test]
+fn test_expand_ui_markup() {
+    assert_eq!(
+        expand_ui_markup("Press {{kbd:Ctrl+Alt+P}} or {{kbd: Ctrl + + }}."),
+        "Press <kbd>Ctrl</kbd>+<kbd>Alt</kbd>+<kbd>P</kbd> or <kbd>Ctrl</kbd>+<kbd>+</kbd>."
+    );
+    assert_eq!(
+        expand_ui_markup("Open {{menu:Edit > Project Settings > Input}}, not {{other:A}}."),
+        "Open **Edit** > **Project Settings** > **Input**, not {{other:A}}."
+    );
+}
+
+#[test]
+fn test_code_owners_rules() {
+    let rules = code_owners_rules(
+        "# comment\n*.h @engine\nGameplay/ @gameplay\nUI/**/Widgets/*.h @ui @design\n",
+    );
+    let owners = |path: &str| {
+        rules
+            .iter()
+            .rev()
+            .find(|(re, _)| re.is_match(path))
+            .map(|(_, owners)| owners.join(" "))
+    };
+    assert_eq!(owners("Core/Types.h").as_deref(), Some("@engine"));
+    assert_eq!(
+        owners("Game/Gameplay/Pickup.h").as_deref(),
+        Some("@gameplay")
+    );
+    assert_eq!(
+        owners("UI/Menu/Widgets/Button.h").as_deref(),
+        Some("@ui @design")
+    );
+    assert_eq!(
+        owners("UI/Widgets/Button.h").as_deref(),
+        Some("@ui @design")
+    );
+    assert_eq!(owners("Types.cpp"), None);
+}
+
+#[test]
+fn test_take_faqs() {
+    let mut doc_comments = Some(
+        "Finds owner.\n@faq Q: Why does it return null?\nA: Owner is set\nafter spawning.\n\n@faq Is it cached?\nNo.\n@see Other"
+            .to_owned(),
+    );
+    let faqs = take_faqs(&mut doc_comments);
+    assert_eq!(doc_comments.as_deref(), Some("Finds owner.\n\n@see Other"));
+    let faqs = faqs
+        .iter()
+        .map(|faq| (faq.question.as_str(), faq.answer.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        faqs,
+        vec![
+            ("Why does it return null?", "Owner is set\nafter spawning."),
+            ("Is it cached?", "No.")
+        ]
+    );
+}
+
+#[test]
+fn test_take_front_matter() {
+    let mut content =
+        "---\nheader: false\ntags: [input, \"UI\"]\nsidebar: 2\n---\n# Title\n".to_owned();
+    let front_matter = take_front_matter(&mut content).unwrap();
+    assert_eq!(content, "# Title\n");
+    assert!(!front_matter.header);
+    assert!(front_matter.footer);
+    assert_eq!(front_matter.tags, vec!["input", "UI"]);
+    let mut content = "---\nheader: false\n# Title\n".to_owned();
+    assert!(take_front_matter(&mut content).is_none());
+    assert_eq!(content, "---\nheader: false\n# Title\n");
+}
+
+#[test]
+fn test_take_failure_modes() {
+    let mut doc_comments = Some(
+        "<summary>Loads asset.</summary>\n@throws FLoadError When file is missing.\n\
+        <exception cref=\"FParseError\">When file\nis corrupted.</exception>\n\
+        @fails Returns null when called before startup."
+            .to_owned(),
+    );
+    let failure_modes = take_failure_modes(&mut doc_comments);
+    assert_eq!(
+        doc_comments.as_deref(),
+        Some("<summary>Loads asset.</summary>")
+    );
+    let failure_modes = failure_modes
+        .iter()
+        .map(|item| (item.exception.as_deref(), item.description.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        failure_modes,
+        vec![
+            (Some("FLoadError"), "When file is missing."),
+            (Some("FParseError"), "When file is corrupted."),
+            (None, "Returns null when called before startup."),
+        ]
+    );
+}
+
+#[test]
+fn test_take_parameter_docs() {
+    let argument = |name: &str, doc_comments: Option<&str>| Argument {
+        name: Some(name.to_owned()),
+        value_type: "float".to_owned(),
+        doc_comments: doc_comments.map(str::to_owned),
+        ..Default::default()
+    };
+    let mut arguments = vec![
+        argument("Speed", None),
+        argument("Height", Some("[cm] Jump height.")),
+        argument("Delay", None),
+    ];
+    let mut doc_comments = Some(
+        "Moves actor.\n@param Speed [cm/s] Maximum movement speed.\n\
+        <param name=\"Delay\" unit=\"s\">Delay before move.</param>"
+            .to_owned(),
+    );
+    take_parameter_docs(&mut doc_comments, &mut arguments);
+    assert_eq!(
+        doc_comments.as_deref(),
+        Some("Moves actor.\n<param name=\"Delay\">Delay before move.</param>")
+    );
+    let units = arguments
+        .iter()
+        .map(|argument| argument.unit.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(units, vec!["cm/s", "cm", "s"]);
+    assert_eq!(
+        arguments[0].doc_comments.as_deref(),
+        Some("Maximum movement speed.")
+    );
+    assert_eq!(arguments[1].doc_comments.as_deref(), Some("Jump height."));
+    assert_eq!(arguments[0].direction(), ArgumentDirection::In);
+
+    let mut arguments = vec![argument("Speed", None), argument("Height", None)];
+    let mut doc_comments = Some(
+        "@param[in,out] Speed [cm/s] Maximum movement speed.\n@param[out] Height Jump height."
+            .to_owned(),
+    );
+    take_parameter_docs(&mut doc_comments, &mut arguments);
+    assert!(doc_comments.is_none());
+    assert_eq!(arguments[0].unit.as_deref(), Some("cm/s"));
+    assert_eq!(
+        arguments[0].doc_comments.as_deref(),
+        Some("Maximum movement speed.")
+    );
+    assert_eq!(arguments[0].direction(), ArgumentDirection::InOut);
+    assert_eq!(arguments[1].direction(), ArgumentDirection::Out);
+}
@@ -0,0 +1,71 @@
+use crate::config::Config;
+use std::{error::Error, path::Path, process::Command};
+
+/// Runs hook commands one by one in system shell, with paths of baked files exposed as
+/// environment variables:
+/// - `UNREAL_DOC_OUTPUT_DIR` - directory with baked files at the moment hooks run. During MD Book
+///   baking it is staging directory that replaces output directory once baking succeeds.
+/// - `UNREAL_DOC_TARGET_DIR` - output directory from config.
+/// - `UNREAL_DOC_BACKEND` - name of baking backend.
+///
+/// Stops at first command that fails, returning its error.
+pub fn run_hooks(
+    commands: &[String],
+    config: &Config,
+    root: &Path,
+    output_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    run_commands(commands, config, root, output_dir, None)
+}
+
+/// Runs hook commands after MD Book got built, like [`run_hooks`] does, additionally exposing
+/// `UNREAL_DOC_BOOK_DIR` environment variable with directory of built book bundle.
+pub fn run_book_hooks(
+    commands: &[String],
+    config: &Config,
+    root: &Path,
+    output_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    run_commands(
+        commands,
+        config,
+        root,
+        output_dir,
+        Some(&output_dir.join("book")),
+    )
+}
+
+fn run_commands(
+    commands: &[String],
+    config: &Config,
+    root: &Path,
+    output_dir: &Path,
+    book_dir: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    for command in commands {
+        let mut process = if cfg!(windows) {
+            let mut process = Command::new("cmd");
+            process.arg("/C").arg(command);
+            process
+        } else {
+            let mut process = Command::new("sh");
+            process.arg("-c").arg(command);
+            process
+        };
+        process
+            .current_dir(root)
+            .env("UNREAL_DOC_OUTPUT_DIR", output_dir)
+            .env("UNREAL_DOC_TARGET_DIR", &config.output_dir)
+            .env("UNREAL_DOC_BACKEND", config.backend.name());
+        if let Some(book_dir) = book_dir {
+            process.env("UNREAL_DOC_BOOK_DIR", book_dir);
+        }
+        let status = process
+            .status()
+            .map_err(|error| format!("Could not run hook command: {} ({})", command, error))?;
+        if !status.success() {
+            return Err(format!("Hook command failed with {}: {}", status, command).into());
+        }
+    }
+    Ok(())
+}
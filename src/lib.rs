@@ -0,0 +1,60 @@
+//! Documentation generator for Unreal C++ projects, usable both as `unreal-doc` command and as
+//! library for build tools and editor plugins. Parse headers with [`parse_unreal_cpp_header`]
+//! (or whole config inputs with [`pipeline`] stages) into [`Document`], modify it as needed and
+//! bake it with one of backends from [`BackendRegistry`], configured by [`Config`].
+
+#[macro_use]
+extern crate pest_derive;
+
+pub mod ast;
+pub mod backends;
+pub mod config;
+pub mod diagnostics;
+mod diff;
+pub mod doctor;
+pub mod document;
+mod hooks;
+pub mod lint;
+pub mod pipeline;
+pub mod plugins;
+mod remote;
+pub mod report;
+pub mod serve;
+mod staging;
+
+pub use crate::{
+    ast::unreal_cpp_header::parse_unreal_cpp_header,
+    backends::{
+        Backend, BackendRegistry,
+        mdbook::{render_symbol, render_to_string},
+    },
+    config::Config,
+    document::Document,
+};
+use std::{
+    fs::{create_dir_all, read_to_string},
+    io::Result,
+    path::Path,
+};
+
+const BOM: char = '\u{FEFF}';
+
+fn ensure_dir(path: &Path) {
+    if path.is_dir() {
+        let _ = create_dir_all(path);
+    } else {
+        let mut path = path.to_path_buf();
+        path.pop();
+        let _ = create_dir_all(&path);
+    }
+}
+
+/// Reads text file, without byte order mark some editors put at its start.
+pub fn read_file(path: impl AsRef<Path>) -> Result<String> {
+    let content = read_to_string(path)?;
+    if content.starts_with(BOM) {
+        Ok(content.chars().skip(1).collect())
+    } else {
+        Ok(content)
+    }
+}
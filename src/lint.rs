@@ -0,0 +1,284 @@
+use crate::{
+    config::{Spellcheck, Terminology, Units},
+    diagnostics::Diagnostics,
+    document::{Document, Function, TypeRef},
+};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
+/// Bundled list of common misspellings, with correction after each of them.
+const MISSPELLINGS: &str = include_str!("misspellings.txt");
+
+/// Prose of Markdown text, with inline code and fenced code blocks blanked out so they are not
+/// checked as regular text. Line structure is kept intact.
+pub fn prose(text: &str) -> String {
+    let mut fenced = false;
+    text.split('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fenced = !fenced;
+                return String::new();
+            }
+            if fenced {
+                return String::new();
+            }
+            line.split('`')
+                .enumerate()
+                .map(|(index, part)| {
+                    if index % 2 == 0 {
+                        part.to_owned()
+                    } else {
+                        " ".repeat(part.len())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Case-insensitive whole-word pattern of term, tolerant to whitespace between its words.
+fn term_pattern(term: &str) -> Option<Regex> {
+    let words = term
+        .split_whitespace()
+        .map(regex::escape)
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        return None;
+    }
+    RegexBuilder::new(&format!(r"\b{}\b", words.join(r"\s+")))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+/// Reports terms written with different capitalization than configured, and banned terms.
+pub fn check_terminology(
+    document: &Document,
+    terminology: &Terminology,
+    diagnostics: &mut Diagnostics,
+) {
+    let terms = terminology
+        .terms
+        .iter()
+        .filter_map(|term| Some((term, term_pattern(term)?)))
+        .collect::<Vec<_>>();
+    let banned = terminology
+        .banned
+        .iter()
+        .filter_map(|(term, replacement)| Some((replacement, term_pattern(term)?)))
+        .collect::<Vec<_>>();
+    if terms.is_empty() && banned.is_empty() {
+        return;
+    }
+    for (location, text) in document.doc_texts() {
+        let text = prose(text);
+        for (term, pattern) in &terms {
+            for found in pattern.find_iter(&text) {
+                let found = found.as_str().split_whitespace().collect::<Vec<_>>();
+                if found != term.split_whitespace().collect::<Vec<_>>() {
+                    diagnostics.warning(
+                        "terminology",
+                        &location,
+                        format!("`{}` should be written as `{}`", found.join(" "), term),
+                    );
+                }
+            }
+        }
+        for (replacement, pattern) in &banned {
+            for found in pattern.find_iter(&text) {
+                let message = if replacement.is_empty() {
+                    format!("Banned term `{}` used", found.as_str())
+                } else {
+                    format!(
+                        "Banned term `{}` used, use `{}` instead",
+                        found.as_str(),
+                        replacement
+                    )
+                };
+                diagnostics.warning("terminology", &location, message);
+            }
+        }
+    }
+}
+
+/// Reports known misspellings, suggesting their corrections. This is not dictionary spellcheck:
+/// only words listed as misspellings, bundled or configured, are reported, any other typos are
+/// not.
+pub fn check_misspellings(
+    document: &Document,
+    spellcheck: &Spellcheck,
+    diagnostics: &mut Diagnostics,
+) {
+    if !spellcheck.enabled {
+        return;
+    }
+    let mut misspellings = MISSPELLINGS
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(' '))
+        .map(|(word, correction)| (word.to_owned(), correction.to_owned()))
+        .collect::<HashMap<_, _>>();
+    for (word, correction) in &spellcheck.misspellings {
+        misspellings.insert(word.to_lowercase(), correction.to_owned());
+    }
+    for word in &spellcheck.words {
+        misspellings.remove(&word.to_lowercase());
+    }
+    // links and HTML tags carry no prose, but may contain anything
+    let markup = Regex::new(r"\]\([^)]*\)|<[^<>]*>|\w+://\S+").unwrap();
+    let word = Regex::new(r"[A-Za-z]+(?:'[A-Za-z]+)?").unwrap();
+    for (location, text) in document.doc_texts() {
+        let text = prose(text);
+        let text = markup.replace_all(&text, " ");
+        for found in word.find_iter(&text) {
+            let found = found.as_str();
+            if let Some(correction) = misspellings.get(&found.to_lowercase()) {
+                diagnostics.warning(
+                    "misspelling",
+                    &location,
+                    format!(
+                        "Misspelled word `{}`, did you mean `{}`?",
+                        found, correction
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Reports floating point parameters without units, of functions in configured categories.
+pub fn check_units(document: &Document, units: &Units, diagnostics: &mut Diagnostics) {
+    if units.categories.is_empty() {
+        return;
+    }
+    let in_categories = |function: &Function| {
+        let Some(category) = function.category() else {
+            return false;
+        };
+        let category = category.trim_matches('"');
+        units.categories.iter().any(|item| {
+            category
+                .strip_prefix(item.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('|'))
+        })
+    };
+    let functions = document
+        .functions
+        .iter()
+        .map(|function| (&function.filename, function.name.to_owned(), function))
+        .chain(
+            document
+                .structs
+                .iter()
+                .chain(document.classes.iter())
+                .flat_map(|item| {
+                    item.methods.iter().map(|function| {
+                        (
+                            &item.filename,
+                            format!("{}::{}", item.name, function.name),
+                            function,
+                        )
+                    })
+                }),
+        );
+    for (filename, symbol, function) in functions {
+        if !in_categories(function) {
+            continue;
+        }
+        for argument in &function.arguments {
+            let type_ref = TypeRef::parse(&argument.value_type);
+            if argument.unit.is_none() && (type_ref.name == "float" || type_ref.name == "double") {
+                diagnostics.warning(
+                    "units",
+                    &format!("{} (`{}`)", filename, symbol),
+                    format!(
+                        "Parameter `{}` has no units, document them with `@param {} [unit]`",
+                        argument.name.as_deref().unwrap_or("_"),
+                        argument.name.as_deref().unwrap_or("Name")
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_check_terminology() {
+    use crate::document::Enum;
+    use std::collections::BTreeMap;
+
+    let document = Document {
+        enums: vec![Enum {
+            name: "EMode".to_owned(),
+            filename: "Mode.h".to_owned(),
+            doc_comments: Some(
+                "Used by blueprint graphs in UE4.\n\n`blueprint` and Blueprint are fine.\n\
+                ```\nblueprint\n```"
+                    .to_owned(),
+            ),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let terminology = Terminology {
+        terms: vec!["Blueprint".to_owned()],
+        banned: BTreeMap::from([("UE4".to_owned(), "Unreal Engine".to_owned())]),
+    };
+    let mut diagnostics = Diagnostics::default();
+    check_terminology(&document, &terminology, &mut diagnostics);
+    let messages = diagnostics
+        .items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Warning in Mode.h (`EMode`): `blueprint` should be written as `Blueprint`",
+            "Warning in Mode.h (`EMode`): Banned term `UE4` used, use `Unreal Engine` instead",
+        ]
+    );
+}
+
+#[test]
+fn test_check_misspellings() {
+    use crate::document::Enum;
+    use std::collections::BTreeMap;
+
+    let document = Document {
+        enums: vec![Enum {
+            name: "EMode".to_owned(),
+            filename: "Mode.h".to_owned(),
+            doc_comments: Some(
+                "Seperate modes of the [Wich](wich.md) plugin, `teh` code is fine.\n\
+                Recieve and Parametr are not."
+                    .to_owned(),
+            ),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let spellcheck = Spellcheck {
+        enabled: true,
+        words: vec!["recieve".to_owned()],
+        misspellings: BTreeMap::from([("parametr".to_owned(), "parameter".to_owned())]),
+    };
+    let mut diagnostics = Diagnostics::default();
+    check_misspellings(&document, &spellcheck, &mut diagnostics);
+    let messages = diagnostics
+        .items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Warning in Mode.h (`EMode`): Misspelled word `Seperate`, did you mean `separate`?",
+            "Warning in Mode.h (`EMode`): Misspelled word `Wich`, did you mean `which`?",
+            "Warning in Mode.h (`EMode`): Misspelled word `Parametr`, did you mean `parameter`?",
+        ]
+    );
+}
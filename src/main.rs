@@ -1,25 +1,19 @@
-#[macro_use]
-extern crate pest_derive;
-
-mod ast;
-mod backends;
-mod config;
-mod document;
-
-use crate::{
-    ast::unreal_cpp_header::parse_unreal_cpp_header,
-    backends::{json::bake_json, mdbook::bake_mdbook},
-    config::*,
-    document::Document,
-};
-use clap::{Arg, Command};
+use clap::{Arg, ArgMatches, Command};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher, recommended_watcher};
 use std::{
-    fs::{create_dir_all, read_to_string},
-    io::Result,
+    collections::HashSet,
+    panic::{AssertUnwindSafe, catch_unwind},
     path::{Path, PathBuf},
+    process,
+    sync::mpsc::{Receiver, channel},
+    time::{Duration, Instant},
+};
+use unreal_doc::{
+    backends::BackendRegistry,
+    doctor::check_environment,
+    pipeline::{BuildOptions, ParseCache, build_document, load_config},
+    serve::{PreviewServer, site_dir},
 };
-
-const BOM: char = '\u{FEFF}';
 
 fn main() {
     let matches = Command::new(env!("CARGO_BIN_NAME"))
@@ -33,6 +27,7 @@ fn main() {
                 .takes_value(true)
                 .value_name("FILE")
                 .default_value("./UnrealDoc.toml")
+                .global(true)
                 .help("UnrealDoc.toml config file"),
         )
         .arg(
@@ -42,142 +37,328 @@ fn main() {
                 .takes_value(true)
                 .value_name("DIR")
                 .required(false)
+                .global(true)
                 .help("Force documentation output to specified directory"),
         )
+        .arg(
+            Arg::new("diff-output")
+                .long("diff-output")
+                .required(false)
+                .global(true)
+                .help("Print changes to existing documentation output instead of writing it"),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .takes_value(true)
+                .value_name("NAME")
+                .required(false)
+                .global(true)
+                .help("Override active profile that conditional book content is checked against"),
+        )
+        .arg(
+            Arg::new("emit-ir")
+                .long("emit-ir")
+                .takes_value(true)
+                .value_name("FILE")
+                .required(false)
+                .global(true)
+                .help("Write parsed document into JSON file before processing it"),
+        )
+        .arg(
+            Arg::new("from-ir")
+                .long("from-ir")
+                .takes_value(true)
+                .value_name("FILE")
+                .required(false)
+                .global(true)
+                .conflicts_with("emit-ir")
+                .help("Read parsed document from JSON file instead of parsing sources"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .required(false)
+                .global(true)
+                .conflicts_with("from-ir")
+                .help("Bake documentation again whenever sources or book pages change"),
+        )
+        .subcommand(
+            Command::new("build")
+                .about("Bake documentation of every given config, parsing shared sources once")
+                .arg(
+                    Arg::new("configs")
+                        .takes_value(true)
+                        .multiple_values(true)
+                        .required(true)
+                        .value_name("FILE")
+                        .help("UnrealDoc.toml config files"),
+                ),
+        )
+        .subcommand(
+            Command::new("doctor")
+                .about("Check config, sources and tools documentation gets baked with"),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Bake documentation and serve it on localhost, baking it again on changes")
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .takes_value(true)
+                        .value_name("PORT")
+                        .default_value("3000")
+                        .help("Port of localhost to serve documentation on"),
+                ),
+        )
         .get_matches();
 
-    let input = matches
-        .value_of("input")
-        .expect("No `input` argument provided!");
-    let input = PathBuf::from(input);
-    let output = matches.value_of("output").map(PathBuf::from);
-    let output = output.as_deref();
-    let (mut config, dir) = load_config(&input, output);
-
-    let mut document = Document::default();
-    for path in &config.input_dirs {
-        document_path(path, path, &mut document, &config.settings);
+    if matches.subcommand_matches("doctor").is_some() {
+        let input = matches
+            .value_of("input")
+            .expect("No `input` argument provided!");
+        let output = matches.value_of("output").map(Path::new);
+        let checks = check_environment(Path::new(input), output);
+        for check in &checks {
+            let status = if check.passed { "ok" } else { "FAILED" };
+            println!("[{}] {}", status, check.message);
+        }
+        let failed = checks.iter().filter(|check| !check.passed).count();
+        if failed > 0 {
+            println!("{} of {} checks failed", failed, checks.len());
+            process::exit(1);
+        }
+        println!("All {} checks passed", checks.len());
+        return;
     }
-    document.resolve_injects();
-    document.resolve_self_names_in_docs();
-    document.sort_items_by_name();
 
-    match config.backend {
-        Backend::Json => bake_json(&document, &config),
-        Backend::MdBook => {
-            if let Ok(site_url) = std::env::var("UNREAL_DOC_MDBOOK_SITE_URL") {
-                if let Some(config) = config.backend_mdbook.as_mut() {
-                    config.site_url = Some(site_url.to_owned());
+    let inputs = match matches.subcommand_matches("build") {
+        Some(build) => build
+            .values_of("configs")
+            .expect("No config files provided!")
+            .map(PathBuf::from)
+            .collect::<Vec<_>>(),
+        None => vec![PathBuf::from(
+            matches
+                .value_of("input")
+                .expect("No `input` argument provided!"),
+        )],
+    };
+    let output = matches.value_of("output").map(PathBuf::from);
+    if output.is_some() && inputs.len() > 1 {
+        panic!("`output` argument can not be used with more than one config file!");
+    }
+    for name in ["emit-ir", "from-ir"] {
+        if matches.is_present(name) && inputs.len() > 1 {
+            panic!(
+                "`{}` argument can not be used with more than one config file!",
+                name
+            );
+        }
+    }
+    let registry = BackendRegistry::default();
+    let serve = matches.subcommand_matches("serve").map(|serve| {
+        serve
+            .value_of("port")
+            .and_then(|port| port.parse::<u16>().ok())
+            .expect("Invalid `port` argument!")
+    });
+    let watch = matches.is_present("watch") || serve.is_some();
+    let mut server = None;
+    // configs of single run are likely to share sources, e.g. plugins of the same project, and
+    // watch mode bakes the same sources over and over
+    let mut cache = ParseCache::new(inputs.len() > 1 || watch);
+    // kept between bakings, so changes made while baking trigger another one
+    let mut watcher = watch.then(SourceWatcher::new);
+    loop {
+        let parsed = cache.parsed;
+        let reused = cache.reused;
+        let mut failed = false;
+        for input in &inputs {
+            if watch {
+                // errors of sources being edited are reported, but do not end watching
+                failed |= catch_unwind(AssertUnwindSafe(|| {
+                    bake_config(input, &matches, output.as_deref(), &registry, &mut cache)
+                }))
+                .is_err();
+            } else {
+                bake_config(input, &matches, output.as_deref(), &registry, &mut cache);
+            }
+        }
+        if inputs.len() > 1 {
+            println!(
+                "Baked {} configs, {} headers parsed, {} reused",
+                inputs.len(),
+                cache.parsed - parsed,
+                cache.reused - reused
+            );
+        } else if watch && !failed {
+            println!(
+                "Baked documentation, {} headers parsed, {} reused",
+                cache.parsed - parsed,
+                cache.reused - reused
+            );
+        }
+        if let Some(port) = serve {
+            match &server {
+                None => {
+                    let (config, _) = load_config(&inputs[0], output.as_deref());
+                    server = Some(PreviewServer::start(site_dir(&config), port));
                 }
+                Some(server) if !failed => server.reload(),
+                Some(_) => {}
             }
-            bake_mdbook(&document, &config, &dir)
+        }
+        match &mut watcher {
+            Some(watcher) => watcher.wait_for_changes(&inputs, output.as_deref()),
+            None => break,
         }
     }
 }
 
-fn load_config(input: &Path, output: Option<&Path>) -> (Config, PathBuf) {
-    let content =
-        read_file(input).unwrap_or_else(|_| panic!("Input config file not found: {:?}", input));
-    let mut config = toml::from_str::<Config>(&content)
-        .unwrap_or_else(|_| panic!("Could not parse config file:\n{}", content));
-    let mut dir = PathBuf::from(input);
-    if dir.is_file() {
-        dir.pop();
-    }
-    for path in &mut config.dependencies {
-        if path.is_relative() {
-            *path = dir.join(&path);
-        }
-    }
-    for path in &mut config.input_dirs {
-        if path.is_relative() {
-            *path = dir.join(&path);
+/// Watches config files, sources and book pages of configs for changes.
+struct SourceWatcher {
+    watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    watched: HashSet<PathBuf>,
+}
+
+impl SourceWatcher {
+    fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            watcher: recommended_watcher(sender).expect("Could not start file system watcher!"),
+            receiver,
+            watched: Default::default(),
         }
     }
-    if let Some(output) = output {
-        config.output_dir = output.into();
-    }
-    if config.output_dir.is_relative() {
-        config.output_dir = dir.join(&config.output_dir);
-    }
-    for path in &config.dependencies {
-        let inputs = load_config(path, None).0.input_dirs;
-        config.input_dirs.extend(inputs);
-    }
-    (config, dir)
-}
 
-fn document_path(path: &Path, root: &Path, document: &mut Document, settings: &Settings) {
-    if path.is_file() {
-        if let Some(ext) = path.extension() {
-            if ext == "h" {
-                let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
-                let content =
-                    read_file(&path).unwrap_or_else(|_| panic!("Could not read file: {:?}", &path));
-                document_header(&path, &content, document, settings);
-            } else if ext == "md" {
-                let content =
-                    read_file(path).unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
-                let root = root.to_string_lossy().into_owned();
-                let path = path.to_string_lossy().into_owned();
-                let pat: &[_] = &['/', '\\'];
-                let relative = path
-                    .trim_start_matches(&root)
-                    .trim_start_matches(pat)
-                    .replace('\\', "/")
-                    .to_owned();
-                document.book.insert(relative, content);
-            } else if let Some(file_name) = path.file_name() {
-                if file_name == "index.txt" {
-                    let content = read_file(path)
-                        .unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
-                    let root = root.to_string_lossy().into_owned();
-                    let path = path.to_string_lossy().into_owned();
-                    let pat: &[_] = &['/', '\\'];
-                    let relative = path
-                        .trim_start_matches(&root)
-                        .trim_start_matches(pat)
-                        .replace('\\', "/")
-                        .to_owned();
-                    document.book.insert(relative, content);
+    /// Blocks until any config file, source or book page of given configs changes. Changes
+    /// coming in quick succession (e.g. editor saving several files) are waited for as one.
+    /// Changes of baked output are ignored, as baking makes them itself.
+    fn wait_for_changes(&mut self, inputs: &[PathBuf], output: Option<&Path>) {
+        // configs are read again, since their changes could add sources
+        let mut paths = vec![];
+        let mut output_dirs = vec![];
+        for input in inputs {
+            paths.push(input.to_owned());
+            if let Ok((config, _)) = catch_unwind(|| load_config(input, output)) {
+                paths.extend(config.input_dirs);
+                paths.extend(config.examples_dir);
+                paths.extend(config.code_owners);
+                output_dirs.extend(std::path::absolute(&config.output_dir));
+            }
+        }
+        for path in paths {
+            if !path.exists() || self.watched.contains(&path) {
+                continue;
+            }
+            match self.watcher.watch(&path, RecursiveMode::Recursive) {
+                Ok(()) => {
+                    self.watched.insert(path);
                 }
+                Err(error) => println!("Could not watch for changes of {:?}: {}", path, error),
             }
         }
-    } else if path.is_dir() {
-        for entry in path
-            .read_dir()
-            .unwrap_or_else(|_| panic!("Could not read directory: {:?}", path))
-        {
-            let path = entry.expect("Could not read directory entry!").path();
-            document_path(&path, root, document, settings);
+        println!("Watching for changes...");
+        while let Ok(event) = self.receiver.recv() {
+            if event.is_ok_and(|event| {
+                !matches!(event.kind, EventKind::Access(_))
+                    && (event.paths.is_empty()
+                        || !event
+                            .paths
+                            .iter()
+                            .all(|path| is_output_path(path, &output_dirs)))
+            }) {
+                break;
+            }
         }
+        while self
+            .receiver
+            .recv_timeout(Duration::from_millis(200))
+            .is_ok()
+        {}
+        println!("Changes detected, baking again");
     }
 }
 
-fn document_header(path: &Path, content: &str, document: &mut Document, settings: &Settings) {
-    parse_unreal_cpp_header(content, document, settings, path).unwrap_or_else(|error| {
-        panic!(
-            "Could not parse Unreal C++ header file content!\nFile: {:?}\nError:\n{}",
-            path, error
-        )
-    });
+/// Tells if path is in one of given output directories, or in staged or previous output that
+/// baking keeps next to them, in `.{name}.staging-{pid}` and `.{name}.previous-{pid}`
+/// directories.
+fn is_output_path(path: &Path, output_dirs: &[PathBuf]) -> bool {
+    let Ok(path) = std::path::absolute(path) else {
+        return false;
+    };
+    output_dirs.iter().any(|output| {
+        if path.starts_with(output) {
+            return true;
+        }
+        let Some(name) = output.file_name() else {
+            return false;
+        };
+        ["staging", "previous"].into_iter().any(|kind| {
+            let sibling = output.with_file_name(format!(".{}.{}-", name.to_string_lossy(), kind));
+            path.to_string_lossy()
+                .starts_with(sibling.to_string_lossy().as_ref())
+        })
+    })
 }
 
-fn ensure_dir(path: &Path) {
-    if path.is_dir() {
-        let _ = create_dir_all(path);
-    } else {
-        let mut path = path.to_path_buf();
-        path.pop();
-        let _ = create_dir_all(&path);
+fn bake_config(
+    input: &Path,
+    matches: &ArgMatches,
+    output: Option<&Path>,
+    registry: &BackendRegistry,
+    cache: &mut ParseCache,
+) {
+    let (mut config, dir) = load_config(input, output);
+    config.diff_output = matches.is_present("diff-output");
+    if matches.subcommand_matches("serve").is_some() {
+        // served book has to be built
+        config.backend_mdbook.get_or_insert_default().build = true;
+    }
+    let backends = config.active_backends();
+    for backend in &backends {
+        if registry.get(backend.name()).is_none() {
+            panic!(
+                "Unknown backend: {}. Available backends: {}",
+                backend.name(),
+                registry.names().collect::<Vec<_>>().join(", ")
+            );
+        }
     }
-}
 
-fn read_file(path: impl AsRef<Path>) -> Result<String> {
-    let content = read_to_string(path)?;
-    if content.starts_with(BOM) {
-        Ok(content.chars().skip(1).collect())
-    } else {
-        Ok(content)
+    let started = Instant::now();
+    let options = BuildOptions {
+        from_ir: matches.value_of("from-ir").map(Path::new),
+        emit_ir: matches.value_of("emit-ir").map(Path::new),
+        profile: matches.value_of("profile"),
+    };
+    // errors end baking here, and in watch mode only this baking
+    let (document, mut report) = build_document(&mut config, &dir, cache, &options)
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    // all backends bake the same document, parsed only once
+    for backend in &backends {
+        let mut config = config.clone();
+        config.backend = backend.to_owned();
+        if backends.len() > 1 {
+            config.output_dir = config.output_dir.join(backend.name().to_lowercase());
+        }
+        let phase_started = Instant::now();
+        let stats = registry
+            .get(backend.name())
+            .expect("Backend is not registered!")
+            .bake(&document, &config, &dir)
+            .unwrap_or_else(|error| {
+                panic!("Could not bake {} documentation: {}", backend.name(), error)
+            });
+        report.add_duration(format!("bake.{}", backend.name()), phase_started.elapsed());
+        report.add_diagnostics(&stats.diagnostics);
+        report.pages.insert(backend.name().to_owned(), stats.pages);
+    }
+    report.add_duration("total", started.elapsed());
+    if let Some(path) = &config.report {
+        report.write(path);
     }
 }
@@ -0,0 +1,707 @@
+//! Stages of documentation baking run by `unreal-doc` command, exposed for tools embedding it:
+//! load config, parse its sources into `Document`, process parsed document and bake it with
+//! backend from `BackendRegistry`. Tools can modify document between any of these stages.
+
+use crate::{
+    ast::unreal_cpp_header::parse_unreal_cpp_header,
+    config::{Config, ParseSettings},
+    diagnostics::Diagnostics,
+    document::{Document, glob_expression},
+    ensure_dir,
+    lint::{check_misspellings, check_terminology, check_units},
+    plugins::ElementPlugins,
+    read_file,
+    remote::fetch_remote_source,
+    report::{CoverageReport, RunReport},
+};
+use regex::Regex;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fs::{metadata, remove_file, rename, write},
+    path::{Path, PathBuf},
+    process,
+    time::{Instant, SystemTime},
+};
+
+/// Loads config file, with paths resolved relative to its directory, and returns it together
+/// with that directory, which is the root other stages resolve paths against. Given output
+/// directory overrides the one from config.
+pub fn load_config(input: &Path, output: Option<&Path>) -> (Config, PathBuf) {
+    let content =
+        read_file(input).unwrap_or_else(|_| panic!("Input config file not found: {:?}", input));
+    let mut config = toml::from_str::<Config>(&content)
+        .unwrap_or_else(|_| panic!("Could not parse config file:\n{}", content));
+    let mut dir = PathBuf::from(input);
+    if dir.is_file() {
+        dir.pop();
+    }
+    for path in &mut config.dependencies {
+        if path.is_relative() {
+            *path = dir.join(&path);
+        }
+    }
+    for path in &mut config.input_dirs {
+        if path.is_relative() {
+            *path = dir.join(&path);
+        }
+    }
+    if !config.remote_sources.is_empty() {
+        let cache_dir = match &config.remote_cache_dir {
+            Some(path) => dir.join(path),
+            None => dir.join(".unreal-doc").join("remote"),
+        };
+        for source in &config.remote_sources {
+            let checkout = fetch_remote_source(source, &cache_dir);
+            if source.paths.is_empty() {
+                config.input_dirs.push(checkout);
+            } else {
+                for path in &source.paths {
+                    config.input_dirs.push(checkout.join(path));
+                }
+            }
+        }
+    }
+    if let Some(path) = &mut config.examples_dir
+        && path.is_relative()
+    {
+        *path = dir.join(&path);
+    }
+    if let Some(path) = &mut config.code_owners
+        && path.is_relative()
+    {
+        *path = dir.join(&path);
+    }
+    if let Some(path) = &mut config.report
+        && path.is_relative()
+    {
+        *path = dir.join(&path);
+    }
+    if let Some(settings) = config.settings.take() {
+        println!("`settings` section is deprecated, use `parse` and `render` sections instead");
+        (config.parse, config.render) = settings.split();
+    }
+    if let Some(output) = output {
+        config.output_dir = output.into();
+    }
+    if config.output_dir.is_relative() {
+        config.output_dir = dir.join(&config.output_dir);
+    }
+    for path in &config.dependencies {
+        let inputs = load_config(path, None).0.input_dirs;
+        config.input_dirs.extend(inputs);
+    }
+    (config, dir)
+}
+
+/// Modification times of header and files it depends on, `None` for missing files.
+type ModifiedTimes = Vec<Option<SystemTime>>;
+
+/// Headers parsed for previous configs of the same run, so headers shared between configs get
+/// parsed once. Parsed headers are keyed by their paths and everything else parsing depends on,
+/// and get parsed again once their files or C++ sources they depend on are modified, so watch
+/// mode re-parses changed headers only.
+pub struct ParseCache {
+    enabled: bool,
+    headers: HashMap<(PathBuf, String), (ModifiedTimes, Document)>,
+    /// Number of headers parsed so far.
+    pub parsed: usize,
+    /// Number of headers taken from cache so far.
+    pub reused: usize,
+}
+
+impl Default for ParseCache {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl ParseCache {
+    /// Cache that only counts parsed headers when not enabled.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            headers: Default::default(),
+            parsed: 0,
+            reused: 0,
+        }
+    }
+
+    /// Symbols parsed from given header, parsed with given function unless they were parsed
+    /// already in the same context and neither header nor given files it depends on were
+    /// modified since. Parsing gets header content and contents of files it depends on, all read
+    /// with given function. Symbols parsed before from the same contents
+    /// are taken from given directory of stored headers, if any, and newly parsed ones are
+    /// stored there.
+    fn header(
+        &mut self,
+        path: &Path,
+        dependencies: &[PathBuf],
+        context: &str,
+        stored_dir: Option<&Path>,
+        read: impl FnOnce() -> (String, Vec<String>),
+        parse: impl FnOnce(String, Vec<String>) -> Document,
+    ) -> Document {
+        let key = (path.to_owned(), context.to_owned());
+        let modified = std::iter::once(path)
+            .chain(dependencies.iter().map(PathBuf::as_path))
+            .map(|path| metadata(path).and_then(|metadata| metadata.modified()).ok())
+            .collect::<Vec<_>>();
+        if let Some((times, document)) = self.headers.get(&key)
+            && *times == modified
+        {
+            self.reused += 1;
+            return document.to_owned();
+        }
+        let (content, sources) = read();
+        let stored = stored_dir.map(|dir| {
+            let path = path.to_string_lossy();
+            let mut parts = vec![env!("CARGO_PKG_VERSION"), GRAMMAR, context, &path, &content];
+            parts.extend(sources.iter().map(|source| source.as_str()));
+            dir.join(format!("{:016x}.json", content_hash(&parts)))
+        });
+        let document = match stored.as_deref().and_then(load_stored_header) {
+            Some(document) => {
+                self.reused += 1;
+                document
+            }
+            None => {
+                let document = parse(content, sources);
+                self.parsed += 1;
+                if let Some(stored) = &stored {
+                    store_header(stored, &document);
+                }
+                document
+            }
+        };
+        if self.enabled && modified[0].is_some() {
+            self.headers.insert(key, (modified, document.to_owned()));
+        }
+        document
+    }
+}
+
+/// Grammar of headers, part of what stored parsed headers depend on.
+const GRAMMAR: &str = include_str!("ast/unreal_cpp_header.pest");
+
+/// 64-bit FNV-1a hash of given parts, stable between runs and platforms.
+fn content_hash(parts: &[&str]) -> u64 {
+    parts.iter().fold(0xcbf29ce484222325_u64, |hash, part| {
+        // separator byte that never appears in text, so parts can not blend into each other
+        part.bytes()
+            .chain(std::iter::once(0xff))
+            .fold(hash, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    })
+}
+
+fn load_stored_header(path: &Path) -> Option<Document> {
+    serde_json::from_str(&read_file(path).ok()?).ok()
+}
+
+/// Stores parsed header for next runs. Failing to store it only makes next run parse it again,
+/// so errors are ignored.
+fn store_header(path: &Path, document: &Document) {
+    let Ok(content) = serde_json::to_string(document) else {
+        return;
+    };
+    ensure_dir(path);
+    // written next to target first, so other runs never read it half-written
+    let staged = path.with_extension(format!("json.{}.tmp", process::id()));
+    if write(&staged, content).is_ok() && rename(&staged, path).is_err() {
+        let _ = remove_file(&staged);
+    }
+}
+
+/// Parses headers and book pages of config input directories into new document, with element
+/// plugins of config running for the time of parsing.
+pub fn parse_document(config: &Config, root: &Path, cache: &mut ParseCache) -> Document {
+    let mut document = Document::default();
+    let mut plugins = ElementPlugins::start(&config.plugins, root);
+    let context = format!(
+        "{}|{:?}|{:?}",
+        serde_json::to_string(&config.parse).expect("Could not serialize parse settings!"),
+        config.plugins,
+        root
+    );
+    let stored_dir = config.incremental.then(|| root.join(".unreal-doc-cache"));
+    let filter = SourceFilter::new(config);
+    let mut sources = Sources {
+        settings: &config.parse,
+        filter: &filter,
+        plugins: &mut plugins,
+        cache,
+        context: &context,
+        stored_dir: stored_dir.as_deref(),
+    };
+    for path in &config.input_dirs {
+        document_path(path, path, &mut document, &mut sources);
+    }
+    document
+}
+
+/// Writes parsed document into JSON file, intermediate representation that can be cached,
+/// compared between runs or read by other tools. Maps get sorted by keys, so the same sources
+/// always give the same file.
+pub fn save_document(document: &Document, path: &Path) {
+    let value = serde_json::to_value(document).expect("Could not serialize document into JSON!");
+    let content =
+        serde_json::to_string_pretty(&value).expect("Could not serialize document into JSON!");
+    ensure_dir(path);
+    write(path, content)
+        .unwrap_or_else(|_| panic!("Could not write intermediate representation: {:?}", path));
+}
+
+/// Reads document saved with `save_document`, to be processed instead of parsing sources.
+pub fn load_document(path: &Path) -> Document {
+    let content = read_file(path)
+        .unwrap_or_else(|_| panic!("Intermediate representation file not found: {:?}", path));
+    serde_json::from_str(&content).unwrap_or_else(|error| {
+        panic!(
+            "Could not parse intermediate representation file: {:?}\n{}",
+            path, error
+        )
+    })
+}
+
+/// Filters parsed document and resolves everything its doc comments and book pages refer to,
+/// as configured. Returns documentation coverage, measured before undocumented symbols can get
+/// excluded.
+pub fn process_document(document: &mut Document, config: &Config, root: &Path) -> CoverageReport {
+    document.remove_hidden(&config.render);
+    document.extract_front_matter();
+    if let Some(path) = &config.examples_dir {
+        document_examples(path, path, document);
+    }
+    if config.render.exclude_editor_only {
+        document.remove_editor_only();
+    }
+    if config.render.require_reflection {
+        document.remove_unreflected();
+    }
+    document.resolve_conditions(config.profile.as_deref(), &config.tags);
+    document.resolve_injects();
+    document.resolve_includes(root);
+    document.apply_substitutions(&config.substitutions);
+    document.resolve_ui_markup();
+    document.extract_aliases();
+    let code_owners = config.code_owners.as_ref().map(|path| {
+        read_file(path).unwrap_or_else(|_| panic!("Could not read code owners file: {:?}", path))
+    });
+    document.extract_owners(code_owners.as_deref());
+    document.extract_stability();
+    document.extract_failure_modes();
+    document.extract_faqs();
+    document.extract_parameter_docs();
+    if config.render.hide_experimental {
+        document.remove_experimental();
+    }
+    if config.render.hide_deprecated {
+        document.remove_deprecated();
+    }
+    let coverage = CoverageReport::measure(document);
+    document.apply_undocumented_mode(config.render.undocumented);
+    if config.render.audit {
+        print!("{}", document.audit_report());
+    }
+    coverage
+}
+
+/// Checks doc comments of processed document against terminology, misspellings and units rules
+/// of config.
+pub fn lint_document(document: &Document, config: &Config) -> Diagnostics {
+    let mut diagnostics = Diagnostics::default();
+    check_terminology(document, &config.terminology, &mut diagnostics);
+    check_misspellings(document, &config.spellcheck, &mut diagnostics);
+    check_units(document, &config.units, &mut diagnostics);
+    diagnostics
+}
+
+/// Prepares processed document for presentation, after which it is ready to be baked. Book
+/// backends also get their redirects and site URL (`UNREAL_DOC_MDBOOK_SITE_URL` environment
+/// variable overrides the one from config) set up here.
+pub fn finish_document(document: &mut Document, config: &mut Config) {
+    document.resolve_self_names_in_docs();
+    if !config.render.raw_html {
+        document.sanitize_html_in_docs();
+    }
+    document.sort_items_by_name();
+
+    if !config
+        .active_backends()
+        .iter()
+        .any(|backend| backend.is_book())
+    {
+        return;
+    }
+    if let Ok(site_url) = std::env::var("UNREAL_DOC_MDBOOK_SITE_URL")
+        && let Some(config) = config.backend_mdbook.as_mut()
+    {
+        config.site_url = Some(site_url.to_owned());
+    }
+    if let Some(mdbook) = config.backend_mdbook.as_ref() {
+        for alias in document.add_aliases(&mdbook.redirects) {
+            println!(
+                "Redirect from `{}` points to symbol that is not documented: `{}`",
+                alias, mdbook.redirects[&alias]
+            );
+        }
+    }
+}
+
+/// How `build_document` gets parsed document and what it overrides in config.
+#[derive(Debug, Default, Clone)]
+pub struct BuildOptions<'a> {
+    /// Intermediate representation file read instead of parsing sources.
+    pub from_ir: Option<&'a Path>,
+    /// Intermediate representation file parsed document gets written into.
+    pub emit_ir: Option<&'a Path>,
+    /// Profile that conditional book content is checked against instead of the one from config.
+    pub profile: Option<&'a str>,
+}
+
+/// Runs all stages up to baking: parses (with headers taken from given cache when possible),
+/// processes, lints (reporting found problems) and finishes document of given config. Returns
+/// document ready to be baked together with run report of it, with durations of these stages,
+/// or error when linting found errors.
+pub fn build_document(
+    config: &mut Config,
+    root: &Path,
+    cache: &mut ParseCache,
+    options: &BuildOptions,
+) -> Result<(Document, RunReport), Box<dyn Error>> {
+    if let Some(profile) = options.profile {
+        config.profile = Some(profile.to_owned());
+    }
+    let mut durations = vec![];
+    let started = Instant::now();
+    let mut document = match options.from_ir {
+        Some(path) => load_document(path),
+        None => parse_document(config, root, cache),
+    };
+    if let Some(path) = options.emit_ir {
+        save_document(&document, path);
+    }
+    durations.push(("parse", started.elapsed()));
+    let started = Instant::now();
+    // from here on parsed document only gets filtered and prepared for presentation
+    let coverage = process_document(&mut document, config, root);
+    durations.push(("process", started.elapsed()));
+    let started = Instant::now();
+    let diagnostics = lint_document(&document, config);
+    diagnostics.report()?;
+    durations.push(("lint", started.elapsed()));
+    finish_document(&mut document, config);
+    let mut report = RunReport::new(&document, coverage);
+    report.add_diagnostics(&diagnostics.items);
+    for (phase, duration) in durations {
+        report.add_duration(phase, duration);
+    }
+    Ok((document, report))
+}
+
+/// Include and exclude glob patterns of config, telling which headers get documented.
+struct SourceFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl SourceFilter {
+    fn new(config: &Config) -> Self {
+        let patterns = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(&format!(
+                        "^{}$",
+                        glob_expression(pattern.trim_start_matches('/'))
+                    ))
+                    .unwrap_or_else(|_| panic!("Invalid glob pattern: {}", pattern))
+                })
+                .collect()
+        };
+        Self {
+            include: patterns(&config.include),
+            exclude: patterns(&config.exclude),
+        }
+    }
+
+    /// Tells if header at given path, relative to its input directory, gets documented.
+    fn matches(&self, path: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(path)))
+            && !self.exclude.iter().any(|re| re.is_match(path))
+    }
+}
+
+/// What sources of single config get parsed with.
+struct Sources<'a> {
+    settings: &'a ParseSettings,
+    filter: &'a SourceFilter,
+    plugins: &'a mut ElementPlugins,
+    cache: &'a mut ParseCache,
+    /// Everything besides header content that parsed symbols depend on.
+    context: &'a str,
+    /// Directory of parsed headers stored between runs.
+    stored_dir: Option<&'a Path>,
+}
+
+fn document_path(path: &Path, root: &Path, document: &mut Document, sources: &mut Sources) {
+    if path.is_file() {
+        if let Some(ext) = path.extension() {
+            if ext == "h" {
+                let relative = relative_path(path, root);
+                if !sources.filter.matches(&relative) {
+                    return;
+                }
+                let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+                let settings = sources.settings;
+                let plugins = &mut *sources.plugins;
+                let paired = if settings.constructor_defaults || settings.lifetime_replication {
+                    paired_source_paths(&path)
+                } else {
+                    vec![]
+                };
+                let read = || {
+                    let content = read_file(&path)
+                        .unwrap_or_else(|_| panic!("Could not read file: {:?}", &path));
+                    let sources = paired
+                        .iter()
+                        .filter_map(|path| read_file(path).ok())
+                        .collect();
+                    (content, sources)
+                };
+                let parse = |content: String, sources: Vec<String>| {
+                    let mut header = Document::default();
+                    document_header(&path, &content, &mut header, settings, plugins);
+                    if settings.constructor_defaults || settings.lifetime_replication {
+                        for source in std::iter::once(&content).chain(sources.iter()) {
+                            if settings.constructor_defaults {
+                                header.apply_constructor_defaults(source);
+                            }
+                            if settings.lifetime_replication {
+                                header.apply_lifetime_replication(source);
+                            }
+                        }
+                    }
+                    header
+                };
+                let header = sources.cache.header(
+                    &path,
+                    &paired,
+                    sources.context,
+                    sources.stored_dir,
+                    read,
+                    parse,
+                );
+                document.append(header);
+                if let Some(file_name) = path.file_name() {
+                    document.set_header_path(&file_name.to_string_lossy(), &relative);
+                }
+            } else if ext == "md" {
+                let content =
+                    read_file(path).unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
+                document.book.insert(relative_path(path, root), content);
+            } else if let Some(file_name) = path.file_name()
+                && file_name == "index.txt"
+            {
+                let content =
+                    read_file(path).unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
+                document.book.insert(relative_path(path, root), content);
+            } else if let Some(file_name) = path.file_name()
+                && file_name == "glossary.toml"
+            {
+                let content =
+                    read_file(path).unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
+                let glossary =
+                    toml::from_str::<BTreeMap<String, String>>(&content).unwrap_or_else(|error| {
+                        panic!("Could not parse glossary file: {:?}\n{}", path, error)
+                    });
+                document.glossary.extend(glossary);
+            }
+        }
+    } else if path.is_dir() {
+        for entry in path
+            .read_dir()
+            .unwrap_or_else(|_| panic!("Could not read directory: {:?}", path))
+        {
+            let path = entry.expect("Could not read directory entry!").path();
+            document_path(&path, root, document, sources);
+        }
+    }
+}
+
+/// Path relative to given root directory, with forward slashes.
+fn relative_path(path: &Path, root: &Path) -> String {
+    let root = root.to_string_lossy().into_owned();
+    let path = path.to_string_lossy().into_owned();
+    let pat: &[_] = &['/', '\\'];
+    path.trim_start_matches(&root)
+        .trim_start_matches(pat)
+        .replace('\\', "/")
+}
+
+fn document_examples(path: &Path, root: &Path, document: &mut Document) {
+    if path.is_file() {
+        if let Some(ext) = path.extension()
+            && (ext == "h" || ext == "cpp")
+        {
+            let content =
+                read_file(path).unwrap_or_else(|_| panic!("Could not read file: {:?}", path));
+            document.add_example_file(&relative_path(path, root), &content);
+        }
+    } else if path.is_dir() {
+        for entry in path
+            .read_dir()
+            .unwrap_or_else(|_| panic!("Could not read directory: {:?}", path))
+        {
+            let path = entry.expect("Could not read directory entry!").path();
+            document_examples(&path, root, document);
+        }
+    }
+}
+
+/// Candidate paths of C++ source file paired with given header: next to it, or in `Private`
+/// directory mirroring its `Public` or `Classes` directory.
+fn paired_source_paths(path: &Path) -> Vec<PathBuf> {
+    let source = path.with_extension("cpp");
+    let mut result = vec![source.clone()];
+    let components = source.components().collect::<Vec<_>>();
+    for (index, component) in components.iter().enumerate() {
+        if component.as_os_str() == "Public" || component.as_os_str() == "Classes" {
+            let mut mirrored = PathBuf::new();
+            for (other, component) in components.iter().enumerate() {
+                if other == index {
+                    mirrored.push("Private");
+                } else {
+                    mirrored.push(component);
+                }
+            }
+            result.push(mirrored);
+        }
+    }
+    result
+}
+
+fn document_header(
+    path: &Path,
+    content: &str,
+    document: &mut Document,
+    settings: &ParseSettings,
+    plugins: &mut ElementPlugins,
+) {
+    parse_unreal_cpp_header(content, document, settings, plugins, path).unwrap_or_else(|error| {
+        panic!(
+            "Could not parse Unreal C++ header file content!\nFile: {:?}\nError:\n{}",
+            path, error
+        )
+    });
+}
+
+#[test]
+fn test_build_document() {
+    let (mut config, root) = load_config(Path::new("resources/UnrealDoc.toml"), None);
+    assert_eq!(config.output_dir, root.join("./docs"));
+    assert_eq!(config.examples_dir, Some(root.join("./examples")));
+    let (document, _) = build_document(
+        &mut config,
+        &root,
+        &mut Default::default(),
+        &Default::default(),
+    )
+    .unwrap();
+    assert!(document.classes.iter().any(|class| class.name == "Bar"));
+    assert!(document.structs.iter().any(|item| item.name == "Foo"));
+    assert!(document.book.contains_key("hello.md"));
+    assert!(!document.examples.is_empty());
+
+    let mut cache = ParseCache::new(true);
+    parse_document(&config, &root, &mut cache);
+    parse_document(&config, &root, &mut cache);
+    assert_eq!(cache.parsed, cache.reused);
+    assert!(cache.parsed > 0);
+}
+
+#[test]
+fn test_source_filter() {
+    let (mut config, root) = load_config(Path::new("resources/UnrealDoc.toml"), None);
+    config.include = vec!["Source/**/Public/**/*.h".to_owned()];
+    config.exclude = vec!["**/ThirdParty/**".to_owned()];
+    let filter = SourceFilter::new(&config);
+    assert!(filter.matches("Source/Game/Public/Actor.h"));
+    assert!(filter.matches("Source/Public/Items/Item.h"));
+    assert!(!filter.matches("Source/Game/Private/Actor.h"));
+    assert!(!filter.matches("Source/Game/Public/ThirdParty/Lib.h"));
+
+    config.include.clear();
+    config.exclude = vec!["*.h".to_owned()];
+    let document = parse_document(&config, &root, &mut Default::default());
+    assert!(document.classes.is_empty());
+    assert!(document.book.contains_key("hello.md"));
+}
+
+#[test]
+fn test_stored_headers() {
+    let dir = std::env::temp_dir().join(format!("unreal-doc-stored-{}", process::id()));
+    let parse = |_, _| Document {
+        enums: vec![crate::document::Enum {
+            name: "EFoo".to_owned(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut cache = ParseCache::default();
+    let read = || ("enum EFoo {};".to_owned(), vec![]);
+    cache.header(Path::new("Foo.h"), &[], "", Some(&dir), read, parse);
+    let read = || ("enum EFoo {};".to_owned(), vec![]);
+    let document = cache.header(Path::new("Foo.h"), &[], "", Some(&dir), read, |_, _| {
+        panic!("Stored header should not be parsed again!")
+    });
+    assert_eq!(document.enums[0].name, "EFoo");
+    let read = || ("enum EFoo { A };".to_owned(), vec![]);
+    cache.header(Path::new("Foo.h"), &[], "", Some(&dir), read, parse);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!((cache.parsed, cache.reused), (2, 1));
+}
+
+#[test]
+fn test_parse_cache_sources() {
+    let dir = std::env::temp_dir().join(format!("unreal-doc-sources-{}", process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let header = dir.join("Foo.h");
+    let source = dir.join("Foo.cpp");
+    write(&header, "class Foo {};").unwrap();
+    write(&source, "Foo::Foo() {}").unwrap();
+    let mut cache = ParseCache::new(true);
+    let dependencies = [source.clone()];
+    let read = || (String::default(), vec![]);
+    let parse = |_, _| Document::default();
+    cache.header(&header, &dependencies, "", None, read, parse);
+    cache.header(&header, &dependencies, "", None, read, parse);
+    assert_eq!((cache.parsed, cache.reused), (1, 1));
+    let modified = SystemTime::now() + std::time::Duration::from_secs(60);
+    std::fs::File::options()
+        .write(true)
+        .open(&source)
+        .and_then(|file| file.set_modified(modified))
+        .unwrap();
+    cache.header(&header, &dependencies, "", None, read, parse);
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!((cache.parsed, cache.reused), (2, 1));
+}
+
+#[test]
+fn test_intermediate_representation() {
+    let (config, root) = load_config(Path::new("resources/UnrealDoc.toml"), None);
+    let document = parse_document(&config, &root, &mut Default::default());
+    let path = std::env::temp_dir().join(format!("unreal-doc-ir-{}.json", std::process::id()));
+    save_document(&document, &path);
+    let loaded = load_document(&path);
+    let saved = read_file(&path).unwrap();
+    save_document(&loaded, &path);
+    let resaved = read_file(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(saved, resaved);
+    assert_eq!(loaded.classes.len(), document.classes.len());
+    assert_eq!(loaded.proxy_functions.len(), document.proxy_functions.len());
+}
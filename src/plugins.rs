@@ -0,0 +1,117 @@
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// Extraction plugins: long-running commands that get every parsed top level symbol before it is
+/// added to document, as one JSON object per line on their standard input:
+/// ```json
+/// {"kind": "class", "file": "Foo.h", "line": 12, "source": "class UFoo ...", "item": {...}}
+/// ```
+/// For every symbol plugin answers with one line on its standard output: symbol item to add,
+/// possibly modified, or `null` to drop it. Plugins run in order, each one getting symbol
+/// answered by previous one.
+#[derive(Default)]
+pub struct ElementPlugins {
+    plugins: Vec<Plugin>,
+}
+
+struct Plugin {
+    command: String,
+    process: Child,
+    input: Option<ChildStdin>,
+    output: BufReader<ChildStdout>,
+}
+
+impl ElementPlugins {
+    /// Starts plugin commands in system shell, in given working directory.
+    pub fn start(commands: &[String], root: &Path) -> Self {
+        let plugins = commands
+            .iter()
+            .map(|command| {
+                let mut process = if cfg!(windows) {
+                    let mut process = Command::new("cmd");
+                    process.arg("/C").arg(command);
+                    process
+                } else {
+                    let mut process = Command::new("sh");
+                    process.arg("-c").arg(command);
+                    process
+                };
+                let mut process = process
+                    .current_dir(root)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .spawn()
+                    .unwrap_or_else(|_| panic!("Could not run plugin command: {}", command));
+                let input = process.stdin.take();
+                let output = BufReader::new(
+                    process
+                        .stdout
+                        .take()
+                        .expect("Could not get plugin standard output!"),
+                );
+                Plugin {
+                    command: command.to_owned(),
+                    process,
+                    input,
+                    output,
+                }
+            })
+            .collect();
+        Self { plugins }
+    }
+
+    /// Passes symbol item of given kind through plugins. Returns `None` if any plugin drops it.
+    pub fn process<T>(
+        &mut self,
+        kind: &str,
+        mut item: T,
+        filename: &str,
+        line: usize,
+        source: &str,
+    ) -> Option<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        for plugin in &mut self.plugins {
+            let message = json!({
+                "kind": kind,
+                "file": filename,
+                "line": line,
+                "source": source,
+                "item": item,
+            });
+            let input = plugin.input.as_mut().expect("Plugin input is closed!");
+            writeln!(input, "{}", message)
+                .and_then(|_| input.flush())
+                .unwrap_or_else(|_| panic!("Could not send symbol to plugin: {}", plugin.command));
+            let mut answer = String::default();
+            plugin
+                .output
+                .read_line(&mut answer)
+                .unwrap_or_else(|_| panic!("Could not read plugin answer: {}", plugin.command));
+            if answer.is_empty() {
+                panic!("Plugin exited without answering: {}", plugin.command);
+            }
+            item = serde_json::from_str::<Option<T>>(&answer).unwrap_or_else(|error| {
+                panic!(
+                    "Could not parse plugin answer: {}\nError: {}\nAnswer: {}",
+                    plugin.command, error, answer
+                )
+            })?;
+        }
+        Some(item)
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        // closed input tells plugin there are no more symbols
+        self.input.take();
+        let _ = self.process.wait();
+    }
+}
@@ -0,0 +1,92 @@
+use crate::config::RemoteSource;
+use std::{
+    fs::{create_dir_all, remove_dir_all, rename},
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+/// Shallow fetches revision of remote source into its own directory in cache directory, unless
+/// it was fetched already, and returns that directory. Fetching happens in temporary directory
+/// that replaces checkout directory once it succeeds, so interrupted fetch never leaves partial
+/// checkout behind.
+pub fn fetch_remote_source(source: &RemoteSource, cache_dir: &Path) -> PathBuf {
+    let dir = cache_dir.join(checkout_name(source));
+    if dir.is_dir() {
+        return dir;
+    }
+    let staging = cache_dir.join(format!(
+        ".{}.fetch-{}",
+        checkout_name(source),
+        process::id()
+    ));
+    let _ = remove_dir_all(&staging);
+    create_dir_all(&staging)
+        .unwrap_or_else(|_| panic!("Could not create remote source directory: {:?}", staging));
+    println!("Fetching remote source: {} at {}", source.url, source.rev);
+    run_git(&["init", "-q"], &staging);
+    run_git(
+        &["fetch", "-q", "--depth", "1", &source.url, &source.rev],
+        &staging,
+    );
+    run_git(&["checkout", "-q", "--detach", "FETCH_HEAD"], &staging);
+    if rename(&staging, &dir).is_err() {
+        // other run fetched the same revision in the meantime
+        let _ = remove_dir_all(&staging);
+        if !dir.is_dir() {
+            panic!("Could not store remote source in: {:?}", dir);
+        }
+    }
+    dir
+}
+
+/// Name of checkout directory, telling repository and revision, e.g.
+/// `github.com-org-Plugin.git@v1.2`.
+fn checkout_name(source: &RemoteSource) -> String {
+    let url = source.url.split("://").last().unwrap_or_default();
+    let sanitize = |text: &str| {
+        text.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect::<String>()
+    };
+    format!(
+        "{}@{}",
+        sanitize(url).trim_matches('-'),
+        sanitize(&source.rev)
+    )
+}
+
+fn run_git(args: &[&str], dir: &Path) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap_or_else(|_| panic!("Could not run git command: git {}", args.join(" ")));
+    if !status.success() {
+        panic!("Git command failed with {}: git {}", status, args.join(" "));
+    }
+}
+
+#[test]
+fn test_checkout_name() {
+    let source = RemoteSource {
+        url: "https://github.com/org/Plugin.git".to_owned(),
+        rev: "release/1.2".to_owned(),
+        paths: vec![],
+    };
+    assert_eq!(
+        checkout_name(&source),
+        "github.com-org-Plugin.git@release-1.2"
+    );
+    let source = RemoteSource {
+        url: "git@github.com:org/Plugin.git".to_owned(),
+        rev: "v1.2".to_owned(),
+        paths: vec![],
+    };
+    assert_eq!(checkout_name(&source), "git-github.com-org-Plugin.git@v1.2");
+}
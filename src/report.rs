@@ -0,0 +1,203 @@
+use crate::{
+    diagnostics::{Diagnostic, Severity},
+    document::Document,
+    ensure_dir,
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::write,
+    path::Path,
+    time::Duration,
+};
+
+/// What single backend baked, collected for run report.
+#[derive(Debug, Default)]
+pub struct BakeStats {
+    /// Number of baked documentation pages, or output files for backends without pages.
+    pub pages: usize,
+    /// Problems found while baking.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Documented symbols among all symbols of given kind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Coverage {
+    pub documented: usize,
+    pub total: usize,
+    /// Documented symbols in percents, 100 when there are no symbols at all.
+    pub percent: f64,
+}
+
+impl Coverage {
+    fn new(documented: usize, total: usize) -> Self {
+        let percent = (documented * 1000 + total / 2)
+            .checked_div(total)
+            .map(|permille| permille as f64 / 10.0)
+            .unwrap_or(100.0);
+        Self {
+            documented,
+            total,
+            percent,
+        }
+    }
+}
+
+/// Documentation coverage of types and free functions, and of their members.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CoverageReport {
+    pub symbols: Coverage,
+    pub members: Coverage,
+}
+
+impl CoverageReport {
+    /// Coverage of symbols left in document. Should be measured before undocumented symbols get
+    /// excluded, so they are counted in.
+    pub fn measure(document: &Document) -> Self {
+        let symbols = document
+            .enums
+            .iter()
+            .map(|item| &item.doc_comments)
+            .chain(document.structs.iter().map(|item| &item.doc_comments))
+            .chain(document.classes.iter().map(|item| &item.doc_comments))
+            .chain(document.functions.iter().map(|item| &item.doc_comments))
+            .chain(document.delegates.iter().map(|item| &item.doc_comments))
+            .collect::<Vec<_>>();
+        let members = document
+            .structs
+            .iter()
+            .chain(document.classes.iter())
+            .flat_map(|item| {
+                item.properties
+                    .iter()
+                    .map(|property| &property.doc_comments)
+                    .chain(item.methods.iter().map(|method| &method.doc_comments))
+            })
+            .collect::<Vec<_>>();
+        let coverage = |items: &[&Option<String>]| {
+            Coverage::new(
+                items.iter().filter(|docs| docs.is_some()).count(),
+                items.len(),
+            )
+        };
+        Self {
+            symbols: coverage(&symbols),
+            members: coverage(&members),
+        }
+    }
+}
+
+/// Machine-readable summary of single run, so documentation health can be tracked over time.
+#[derive(Debug, Default, Serialize)]
+pub struct RunReport {
+    pub generator: String,
+    /// Numbers of documented symbols by their kind, along with symbols left out.
+    pub symbols: BTreeMap<&'static str, usize>,
+    pub coverage: CoverageReport,
+    /// Numbers of baked pages by backend names.
+    pub pages: BTreeMap<String, usize>,
+    /// Numbers of warnings by their categories.
+    pub warnings: BTreeMap<&'static str, usize>,
+    /// Numbers of errors by their categories.
+    pub errors: BTreeMap<&'static str, usize>,
+    /// Durations of run phases in milliseconds, by phase names.
+    pub durations: BTreeMap<String, u128>,
+    #[serde(skip)]
+    diagnostics: BTreeSet<(&'static str, bool, String, String)>,
+}
+
+impl RunReport {
+    pub fn new(document: &Document, coverage: CoverageReport) -> Self {
+        Self {
+            generator: format!("unreal-doc {}", env!("CARGO_PKG_VERSION")),
+            symbols: BTreeMap::from([
+                ("enums", document.enums.len()),
+                ("structs", document.structs.len()),
+                ("classes", document.classes.len()),
+                ("functions", document.functions.len()),
+                ("delegates", document.delegates.len()),
+                ("skipped", document.skipped.len()),
+            ]),
+            coverage,
+            ..Default::default()
+        }
+    }
+
+    /// Counts given problems. The same problem found by more than one backend counts once.
+    pub fn add_diagnostics<'a>(&mut self, diagnostics: impl IntoIterator<Item = &'a Diagnostic>) {
+        for item in diagnostics {
+            let is_error = item.severity == Severity::Error;
+            if !self.diagnostics.insert((
+                item.category,
+                is_error,
+                item.page.to_owned(),
+                item.message.to_owned(),
+            )) {
+                continue;
+            }
+            let counts = if is_error {
+                &mut self.errors
+            } else {
+                &mut self.warnings
+            };
+            *counts.entry(item.category).or_default() += 1;
+        }
+    }
+
+    pub fn add_duration(&mut self, phase: impl ToString, duration: Duration) {
+        self.durations
+            .insert(phase.to_string(), duration.as_millis());
+    }
+
+    pub fn write(&self, path: &Path) {
+        let content = serde_json::to_string_pretty(self).expect("Could not serialize run report!");
+        ensure_dir(path);
+        write(path, content).unwrap_or_else(|_| panic!("Could not write run report: {:?}", path));
+    }
+}
+
+#[test]
+fn test_run_report() {
+    use crate::{
+        diagnostics::Diagnostics,
+        document::{Property, StructClass},
+    };
+
+    let document = Document {
+        classes: vec![StructClass {
+            name: "UFoo".to_owned(),
+            doc_comments: Some("Foo.".to_owned()),
+            properties: vec![
+                Property {
+                    name: "A".to_owned(),
+                    doc_comments: Some("A.".to_owned()),
+                    ..Default::default()
+                },
+                Property {
+                    name: "B".to_owned(),
+                    ..Default::default()
+                },
+                Property {
+                    name: "C".to_owned(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let coverage = CoverageReport::measure(&document);
+    assert_eq!(coverage.symbols, Coverage::new(1, 1));
+    assert_eq!(coverage.members.percent, 33.3);
+
+    let mut report = RunReport::new(&document, coverage);
+    let mut diagnostics = Diagnostics::default();
+    diagnostics.warning("misspelling", "Foo.h", "Misspelled word");
+    diagnostics.error("snippets", "index.md", "Missing snippet");
+    report.add_diagnostics(&diagnostics.items);
+    // the same problems found again by another backend
+    report.add_diagnostics(&diagnostics.items);
+    assert_eq!(report.symbols["classes"], 1);
+    assert_eq!(report.warnings, BTreeMap::from([("misspelling", 1)]));
+    assert_eq!(report.errors, BTreeMap::from([("snippets", 1)]));
+}
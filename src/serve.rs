@@ -0,0 +1,188 @@
+//! Preview server of baked documentation, used by `unreal-doc serve` command. Pages reload in
+//! browser whenever documentation gets baked again.
+
+use crate::config::{Backend, Config};
+use std::{
+    fs::read,
+    path::{Component, Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+use tiny_http::{Header, Request, Response, Server};
+
+/// Path polled by served pages to find out if documentation was baked again.
+const VERSION_PATH: &str = "/__unreal_doc/version";
+
+/// Script put at the end of served pages, reloading them once documentation is baked again.
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+    var version = null;
+    setInterval(function () {
+        fetch("/__unreal_doc/version")
+            .then(function (response) { return response.text(); })
+            .then(function (current) {
+                if (version !== null && current !== version) {
+                    location.reload();
+                }
+                version = current;
+            })
+            .catch(function () {});
+    }, 1000);
+})();
+</script>
+"#;
+
+/// Serves files of site directory over HTTP on localhost from background thread.
+pub struct PreviewServer {
+    version: Arc<AtomicUsize>,
+}
+
+impl PreviewServer {
+    /// Starts serving given directory on given port of localhost.
+    pub fn start(site_dir: PathBuf, port: u16) -> Self {
+        let server = Server::http(("127.0.0.1", port))
+            .unwrap_or_else(|error| panic!("Could not start server on port {}: {}", port, error));
+        let version = Arc::new(AtomicUsize::new(0));
+        let current = version.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                respond(request, &site_dir, current.load(Ordering::SeqCst));
+            }
+        });
+        println!("Serving documentation at http://127.0.0.1:{}/", port);
+        Self { version }
+    }
+
+    /// Makes pages open in browser reload.
+    pub fn reload(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Directory of site baked by first active backend that bakes one: built book of `MdBook`
+/// backend or `Html` site.
+pub fn site_dir(config: &Config) -> PathBuf {
+    let backends = config.active_backends();
+    let output_dir = |backend: &Backend| {
+        if backends.len() > 1 {
+            config.output_dir.join(backend.name().to_lowercase())
+        } else {
+            config.output_dir.to_owned()
+        }
+    };
+    for backend in &backends {
+        match backend {
+            Backend::MdBook => return output_dir(backend).join("book"),
+            Backend::Html => {
+                let marketplace = config
+                    .backend_html
+                    .as_ref()
+                    .is_some_and(|html| html.marketplace);
+                return if marketplace {
+                    output_dir(backend).join("docs")
+                } else {
+                    output_dir(backend)
+                };
+            }
+            _ => {}
+        }
+    }
+    panic!("Only documentation baked by `MdBook` or `Html` backend can be served!");
+}
+
+fn respond(request: Request, site_dir: &Path, version: usize) {
+    let url = request.url().split(['?', '#']).next().unwrap_or_default();
+    if url == VERSION_PATH {
+        let _ = request.respond(Response::from_string(version.to_string()));
+        return;
+    }
+    let path = percent_decode(url);
+    let relative = Path::new(path.trim_start_matches('/'));
+    // paths can not point outside of site directory
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        let _ = request.respond(Response::from_string("Forbidden").with_status_code(403));
+        return;
+    }
+    let mut path = site_dir.join(relative);
+    if path.is_dir() {
+        path = path.join("index.html");
+    }
+    let (path, status) = if path.is_file() {
+        (path, 200)
+    } else {
+        (site_dir.join("404.html"), 404)
+    };
+    let Ok(mut content) = read(&path) else {
+        let _ = request.respond(Response::from_string("Not found").with_status_code(404));
+        return;
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    if extension == "html" {
+        content.extend(RELOAD_SCRIPT.as_bytes());
+    }
+    let content_type = Header::from_bytes("Content-Type", content_type(extension))
+        .expect("Could not create content type header!");
+    let _ = request.respond(
+        Response::from_data(content)
+            .with_status_code(status)
+            .with_header(content_type),
+    );
+}
+
+fn content_type(extension: &str) -> &'static str {
+    match extension {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "txt" | "md" => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+fn percent_decode(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| content.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                result.push(byte);
+                index += 3;
+            }
+            None => {
+                result.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+#[test]
+fn test_percent_decode() {
+    assert_eq!(percent_decode("/My%20Page.html"), "/My Page.html");
+    assert_eq!(percent_decode("/100%.html"), "/100%.html");
+    assert_eq!(percent_decode("/%C5%BC.html"), "/ż.html");
+}
@@ -0,0 +1,144 @@
+use fs_extra::dir::{CopyOptions, copy};
+use std::{
+    error::Error,
+    fs::{create_dir_all, read_dir, remove_dir_all, rename},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// How many times swapping staged output in is retried when another run swapped its output in
+/// at the same time.
+const SWAP_ATTEMPTS: usize = 10;
+
+/// Temporary directory next to output directory, where whole output gets baked before it
+/// replaces output directory at once. Interrupted baking never leaves output half-written, and
+/// concurrent runs never mix their files, as each one stages into its own directory. Staging
+/// directory gets removed if baking fails before committing it, and output moved aside by run
+/// killed in the middle of committing gets restored by the next run.
+pub struct Staging {
+    target: PathBuf,
+    path: PathBuf,
+}
+
+impl Staging {
+    /// Starts staging of output directory, with its current content copied if it is kept.
+    pub fn begin(target: &Path, keep_existing: bool) -> Result<Self, Box<dyn Error>> {
+        restore_previous(target);
+        let path = sibling_path(target, "staging");
+        let _ = remove_dir_all(&path);
+        create_dir_all(&path).map_err(|error| {
+            format!("Could not create staging directory: {:?} ({})", path, error)
+        })?;
+        if keep_existing && target.is_dir() {
+            let mut options = CopyOptions::new();
+            options.overwrite = true;
+            options.content_only = true;
+            copy(target, &path, &options).map_err(|error| {
+                format!(
+                    "Could not copy existing output into staging directory: {:?} ({})",
+                    path, error
+                )
+            })?;
+        }
+        Ok(Self {
+            target: target.to_owned(),
+            path,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Replaces output directory with staged one.
+    pub fn commit(self) -> Result<(), Box<dyn Error>> {
+        let previous = sibling_path(&self.target, "previous");
+        for _ in 0..SWAP_ATTEMPTS {
+            let _ = remove_dir_all(&previous);
+            match rename(&self.target, &previous) {
+                Ok(_) => {}
+                Err(error) if error.kind() == ErrorKind::NotFound => {}
+                Err(error) => {
+                    return Err(format!(
+                        "Could not move previous output directory {:?} away: {}",
+                        self.target, error
+                    )
+                    .into());
+                }
+            }
+            if rename(&self.path, &self.target).is_ok() {
+                let _ = remove_dir_all(&previous);
+                return Ok(());
+            }
+        }
+        Err(format!(
+            "Could not move staged output {:?} into output directory: {:?}",
+            self.path, self.target
+        )
+        .into())
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.path);
+    }
+}
+
+/// Moves previous output back into missing output directory, when run got interrupted after
+/// moving it aside and before moving its staged output in. The latest one is restored when
+/// there are many.
+fn restore_previous(target: &Path) {
+    if target.exists() {
+        return;
+    }
+    let parent = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = read_dir(parent) else {
+        return;
+    };
+    let prefix = format!(".{}.previous-", file_name(target));
+    let latest = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter(|entry| entry.path().is_dir())
+        .max_by_key(|entry| {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        });
+    if let Some(entry) = latest {
+        let _ = rename(entry.path(), target);
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_owned())
+}
+
+/// Hidden path next to given one, unique to this process.
+fn sibling_path(path: &Path, kind: &str) -> PathBuf {
+    path.with_file_name(format!(".{}.{}-{}", file_name(path), kind, process::id()))
+}
+
+#[test]
+fn test_restore_previous() {
+    let root = std::env::temp_dir().join(format!("unreal-doc-staging-{}", process::id()));
+    let target = root.join("docs");
+    let _ = remove_dir_all(&root);
+    create_dir_all(root.join(".docs.previous-1")).unwrap();
+    std::fs::write(root.join(".docs.previous-1").join("index.md"), "# Index").unwrap();
+
+    let staging = Staging::begin(&target, true).unwrap();
+    assert!(staging.path().join("index.md").is_file());
+    assert!(!root.join(".docs.previous-1").exists());
+    staging.commit().unwrap();
+    assert!(target.join("index.md").is_file());
+    let _ = remove_dir_all(&root);
+}